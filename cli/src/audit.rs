@@ -0,0 +1,81 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sc_cli::{CliConfiguration, SharedParams};
+use sc_service::ChainSpec;
+use sp_runtime::BuildStorage;
+use structopt::StructOpt;
+
+use chainx_primitives::AssetId;
+use chainx_service::IdentifyVariant;
+use xpallet_assets::Module as XAssets;
+
+/// The `audit-assets` command used to check the total balance invariant of
+/// xpallet-assets against a state snapshot.
+///
+/// The snapshot is a chain spec with the raw storage, e.g., the output of `export-state`,
+/// which is passed via `--chain`.
+#[derive(Debug, StructOpt)]
+pub struct AuditAssetsCmd {
+    /// Only audit the given asset, all the assets are audited by default.
+    #[structopt(long = "asset-id", value_name = "ASSET_ID")]
+    pub asset_id: Option<AssetId>,
+
+    #[allow(missing_docs)]
+    #[structopt(flatten)]
+    pub shared_params: SharedParams,
+}
+
+impl AuditAssetsCmd {
+    /// Run the audit command.
+    pub fn run(&self, chain_spec: Box<dyn ChainSpec>) -> sc_cli::Result<()> {
+        if chain_spec.is_chainx() {
+            self.audit::<chainx_runtime::Runtime>(chain_spec)
+        } else if chain_spec.is_malan() {
+            self.audit::<malan_runtime::Runtime>(chain_spec)
+        } else {
+            self.audit::<dev_runtime::Runtime>(chain_spec)
+        }
+    }
+
+    /// Audits the assets in the storage built from `chain_spec` using the pallet of runtime `T`.
+    fn audit<T: xpallet_assets::Trait>(
+        &self,
+        chain_spec: Box<dyn ChainSpec>,
+    ) -> sc_cli::Result<()> {
+        let storage = chain_spec.build_storage().map_err(sc_cli::Error::Input)?;
+        let mismatches =
+            sp_io::TestExternalities::new(storage).execute_with(|| match self.asset_id {
+                Some(id) => {
+                    let mismatches = XAssets::<T>::audit_invariant(&id);
+                    if mismatches.is_empty() {
+                        Default::default()
+                    } else {
+                        vec![(id, mismatches)].into_iter().collect()
+                    }
+                }
+                None => XAssets::<T>::audit_all_invariants(),
+            });
+
+        if mismatches.is_empty() {
+            println!("The total balance invariant of all the audited assets holds.");
+            return Ok(());
+        }
+        for (id, mismatches) in mismatches {
+            for mismatch in mismatches {
+                println!(
+                    "asset_id:{}, asset_type:{:?}, recorded:{:?}, actual:{:?}",
+                    id, mismatch.asset_type, mismatch.recorded, mismatch.actual
+                );
+            }
+        }
+        Err(sc_cli::Error::Other(
+            "TotalAssetBalance does not match the sum of AssetBalance".into(),
+        ))
+    }
+}
+
+impl CliConfiguration for AuditAssetsCmd {
+    fn shared_params(&self) -> &SharedParams {
+        &self.shared_params
+    }
+}
@@ -385,6 +385,7 @@ fn build_genesis(
             assets_restrictions,
             endowed: assets_endowed,
         }),
+        xpallet_gateway_records: Some(dev::XGatewayRecordsConfig::default()),
         xpallet_gateway_common: Some(dev::XGatewayCommonConfig { trustees }),
         xpallet_gateway_bitcoin: Some(dev::XGatewayBitcoinConfig {
             genesis_trustees: btc_genesis_trustees,
@@ -645,6 +646,7 @@ fn mainnet_genesis(
             assets_restrictions,
             endowed: Default::default(),
         }),
+        xpallet_gateway_records: Some(chainx::XGatewayRecordsConfig::default()),
         xpallet_gateway_common: Some(chainx::XGatewayCommonConfig { trustees }),
         xpallet_gateway_bitcoin: Some(chainx::XGatewayBitcoinConfig {
             genesis_trustees: btc_genesis_trustees,
@@ -669,6 +671,7 @@ fn mainnet_genesis(
             glob_dist_ratio: (12, 88), // (Treasury, X-type Asset and Staking) = (12, 88)
             mining_ratio: (10, 90),    // (Asset Mining, Staking) = (10, 90)
             minimum_penalty: 100 * DOLLARS,
+            slash_defer_duration: 24, // Roughly one day as each era lasts for 12 sessions
             candidate_requirement: (100 * DOLLARS, 1_000 * DOLLARS), // Minimum value (self_bonded, total_bonded) to be a validator candidate
             ..Default::default()
         }),
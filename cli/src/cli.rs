@@ -50,6 +50,10 @@ pub enum Subcommand {
 
     /// Revert the chain to a previous state.
     Revert(sc_cli::RevertCmd),
+
+    /// Audit the total balance invariant of assets against a state snapshot.
+    #[structopt(name = "audit-assets")]
+    AuditAssets(crate::audit::AuditAssetsCmd),
 }
 
 #[allow(missing_docs)]
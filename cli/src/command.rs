@@ -328,6 +328,12 @@ pub fn run() -> sc_cli::Result<()> {
                 Ok((cmd.run(client, backend), task_manager))
             })
         }
+        Some(Subcommand::AuditAssets(cmd)) => {
+            let runner = cli.create_runner(cmd)?;
+            set_default_ss58_version(&runner.config().chain_spec);
+
+            runner.sync_run(|config| cmd.run(config.chain_spec))
+        }
     }
 }
 
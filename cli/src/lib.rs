@@ -5,6 +5,7 @@
 #![warn(missing_docs)]
 #![warn(unused_extern_crates)]
 
+mod audit;
 mod chain_spec;
 mod cli;
 mod command;
@@ -73,3 +73,15 @@ impl RegistrarHandler for Tuple {
         Ok(())
     }
 }
+
+/// Trait for checking whether an asset has been issued.
+pub trait AssetIssuance {
+    /// Returns true if the total issuance of the asset is not zero.
+    fn is_issued(asset_id: &AssetId) -> bool;
+}
+
+impl AssetIssuance for () {
+    fn is_issued(_asset_id: &AssetId) -> bool {
+        false
+    }
+}
@@ -31,6 +31,10 @@ use chainx_primitives::{AssetId, Decimals};
 //      thus we agree on the prefix:
 //      L_: use 0x90000000
 //      S_: use 0xa0000000
+//
+// 4. token issued by the users on ChainX.
+//      Anyone could register a new token on ChainX by reserving a deposit, these tokens use
+//      `0x02000000 | id` as the AssetId, and the id is allocated by the registrar in order.
 
 /// Native asset of ChainX.
 pub const PCX: AssetId = 0;
@@ -55,3 +59,9 @@ pub const S_DOT: AssetId = 0xa0000000 | X_DOT;
 const EXTEND: AssetId = 0x01000000;
 /// USDT asset in ChainX.
 pub const USDT: AssetId = EXTEND | 0x01;
+
+const USER_EXTEND: AssetId = 0x02000000;
+/// The first AssetId of the tokens issued by the users.
+pub const USER_ASSET_START: AssetId = USER_EXTEND | 0x01;
+/// The last AssetId of the tokens issued by the users.
+pub const USER_ASSET_END: AssetId = USER_EXTEND | 0x00ff_ffff;
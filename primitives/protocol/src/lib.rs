@@ -20,5 +20,11 @@ pub const ASSET_TOKEN_NAME_MAX_LEN: usize = 48;
 /// The maximum length of asset description
 pub const ASSET_DESC_MAX_LEN: usize = 128;
 
+/// The maximum length of asset contract address on the source chain
+pub const ASSET_CONTRACT_ADDRESS_MAX_LEN: usize = 128;
+
+/// The maximum length of asset logo uri and website url
+pub const ASSET_URL_MAX_LEN: usize = 256;
+
 /// The maximum length of memo
 pub const MEMO_MAX_LEN: usize = 80;
@@ -73,6 +73,9 @@ pub type Token = Vec<u8>;
 /// Asset token description
 pub type Desc = Vec<u8>;
 
+/// Asset metadata url, e.g., the logo or the website
+pub type Url = Vec<u8>;
+
 /// Referral ID of validator
 pub type ReferralId = Vec<u8>;
 
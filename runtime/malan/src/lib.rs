@@ -47,7 +47,9 @@ use sp_authority_discovery::AuthorityId as AuthorityDiscoveryId;
 
 use xpallet_dex_spot::{Depth, FullPairInfo, RpcOrder, TradingPairId};
 use xpallet_mining_asset::{MinerLedger, MiningAssetInfo, MiningDividendInfo};
-use xpallet_mining_staking::{NominatorInfo, NominatorLedger, ValidatorInfo};
+use xpallet_mining_staking::{
+    EraIndex, EraRewards, NominatorInfo, NominatorLedger, ValidatorEraRecord, ValidatorInfo,
+};
 use xpallet_support::traits::MultisigAddressFor;
 
 // A few exports that help ease life for downstream crates.
@@ -75,7 +77,8 @@ pub use xp_runtime::Memo;
 
 // xpallet re-exports
 pub use xpallet_assets::{
-    AssetInfo, AssetRestrictions, AssetType, Chain, TotalAssetInfo, WithdrawalLimit,
+    AssetInfo, AssetRestrictions, AssetType, Chain, TotalAssetInfo, TotalBalanceMismatch,
+    WithdrawalLimit,
 };
 #[cfg(feature = "std")]
 pub use xpallet_gateway_bitcoin::h256_rev;
@@ -87,7 +90,7 @@ pub use xpallet_gateway_common::{
     trustees,
     types::{GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig},
 };
-pub use xpallet_gateway_records::Withdrawal;
+pub use xpallet_gateway_records::{TxRecord, Withdrawal, WithdrawalCaps};
 pub use xpallet_mining_asset::MiningWeight;
 pub use xpallet_mining_staking::VoteWeight;
 
@@ -895,12 +898,16 @@ impl xpallet_system::Trait for Runtime {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = xp_protocol::PCX;
+    pub const AssetDeposit: Balance = 1000 * DOLLARS;
 }
 
 impl xpallet_assets_registrar::Trait for Runtime {
     type Event = Event;
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = XMiningAsset;
+    type AssetIssuance = XAssets;
     type WeightInfo = xpallet_assets_registrar::weights::SubstrateWeight<Runtime>;
 }
 
@@ -914,8 +921,16 @@ impl xpallet_assets::Trait for Runtime {
     type WeightInfo = xpallet_assets::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const WithdrawalWindow: BlockNumber = DAYS;
+    pub const MaxWithdrawalHistory: u32 = 100;
+}
+
 impl xpallet_gateway_records::Trait for Runtime {
     type Event = Event;
+    type WithdrawalWindow = WithdrawalWindow;
+    type MaxWithdrawalHistory = MaxWithdrawalHistory;
+    type WithdrawalFeeProvider = XGatewayCommon;
     type WeightInfo = xpallet_gateway_records::weights::SubstrateWeight<Runtime>;
 }
 
@@ -930,8 +945,7 @@ impl xpallet_gateway_common::Trait for Runtime {
     type Event = Event;
     type Validator = XStaking;
     type DetermineMultisigAddress = MultisigProvider;
-    type Bitcoin = XGatewayBitcoin;
-    type BitcoinTrustee = XGatewayBitcoin;
+    type Gateways = (XGatewayBitcoin, XGatewayEthereum, XGatewayPolkadot);
     type WeightInfo = xpallet_gateway_common::weights::SubstrateWeight<Runtime>;
 }
 
@@ -946,6 +960,31 @@ impl xpallet_gateway_bitcoin::Trait for Runtime {
     type WeightInfo = xpallet_gateway_bitcoin::weights::SubstrateWeight<Runtime>;
 }
 
+impl xpallet_gateway_ethereum::Trait for Runtime {
+    type Event = Event;
+    type TrusteeOrigin = EnsureSignedBy<
+        xpallet_gateway_ethereum::trustee::EthTrusteeMultisig<Runtime>,
+        AccountId,
+    >;
+    type WeightInfo = xpallet_gateway_ethereum::weights::SubstrateWeight<Runtime>;
+}
+
+parameter_types! {
+    /// The ss58 address version of Polkadot.
+    pub const DotNetworkPrefix: u8 = 0;
+}
+
+impl xpallet_gateway_polkadot::Trait for Runtime {
+    type Event = Event;
+    type NetworkPrefix = DotNetworkPrefix;
+    type TrusteeSessionProvider = xpallet_gateway_polkadot::trustee::DotTrusteeSessionManager<Runtime>;
+    type TrusteeOrigin = EnsureSignedBy<
+        xpallet_gateway_polkadot::trustee::DotTrusteeMultisig<Runtime>,
+        AccountId,
+    >;
+    type WeightInfo = xpallet_gateway_polkadot::weights::SubstrateWeight<Runtime>;
+}
+
 impl xpallet_dex_spot::Trait for Runtime {
     type Event = Event;
     type Price = Balance;
@@ -965,6 +1004,7 @@ parameter_types! {
     pub const MigrationSessionOffset: SessionIndex = 55533;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const StakingOffchainElection: bool = false;
 }
 
 impl xpallet_mining_staking::Trait for Runtime {
@@ -978,6 +1018,10 @@ impl xpallet_mining_staking::Trait for Runtime {
     type AssetMining = XMiningAsset;
     type DetermineRewardPotAccount =
         xpallet_mining_staking::SimpleValidatorRewardPotAccountDeterminer<Runtime>;
+    type SlashCancelOrigin = EnsureRootOrHalfCouncil;
+    type ElectionStrategy = xpallet_mining_staking::SequentialPhragmen;
+    type OffchainElection = StakingOffchainElection;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = xpallet_mining_staking::weights::SubstrateWeight<Runtime>;
 }
 
@@ -999,6 +1043,18 @@ impl xpallet_mining_asset::Trait for Runtime {
     type WeightInfo = xpallet_mining_asset::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+    pub const MiningPoolsModuleId: ModuleId = ModuleId(*b"pcx/pool");
+    pub const MaxPoolTargets: u32 = 16;
+}
+
+impl xpallet_mining_pools::Trait for Runtime {
+    type Event = Event;
+    type ModuleId = MiningPoolsModuleId;
+    type MaxTargets = MaxPoolTargets;
+    type WeightInfo = xpallet_mining_pools::weights::SubstrateWeight<Runtime>;
+}
+
 impl xpallet_genesis_builder::Trait for Runtime {}
 
 construct_runtime!(
@@ -1044,15 +1100,15 @@ construct_runtime!(
 
         // ChainX basics.
         XSystem: xpallet_system::{Module, Call, Storage, Event<T>, Config} = 24,
-        XAssetsRegistrar: xpallet_assets_registrar::{Module, Call, Storage, Event, Config} = 25,
+        XAssetsRegistrar: xpallet_assets_registrar::{Module, Call, Storage, Event<T>, Config} = 25,
         XAssets: xpallet_assets::{Module, Call, Storage, Event<T>, Config<T>} = 26,
 
         // Mining, must be after XAssets.
-        XStaking: xpallet_mining_staking::{Module, Call, Storage, Event<T>, Config<T>} = 27,
+        XStaking: xpallet_mining_staking::{Module, Call, Storage, Event<T>, Config<T>, ValidateUnsigned} = 27,
         XMiningAsset: xpallet_mining_asset::{Module, Call, Storage, Event<T>, Config<T>} = 28,
 
         // Crypto gateway stuff.
-        XGatewayRecords: xpallet_gateway_records::{Module, Call, Storage, Event<T>} = 29,
+        XGatewayRecords: xpallet_gateway_records::{Module, Call, Storage, Event<T>, Config} = 29,
         XGatewayCommon: xpallet_gateway_common::{Module, Call, Storage, Event<T>, Config<T>} = 30,
         XGatewayBitcoin: xpallet_gateway_bitcoin::{Module, Call, Storage, Event<T>, Config<T>} = 31,
 
@@ -1073,6 +1129,10 @@ construct_runtime!(
         Sudo: pallet_sudo::{Module, Call, Config<T>, Storage, Event<T>} = 36,
 
         Proxy: pallet_proxy::{Module, Call, Storage, Event<T>} = 37,
+
+        XGatewayEthereum: xpallet_gateway_ethereum::{Module, Call, Storage, Event<T>} = 38,
+        XGatewayPolkadot: xpallet_gateway_polkadot::{Module, Call, Storage, Event<T>} = 39,
+        XMiningPools: xpallet_mining_pools::{Module, Call, Storage, Event<T>} = 40,
     }
 );
 
@@ -1318,6 +1378,14 @@ impl_runtime_apis! {
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>> {
             XAssets::total_asset_infos()
         }
+
+        fn allowance(owner: AccountId, spender: AccountId, asset_id: AssetId) -> Balance {
+            XAssets::allowance((owner, spender), asset_id)
+        }
+
+        fn audit_invariant(asset_id: AssetId) -> Vec<TotalBalanceMismatch<Balance>> {
+            XAssets::audit_invariant(&asset_id)
+        }
     }
 
     impl xpallet_mining_staking_rpc_runtime_api::XStakingApi<Block, AccountId, Balance, VoteWeight, BlockNumber> for Runtime {
@@ -1336,6 +1404,12 @@ impl_runtime_apis! {
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber> {
             XStaking::nominator_info_of(who)
         }
+        fn era_rewards(era: EraIndex) -> EraRewards<AccountId, Balance> {
+            XStaking::era_rewards(era)
+        }
+        fn validator_history(who: AccountId, eras: u32) -> BTreeMap<EraIndex, ValidatorEraRecord<Balance>> {
+            XStaking::validator_history(who, eras)
+        }
     }
 
     impl xpallet_dex_spot_rpc_runtime_api::XSpotApi<Block, AccountId, Balance, BlockNumber, Balance> for Runtime {
@@ -1374,6 +1448,22 @@ impl_runtime_apis! {
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<u32, Withdrawal<AccountId, Balance, BlockNumber>> {
             XGatewayRecords::withdrawals_list_by_chain(chain)
         }
+
+        fn withdrawal_caps(asset_id: AssetId) -> Option<(WithdrawalCaps<Balance>, Balance)> {
+            XGatewayRecords::withdrawal_caps(asset_id)
+        }
+
+        fn withdrawal_list_of(who: AccountId, page: u32) -> BTreeMap<u32, Withdrawal<AccountId, Balance, BlockNumber>> {
+            XGatewayRecords::withdrawal_list_of(&who, page)
+        }
+
+        fn withdrawal_record(id: u32) -> Option<Withdrawal<AccountId, Balance, BlockNumber>> {
+            XGatewayRecords::withdrawal_record(id)
+        }
+
+        fn tx_record(chain: Chain, txid: Vec<u8>) -> Option<TxRecord<AccountId, Balance, BlockNumber>> {
+            XGatewayRecords::tx_record(chain, txid)
+        }
     }
 
     impl xpallet_gateway_common_rpc_runtime_api::XGatewayCommonApi<Block, AccountId, Balance> for Runtime {
@@ -1,11 +1,12 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_benchmarking::benchmarks;
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
 use frame_system::RawOrigin;
 
 use super::*;
 
 const ASSET_ID: AssetId = 8888;
+const SEED: u32 = 0;
 
 fn b_asset_info_test_data<T: Trait>() -> AssetInfo {
     AssetInfo::new::<T>(
@@ -18,6 +19,23 @@ fn b_asset_info_test_data<T: Trait>() -> AssetInfo {
     .unwrap()
 }
 
+fn b_user_asset_info_test_data<T: Trait>() -> AssetInfo {
+    AssetInfo::new::<T>(
+        b"user_token".to_vec(),
+        b"user_token_name".to_vec(),
+        Chain::ChainX,
+        8,
+        b"user_token_desc".to_vec(),
+    )
+    .unwrap()
+}
+
+fn create_funded_user<T: Trait>(name: &'static str, index: u32) -> T::AccountId {
+    let user = account(name, index, SEED);
+    T::DepositCurrency::make_free_balance_be(&user, T::AssetDeposit::get() * 10u32.into());
+    user
+}
+
 benchmarks! {
     _{
         // User account seed
@@ -56,15 +74,43 @@ benchmarks! {
         ASSET_ID,
         Some(b"new_token".to_vec()),
         Some(b"new_token_name".to_vec()),
-        Some(b"new_desc".to_vec())
+        Some(b"new_desc".to_vec()),
+        Some(b"0xdac17f958d2ee523a2206206994597c13d831ec7".to_vec()),
+        Some(b"https://chainx.org/logo.png".to_vec()),
+        Some(b"https://chainx.org".to_vec())
     )
     verify {
         let mut new_asset_info = asset_info.clone();
         new_asset_info.set_token(b"new_token".to_vec());
         new_asset_info.set_token_name(b"new_token_name".to_vec());
         new_asset_info.set_desc(b"new_desc".to_vec());
+        new_asset_info.set_contract_address(b"0xdac17f958d2ee523a2206206994597c13d831ec7".to_vec());
+        new_asset_info.set_logo_uri(b"https://chainx.org/logo.png".to_vec());
+        new_asset_info.set_website(b"https://chainx.org".to_vec());
         assert_eq!(AssetInfoOf::get(ASSET_ID).unwrap(), new_asset_info);
     }
+
+    create {
+        let caller: T::AccountId = whitelisted_caller();
+        T::DepositCurrency::make_free_balance_be(&caller, T::AssetDeposit::get() * 10u32.into());
+        let asset_info = b_user_asset_info_test_data::<T>();
+        let id = Module::<T>::next_user_asset_id();
+    }: _(RawOrigin::Signed(caller.clone()), asset_info.clone())
+    verify {
+        assert_eq!(AssetInfoOf::get(id), Some(asset_info));
+        assert_eq!(Module::<T>::asset_owner(&id), Some(caller));
+    }
+
+    transfer_ownership {
+        let owner = create_funded_user::<T>("owner", 0);
+        let new_owner = create_funded_user::<T>("new_owner", 0);
+        let new_owner_lookup = T::Lookup::unlookup(new_owner.clone());
+        let id = Module::<T>::next_user_asset_id();
+        Module::<T>::create(RawOrigin::Signed(owner.clone()).into(), b_user_asset_info_test_data::<T>())?;
+    }: _(RawOrigin::Signed(owner), id, new_owner_lookup)
+    verify {
+        assert_eq!(Module::<T>::asset_owner(&id), Some(new_owner));
+    }
 }
 
 #[cfg(test)]
@@ -80,6 +126,8 @@ mod tests {
             assert_ok!(test_benchmark_deregister::<Test>());
             assert_ok!(test_benchmark_recover::<Test>());
             assert_ok!(test_benchmark_update_asset_info::<Test>());
+            assert_ok!(test_benchmark_create::<Test>());
+            assert_ok!(test_benchmark_transfer_ownership::<Test>());
         });
     }
 }
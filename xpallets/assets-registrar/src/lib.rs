@@ -22,44 +22,71 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::Get,
+    traits::{BalanceStatus, Currency, Get, ReservableCurrency},
+    weights::Weight,
     IterableStorageMap,
 };
-use frame_system::ensure_root;
+use frame_system::{ensure_root, ensure_signed, RawOrigin};
+use sp_runtime::traits::{StaticLookup, Zero};
 
-use chainx_primitives::{AssetId, Desc, Token};
+use chainx_primitives::{AssetId, ChainAddress, Desc, Token, Url};
 use xp_logging::info;
+use xp_protocol::{USER_ASSET_END, USER_ASSET_START};
 
-pub use self::types::AssetInfo;
+use self::types::AssetInfoV1;
+pub use self::types::{AssetInfo, AssetOwnership, Releases};
 pub use self::weights::WeightInfo;
-pub use xp_assets_registrar::{Chain, RegistrarHandler};
+pub use xp_assets_registrar::{AssetIssuance, Chain, RegistrarHandler};
+
+pub type DepositBalanceOf<T> =
+    <<T as Trait>::DepositCurrency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
 pub trait Trait: frame_system::Trait {
     /// The overarching event type.
-    type Event: From<Event> + Into<<Self as frame_system::Trait>::Event>;
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
     /// Native asset Id.
     type NativeAssetId: Get<AssetId>;
 
+    /// The currency used to reserve the deposit of registering an asset permissionlessly.
+    type DepositCurrency: ReservableCurrency<Self::AccountId>;
+
+    /// The deposit reserved from the owner of an asset registered permissionlessly.
+    type AssetDeposit: Get<DepositBalanceOf<Self>>;
+
     /// Handler for doing stuff after the asset is registered/deregistered.
     type RegistrarHandler: RegistrarHandler;
 
+    /// Checker of the issuance of an asset, which prevents the issued asset registered
+    /// permissionlessly from being deregistered.
+    type AssetIssuance: AssetIssuance;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
 
 decl_event!(
     /// Event for the XAssetRegistrar Module
-    pub enum Event {
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = DepositBalanceOf<T>,
+    {
         /// A new asset was registered. [asset_id, has_mining_rights]
         Registered(AssetId, bool),
         /// A deregistered asset was recovered. [asset_id, has_mining_rights]
         Recovered(AssetId, bool),
         /// An asset was deregistered. [asset_id]
         Deregistered(AssetId),
+        /// A new asset was created by an account with the deposit reserved. [asset_id, owner, deposit]
+        Created(AssetId, AccountId, Balance),
+        /// The ownership of an asset was transferred. [asset_id, old_owner, new_owner]
+        OwnershipTransferred(AssetId, AccountId, AccountId),
+        /// The deposit of an asset was refunded to the owner. [asset_id, owner, deposit]
+        DepositRefunded(AssetId, AccountId, Balance),
     }
 );
 
@@ -74,6 +101,10 @@ decl_error! {
         InvalidAssetTokenNameLength,
         /// Desc length is zero or too long
         InvalidAssetDescLength,
+        /// Contract address length is too long
+        InvalidAssetContractAddressLength,
+        /// Url length is too long
+        InvalidAssetUrlLength,
         /// Text is invalid ASCII, only allow ASCII visible character [0x20, 0x7E]
         InvalidAscii,
         /// The asset already exists.
@@ -84,6 +115,16 @@ decl_error! {
         AssetAlreadyValid,
         /// The asset is invalid (not online).
         AssetIsInvalid,
+        /// The asset registered permissionlessly must be on ChainX.
+        InvalidUserAssetChain,
+        /// No more asset id for the asset registered permissionlessly.
+        UserAssetIdExhausted,
+        /// The sender is not the owner of the asset.
+        NotAssetOwner,
+        /// The token symbol is already used by another asset.
+        AssetTokenAlreadyExists,
+        /// The asset registered permissionlessly still has issuance.
+        AssetStillIssued,
     }
 }
 
@@ -95,11 +136,25 @@ decl_storage! {
         /// Asset info of each asset.
         pub AssetInfoOf get(fn asset_info_of): map hasher(twox_64_concat) AssetId => Option<AssetInfo>;
 
+        /// The map of token symbol to the asset id, the symbol of a deregistered asset is kept
+        /// unless it was registered permissionlessly.
+        pub AssetIdOfToken get(fn asset_id_of_token): map hasher(blake2_128_concat) Token => Option<AssetId>;
+
         /// The map of asset to the online state.
         pub AssetOnline get(fn asset_online): map hasher(twox_64_concat) AssetId => bool;
 
         /// The map of asset to the block number at which the asset was registered.
         pub RegisteredAt get(fn registered_at): map hasher(twox_64_concat) AssetId => T::BlockNumber;
+
+        /// The owner and deposit of each asset registered permissionlessly.
+        pub AssetOwnershipOf get(fn asset_ownership_of):
+            map hasher(twox_64_concat) AssetId => Option<AssetOwnership<T::AccountId, DepositBalanceOf<T>>>;
+
+        /// The asset id to be allocated for the next asset registered permissionlessly.
+        pub NextUserAssetId get(fn next_user_asset_id): AssetId = USER_ASSET_START;
+
+        /// Storage version of the module.
+        pub StorageVersion get(fn storage_version) build(|_: &GenesisConfig| Releases::V3_0_0): Releases;
     }
     add_extra_genesis {
         config(assets): Vec<(AssetId, AssetInfo, bool, bool)>;
@@ -124,6 +179,12 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        const AssetDeposit: DepositBalanceOf<T> = T::AssetDeposit::get();
+
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_asset_info().saturating_add(Self::migrate_token_index())
+        }
+
         /// Register a new foreign asset.
         ///
         /// This is a root-only operation.
@@ -139,6 +200,7 @@ decl_module! {
 
             asset.is_valid::<T>()?;
             ensure!(!Self::exists(&asset_id), Error::<T>::AssetAlreadyExists);
+            Self::ensure_token_available(asset.token(), &asset_id)?;
 
             info!(
                 "[register_asset] id:{}, info:{:?}, is_online:{}, has_mining_rights:{}",
@@ -147,7 +209,7 @@ decl_module! {
 
             Self::apply_register(asset_id, asset)?;
 
-            Self::deposit_event(Event::Registered(asset_id, has_mining_rights));
+            Self::deposit_event(Event::<T>::Registered(asset_id, has_mining_rights));
             T::RegistrarHandler::on_register(&asset_id, has_mining_rights)?;
 
             if !is_online {
@@ -157,20 +219,92 @@ decl_module! {
             Ok(())
         }
 
+        /// Register a new asset on ChainX permissionlessly, `AssetDeposit` is reserved from the sender.
+        ///
+        /// The asset id is allocated automatically. The sender becomes the owner of the asset,
+        /// who is able to mint/burn the asset, set the restrictions of the asset within bounds,
+        /// transfer the ownership and deregister the asset to get the deposit refunded.
+        #[weight = T::WeightInfo::create()]
+        pub fn create(origin, asset: AssetInfo) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            asset.is_valid::<T>()?;
+            ensure!(asset.chain() == Chain::ChainX, Error::<T>::InvalidUserAssetChain);
+
+            let asset_id = Self::next_available_user_asset_id()?;
+            Self::ensure_token_available(asset.token(), &asset_id)?;
+            let deposit = T::AssetDeposit::get();
+            T::DepositCurrency::reserve(&who, deposit)?;
+
+            info!(
+                "[create_asset] id:{}, info:{:?}, owner:{:?}, deposit:{:?}",
+                asset_id, asset, who, deposit
+            );
+
+            Self::apply_register(asset_id, asset)?;
+            NextUserAssetId::put(asset_id + 1);
+            AssetOwnershipOf::<T>::insert(asset_id, AssetOwnership { owner: who.clone(), deposit });
+
+            Self::deposit_event(Event::<T>::Created(asset_id, who, deposit));
+            Self::deposit_event(Event::<T>::Registered(asset_id, false));
+            T::RegistrarHandler::on_register(&asset_id, false)?;
+
+            Ok(())
+        }
+
+        /// Transfer the ownership of an asset registered permissionlessly to `new_owner`,
+        /// the deposit is moved to `new_owner` too.
+        ///
+        /// This is an owner-only operation.
+        #[weight = T::WeightInfo::transfer_ownership()]
+        pub fn transfer_ownership(
+            origin,
+            #[compact] id: AssetId,
+            new_owner: <T::Lookup as StaticLookup>::Source
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let new_owner = T::Lookup::lookup(new_owner)?;
+
+            let mut ownership = Self::asset_ownership_of(&id).ok_or(Error::<T>::NotAssetOwner)?;
+            ensure!(ownership.owner == who, Error::<T>::NotAssetOwner);
+            if who == new_owner {
+                return Ok(());
+            }
+
+            T::DepositCurrency::repatriate_reserved(
+                &who,
+                &new_owner,
+                ownership.deposit,
+                BalanceStatus::Reserved,
+            )?;
+            ownership.owner = new_owner.clone();
+            AssetOwnershipOf::<T>::insert(id, ownership);
+
+            Self::deposit_event(Event::<T>::OwnershipTransferred(id, who, new_owner));
+            Ok(())
+        }
+
         /// Deregister an asset with given `id`.
         ///
-        /// This asset will be marked as invalid.
+        /// This asset will be marked as invalid. The asset registered permissionlessly can only be
+        /// deregistered when its total issuance is zero, in which case the deposit is refunded
+        /// to the owner, the ownership is removed and the token symbol is released.
         ///
-        /// This is a root-only operation.
+        /// This is a root-only operation, or an owner-only operation for the asset
+        /// registered permissionlessly.
         #[weight = T::WeightInfo::deregister()]
         pub fn deregister(origin, #[compact] id: AssetId) -> DispatchResult {
-            ensure_root(origin)?;
+            Self::ensure_root_or_asset_owner(origin, &id)?;
 
             ensure!(Self::is_valid(&id), Error::<T>::AssetIsInvalid);
+            if Self::asset_ownership_of(&id).is_some() {
+                ensure!(!T::AssetIssuance::is_issued(&id), Error::<T>::AssetStillIssued);
+            }
 
             AssetOnline::remove(id);
+            Self::release_user_asset(&id);
 
-            Self::deposit_event(Event::Deregistered(id));
+            Self::deposit_event(Event::<T>::Deregistered(id));
             T::RegistrarHandler::on_deregister(&id)?;
 
             Ok(())
@@ -179,18 +313,22 @@ decl_module! {
         /// Recover a deregister asset to the valid state.
         ///
         /// `RegistrarHandler::on_register()` will be triggered again during the recover process.
+        /// The token symbol must not be taken by another asset in the meantime, the recovered
+        /// asset registered permissionlessly is managed by root since its ownership was removed.
         ///
         /// This is a root-only operation.
         #[weight = T::WeightInfo::recover()]
         pub fn recover(origin, #[compact] id: AssetId, has_mining_rights: bool) -> DispatchResult {
             ensure_root(origin)?;
 
-            ensure!(Self::exists(&id), Error::<T>::AssetDoesNotExist);
+            let info = Self::asset_info_of(&id).ok_or(Error::<T>::AssetDoesNotExist)?;
             ensure!(!Self::is_valid(&id), Error::<T>::AssetAlreadyValid);
+            Self::ensure_token_available(info.token(), &id)?;
 
             AssetOnline::insert(id, true);
+            AssetIdOfToken::insert(info.token(), id);
 
-            Self::deposit_event(Event::Recovered(id, has_mining_rights));
+            Self::deposit_event(Event::<T>::Recovered(id, has_mining_rights));
             T::RegistrarHandler::on_register(&id, has_mining_rights)?;
             Ok(())
         }
@@ -204,12 +342,17 @@ decl_module! {
             #[compact] id: AssetId,
             token: Option<Token>,
             token_name: Option<Token>,
-            desc: Option<Desc>
+            desc: Option<Desc>,
+            contract_address: Option<ChainAddress>,
+            logo_uri: Option<Url>,
+            website: Option<Url>
         ) -> DispatchResult {
             ensure_root(origin)?;
 
             let mut info = Self::asset_info_of(&id).ok_or(Error::<T>::AssetDoesNotExist)?;
+            let old_token = info.token().clone();
             if let Some(t) = token {
+                Self::ensure_token_available(&t, &id)?;
                 info.set_token(t)
             }
             if let Some(name) = token_name {
@@ -218,6 +361,20 @@ decl_module! {
             if let Some(desc) = desc {
                 info.set_desc(desc);
             }
+            if let Some(address) = contract_address {
+                info.set_contract_address(address);
+            }
+            if let Some(uri) = logo_uri {
+                info.set_logo_uri(uri);
+            }
+            if let Some(url) = website {
+                info.set_website(url);
+            }
+            info.is_valid::<T>()?;
+            if info.token() != &old_token {
+                AssetIdOfToken::remove(&old_token);
+                AssetIdOfToken::insert(info.token(), id);
+            }
             AssetInfoOf::insert(id, info);
             Ok(())
         }
@@ -296,6 +453,70 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Returns the owner of the asset registered permissionlessly.
+    pub fn asset_owner(id: &AssetId) -> Option<T::AccountId> {
+        Self::asset_ownership_of(id).map(|ownership| ownership.owner)
+    }
+
+    /// Helper function for checking the owner of the asset registered permissionlessly.
+    pub fn ensure_asset_owner(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        ensure!(
+            Self::asset_owner(id).as_ref() == Some(who),
+            Error::<T>::NotAssetOwner
+        );
+        Ok(())
+    }
+
+    fn ensure_root_or_asset_owner(origin: T::Origin, id: &AssetId) -> DispatchResult {
+        match origin.into() {
+            Ok(RawOrigin::Root) => Ok(()),
+            Ok(RawOrigin::Signed(who)) => Self::ensure_asset_owner(id, &who),
+            _ => Err(DispatchError::BadOrigin),
+        }
+    }
+
+    /// Helper function for checking the token symbol is not used by any asset other than `id`.
+    fn ensure_token_available(token: &Token, id: &AssetId) -> DispatchResult {
+        match Self::asset_id_of_token(token) {
+            Some(owner_id) if owner_id != *id => Err(Error::<T>::AssetTokenAlreadyExists.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the next asset id which is not occupied for the asset registered permissionlessly.
+    fn next_available_user_asset_id() -> Result<AssetId, DispatchError> {
+        let mut id = Self::next_user_asset_id();
+        // skip the ids which have been registered by root.
+        while Self::exists(&id) {
+            ensure!(id < USER_ASSET_END, Error::<T>::UserAssetIdExhausted);
+            id += 1;
+        }
+        ensure!(id <= USER_ASSET_END, Error::<T>::UserAssetIdExhausted);
+        Ok(id)
+    }
+
+    /// Removes the ownership of the asset registered permissionlessly and releases its token
+    /// symbol, the deposit is unreserved.
+    fn release_user_asset(id: &AssetId) {
+        let ownership = match AssetOwnershipOf::<T>::take(id) {
+            Some(ownership) => ownership,
+            None => return,
+        };
+        if let Some(info) = Self::asset_info_of(id) {
+            if Self::asset_id_of_token(info.token()) == Some(*id) {
+                AssetIdOfToken::remove(info.token());
+            }
+        }
+        if !ownership.deposit.is_zero() {
+            T::DepositCurrency::unreserve(&ownership.owner, ownership.deposit);
+            Self::deposit_event(Event::<T>::DepositRefunded(
+                *id,
+                ownership.owner,
+                ownership.deposit,
+            ));
+        }
+    }
+
     /// Actually register an asset.
     fn apply_register(id: AssetId, asset: AssetInfo) -> DispatchResult {
         let chain = asset.chain();
@@ -305,6 +526,7 @@ impl<T: Trait> Module<T> {
             }
         });
 
+        AssetIdOfToken::insert(asset.token(), id);
         AssetInfoOf::insert(&id, asset);
         AssetOnline::insert(&id, true);
 
@@ -313,3 +535,39 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 }
+
+impl<T: Trait> Module<T> {
+    /// Migrate `AssetInfoOf` to `Releases::V2_0_0`, the new metadata are left empty.
+    pub fn migrate_asset_info() -> Weight {
+        if Self::storage_version() != Releases::V1_0_0 {
+            return 0;
+        }
+        AssetInfoOf::translate::<AssetInfoV1, _>(|_, info| Some(info.upgrade()));
+        StorageVersion::put(Releases::V2_0_0);
+
+        let count = AssetInfoOf::iter().count() as Weight;
+        info!("[migrate_asset_info] {} asset infos migrated", count);
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    /// Migrate to `Releases::V3_0_0`, building the index `AssetIdOfToken` of the existing assets.
+    ///
+    /// The asset with the smaller id keeps the symbol if several assets share the same symbol.
+    pub fn migrate_token_index() -> Weight {
+        if Self::storage_version() != Releases::V2_0_0 {
+            return 0;
+        }
+        let mut assets = AssetInfoOf::iter().collect::<Vec<_>>();
+        assets.sort_by_key(|(id, _)| *id);
+        let count = assets.len() as Weight;
+        for (id, info) in assets {
+            if !AssetIdOfToken::contains_key(info.token()) {
+                AssetIdOfToken::insert(info.token(), id);
+            }
+        }
+        StorageVersion::put(Releases::V3_0_0);
+
+        info!("[migrate_token_index] {} asset tokens indexed", count);
+        T::DbWeight::get().reads_writes(2 * count + 1, count + 1)
+    }
+}
@@ -1,5 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use std::cell::RefCell;
+
 use frame_support::{
     assert_noop, assert_ok, impl_outer_event, impl_outer_origin, parameter_types, sp_io,
     weights::Weight,
@@ -17,7 +19,9 @@ use xp_protocol::X_BTC;
 use crate::*;
 
 /// The AccountId alias in this test module.
+pub(crate) type AccountId = u64;
 pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
 
 impl_outer_origin! {
     pub enum Origin for Test {}
@@ -32,7 +36,8 @@ mod xpallet_assets_metadata {
 impl_outer_event! {
     pub enum MetaEvent for Test {
         system<T>,
-        xpallet_assets_metadata,
+        pallet_balances<T>,
+        xpallet_assets_metadata<T>,
     }
 }
 
@@ -57,7 +62,7 @@ impl frame_system::Trait for Test {
     type BlockNumber = BlockNumber;
     type Hash = H256;
     type Hashing = BlakeTwo256;
-    type AccountId = u64;
+    type AccountId = AccountId;
     type Lookup = IdentityLookup<Self::AccountId>;
     type Header = Header;
     type Event = MetaEvent;
@@ -71,23 +76,61 @@ impl frame_system::Trait for Test {
     type AvailableBlockRatio = AvailableBlockRatio;
     type Version = ();
     type PalletInfo = ();
-    type AccountData = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
     type OnNewAccount = ();
     type OnKilledAccount = ();
     type SystemWeightInfo = ();
 }
 
+parameter_types! {
+    pub const ExistentialDeposit: Balance = 1;
+}
+
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = MetaEvent;
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
+}
+
+thread_local! {
+    static ISSUED: RefCell<bool> = RefCell::new(false);
+}
+
+pub struct MockAssetIssuance;
+impl MockAssetIssuance {
+    pub fn set_issued(issued: bool) {
+        ISSUED.with(|v| *v.borrow_mut() = issued);
+    }
+}
+impl AssetIssuance for MockAssetIssuance {
+    fn is_issued(_asset_id: &AssetId) -> bool {
+        ISSUED.with(|v| *v.borrow())
+    }
 }
 
 impl Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = ();
+    type AssetIssuance = MockAssetIssuance;
     type WeightInfo = ();
 }
 
+pub(crate) const ALICE: AccountId = 1;
+pub(crate) const BOB: AccountId = 2;
+pub(crate) const CHARLIE: AccountId = 3;
+
 pub struct ExtBuilder;
 impl Default for ExtBuilder {
     fn default() -> Self {
@@ -115,6 +158,11 @@ impl ExtBuilder {
             .build_storage::<Test>()
             .unwrap();
 
+        let _ = pallet_balances::GenesisConfig::<Test> {
+            balances: vec![(ALICE, 1000), (BOB, 1000), (CHARLIE, 50)],
+        }
+        .assimilate_storage(&mut storage);
+
         let _ = GenesisConfig { assets }.assimilate_storage::<Test>(&mut storage);
 
         let ext = sp_io::TestExternalities::new(storage);
@@ -131,6 +179,7 @@ impl ExtBuilder {
 }
 
 pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
 pub type XAssetsRegistrar = Module<Test>;
 pub type Err = Error<Test>;
 
@@ -185,3 +234,316 @@ fn test_register() {
         );
     })
 }
+
+fn user_asset() -> AssetInfo {
+    user_asset_of(b"USER")
+}
+
+fn user_asset_of(token: &[u8]) -> AssetInfo {
+    AssetInfo::new::<Test>(
+        token.to_vec(),
+        b"User Token".to_vec(),
+        Chain::ChainX,
+        8,
+        b"user token".to_vec(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_create() {
+    ExtBuilder::default().build_and_execute(|| {
+        let btc_asset = btc().1;
+        assert_noop!(
+            XAssetsRegistrar::create(Origin::signed(ALICE), btc_asset),
+            Err::InvalidUserAssetChain
+        );
+        assert_noop!(
+            XAssetsRegistrar::create(Origin::signed(CHARLIE), user_asset()),
+            pallet_balances::Error::<Test>::InsufficientBalance
+        );
+
+        // the id registered by root is skipped.
+        assert_ok!(XAssetsRegistrar::register(
+            Origin::root(),
+            USER_ASSET_START,
+            user_asset_of(b"ROOT"),
+            true,
+            false
+        ));
+        assert_ok!(XAssetsRegistrar::create(
+            Origin::signed(ALICE),
+            user_asset()
+        ));
+        let id = USER_ASSET_START + 1;
+        assert_eq!(XAssetsRegistrar::next_user_asset_id(), id + 1);
+        assert_eq!(XAssetsRegistrar::asset_owner(&id), Some(ALICE));
+        assert_eq!(XAssetsRegistrar::get_asset_info(&id), Ok(user_asset()));
+        assert_eq!(Balances::reserved_balance(ALICE), 100);
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(XAssetsRegistrar::asset_owner(&USER_ASSET_START), None);
+
+        // the token symbol must be unique.
+        assert_noop!(
+            XAssetsRegistrar::create(Origin::signed(BOB), user_asset()),
+            Err::AssetTokenAlreadyExists
+        );
+        assert_noop!(
+            XAssetsRegistrar::create(Origin::signed(BOB), user_asset_of(b"X-BTC")),
+            Err::AssetTokenAlreadyExists
+        );
+        assert_noop!(
+            XAssetsRegistrar::register(Origin::root(), 100, user_asset(), true, false),
+            Err::AssetTokenAlreadyExists
+        );
+        assert_ok!(XAssetsRegistrar::create(
+            Origin::signed(BOB),
+            user_asset_of(b"USER2")
+        ));
+        assert_eq!(XAssetsRegistrar::asset_owner(&(id + 1)), Some(BOB));
+        assert_eq!(
+            XAssetsRegistrar::asset_id_of_token(b"USER2".to_vec()),
+            Some(id + 1)
+        );
+    })
+}
+
+#[test]
+fn test_transfer_ownership() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssetsRegistrar::create(
+            Origin::signed(ALICE),
+            user_asset()
+        ));
+        let id = USER_ASSET_START;
+
+        assert_noop!(
+            XAssetsRegistrar::transfer_ownership(Origin::signed(BOB), id, BOB),
+            Err::NotAssetOwner
+        );
+        assert_noop!(
+            XAssetsRegistrar::transfer_ownership(Origin::signed(ALICE), X_BTC, BOB),
+            Err::NotAssetOwner
+        );
+
+        assert_ok!(XAssetsRegistrar::transfer_ownership(
+            Origin::signed(ALICE),
+            id,
+            BOB
+        ));
+        assert_eq!(XAssetsRegistrar::asset_owner(&id), Some(BOB));
+        // the deposit is moved to the new owner.
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 900);
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+        assert_eq!(Balances::free_balance(BOB), 1000);
+    })
+}
+
+#[test]
+fn test_deregister_user_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssetsRegistrar::create(
+            Origin::signed(ALICE),
+            user_asset()
+        ));
+        let id = USER_ASSET_START;
+
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::signed(BOB), id),
+            Err::NotAssetOwner
+        );
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::signed(ALICE), X_BTC),
+            Err::NotAssetOwner
+        );
+
+        // the issued asset can not be deregistered.
+        MockAssetIssuance::set_issued(true);
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::signed(ALICE), id),
+            Err::AssetStillIssued
+        );
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::root(), id),
+            Err::AssetStillIssued
+        );
+        MockAssetIssuance::set_issued(false);
+
+        // the deposit is refunded, the ownership and the token symbol are released.
+        assert_ok!(XAssetsRegistrar::deregister(Origin::signed(ALICE), id));
+        assert!(!XAssetsRegistrar::is_valid(&id));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(XAssetsRegistrar::asset_ownership_of(&id), None);
+        assert_eq!(XAssetsRegistrar::asset_id_of_token(b"USER".to_vec()), None);
+
+        // the freed symbol can be registered again.
+        assert_ok!(XAssetsRegistrar::create(Origin::signed(BOB), user_asset()));
+        let new_id = id + 1;
+        assert_eq!(XAssetsRegistrar::asset_owner(&new_id), Some(BOB));
+        assert_eq!(
+            XAssetsRegistrar::asset_id_of_token(b"USER".to_vec()),
+            Some(new_id)
+        );
+        assert_eq!(Balances::reserved_balance(BOB), 100);
+
+        // the deregistered asset can not be recovered with the symbol taken.
+        assert_noop!(
+            XAssetsRegistrar::recover(Origin::root(), id, false),
+            Err::AssetTokenAlreadyExists
+        );
+        assert_ok!(XAssetsRegistrar::deregister(Origin::signed(BOB), new_id));
+        assert_eq!(Balances::reserved_balance(BOB), 0);
+
+        // recovered by root without the ownership, the deposit would not be refunded twice.
+        assert_ok!(XAssetsRegistrar::recover(Origin::root(), id, false));
+        assert_eq!(
+            XAssetsRegistrar::asset_id_of_token(b"USER".to_vec()),
+            Some(id)
+        );
+        assert_eq!(XAssetsRegistrar::asset_owner(&id), None);
+        assert_ok!(XAssetsRegistrar::deregister(Origin::root(), id));
+        assert_eq!(Balances::free_balance(ALICE), 1000);
+        assert_eq!(Balances::free_balance(BOB), 1000);
+    })
+}
+
+#[test]
+fn test_update_asset_info() {
+    ExtBuilder::default().build_and_execute(|| {
+        let usdt = b"0xdac17f958d2ee523a2206206994597c13d831ec7".to_vec();
+        assert_ok!(XAssetsRegistrar::update_asset_info(
+            Origin::root(),
+            X_BTC,
+            None,
+            None,
+            None,
+            Some(usdt.clone()),
+            Some(b"https://chainx.org/logo.png".to_vec()),
+            Some(b"https://chainx.org".to_vec())
+        ));
+        let info = XAssetsRegistrar::asset_info_of(X_BTC).unwrap();
+        assert_eq!(info.token(), &b"X-BTC".to_vec());
+        assert_eq!(info.contract_address(), &usdt);
+        assert_eq!(info.logo_uri(), &b"https://chainx.org/logo.png".to_vec());
+        assert_eq!(info.website(), &b"https://chainx.org".to_vec());
+
+        assert_ok!(XAssetsRegistrar::create(
+            Origin::signed(ALICE),
+            user_asset()
+        ));
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                Some(b"USER".to_vec()),
+                None,
+                None,
+                None,
+                None,
+                None
+            ),
+            Err::AssetTokenAlreadyExists
+        );
+        assert_ok!(XAssetsRegistrar::update_asset_info(
+            Origin::root(),
+            X_BTC,
+            Some(b"XBTC".to_vec()),
+            None,
+            None,
+            None,
+            None,
+            None
+        ));
+        assert_eq!(XAssetsRegistrar::asset_id_of_token(b"X-BTC".to_vec()), None);
+        assert_eq!(
+            XAssetsRegistrar::asset_id_of_token(b"XBTC".to_vec()),
+            Some(X_BTC)
+        );
+
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                None,
+                None,
+                None,
+                Some(vec![b'a'; 129]),
+                None,
+                None
+            ),
+            Err::InvalidAssetContractAddressLength
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                None,
+                None,
+                None,
+                None,
+                Some(vec![b'a'; 257]),
+                None
+            ),
+            Err::InvalidAssetUrlLength
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(b"https://chainx.org/<script>".to_vec())
+            ),
+            DispatchError::Other("'<' and '>' are not allowed, which could be abused off-chain.")
+        );
+        assert_noop!(
+            XAssetsRegistrar::update_asset_info(
+                Origin::root(),
+                X_BTC,
+                None,
+                None,
+                None,
+                None,
+                Some(vec![b'a', 0x80]),
+                None
+            ),
+            Err::InvalidAscii
+        );
+    })
+}
+
+#[test]
+fn test_migrate_asset_info() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the same layout as `AssetInfoV1`
+        let old_info = (
+            b"ABC".to_vec(),
+            b"ABC Token".to_vec(),
+            Chain::Ethereum,
+            18u8,
+            b"abc".to_vec(),
+        );
+        frame_support::storage::unhashed::put(&AssetInfoOf::hashed_key_for(100), &old_info);
+        StorageVersion::put(Releases::V1_0_0);
+
+        XAssetsRegistrar::migrate_asset_info();
+        assert_eq!(XAssetsRegistrar::storage_version(), Releases::V2_0_0);
+        XAssetsRegistrar::migrate_token_index();
+        assert_eq!(XAssetsRegistrar::storage_version(), Releases::V3_0_0);
+        assert_eq!(
+            XAssetsRegistrar::asset_id_of_token(b"ABC".to_vec()),
+            Some(100)
+        );
+        let info = XAssetsRegistrar::asset_info_of(100).unwrap();
+        assert_eq!(info.token(), &b"ABC".to_vec());
+        assert_eq!(info.chain(), Chain::Ethereum);
+        assert_eq!(info.decimals(), 18);
+        assert!(info.contract_address().is_empty());
+        assert!(info.website().is_empty());
+    })
+}
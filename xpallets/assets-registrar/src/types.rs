@@ -5,9 +5,10 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_runtime::RuntimeDebug;
 use sp_std::fmt;
 
-use chainx_primitives::{Decimals, Desc, Token};
+use chainx_primitives::{ChainAddress, Decimals, Desc, Token, Url};
 use xp_assets_registrar::Chain;
 
 use crate::verifier::*;
@@ -25,6 +26,22 @@ pub struct AssetInfo {
     decimals: Decimals,
     #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
     desc: Desc,
+    /// The contract or asset address on the source chain, empty for the native token.
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text", default))]
+    contract_address: ChainAddress,
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text", default))]
+    logo_uri: Url,
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text", default))]
+    website: Url,
+}
+
+/// The owner of an asset registered permissionlessly, and the deposit reserved from the owner.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AssetOwnership<AccountId, Balance> {
+    pub owner: AccountId,
+    pub deposit: Balance,
 }
 
 impl fmt::Debug for AssetInfo {
@@ -36,6 +53,12 @@ impl fmt::Debug for AssetInfo {
             .field("chain", &self.chain)
             .field("decimals", &self.decimals)
             .field("desc", &String::from_utf8_lossy(&self.desc))
+            .field(
+                "contract_address",
+                &String::from_utf8_lossy(&self.contract_address),
+            )
+            .field("logo_uri", &String::from_utf8_lossy(&self.logo_uri))
+            .field("website", &String::from_utf8_lossy(&self.website))
             .finish()
     }
     #[cfg(not(feature = "std"))]
@@ -58,6 +81,9 @@ impl AssetInfo {
             chain,
             decimals,
             desc,
+            contract_address: ChainAddress::default(),
+            logo_uri: Url::default(),
+            website: Url::default(),
         };
         asset.is_valid::<T>()?;
         Ok(asset)
@@ -66,7 +92,10 @@ impl AssetInfo {
     pub fn is_valid<T: Trait>(&self) -> DispatchResult {
         is_valid_token::<T>(&self.token)?;
         is_valid_token_name::<T>(&self.token_name)?;
-        is_valid_desc::<T>(&self.desc)
+        is_valid_desc::<T>(&self.desc)?;
+        is_valid_contract_address::<T>(&self.contract_address)?;
+        is_valid_url::<T>(&self.logo_uri)?;
+        is_valid_url::<T>(&self.website)
     }
 
     pub fn token(&self) -> &Token {
@@ -89,6 +118,18 @@ impl AssetInfo {
         self.decimals
     }
 
+    pub fn contract_address(&self) -> &ChainAddress {
+        &self.contract_address
+    }
+
+    pub fn logo_uri(&self) -> &Url {
+        &self.logo_uri
+    }
+
+    pub fn website(&self) -> &Url {
+        &self.website
+    }
+
     pub fn set_desc(&mut self, desc: Desc) {
         self.desc = desc
     }
@@ -100,4 +141,58 @@ impl AssetInfo {
     pub fn set_token_name(&mut self, token_name: Token) {
         self.token_name = token_name
     }
+
+    pub fn set_contract_address(&mut self, contract_address: ChainAddress) {
+        self.contract_address = contract_address
+    }
+
+    pub fn set_logo_uri(&mut self, logo_uri: Url) {
+        self.logo_uri = logo_uri
+    }
+
+    pub fn set_website(&mut self, website: Url) {
+        self.website = website
+    }
+}
+
+/// The layout of `AssetInfo` before the metadata extensions were added.
+#[derive(Encode, Decode)]
+pub(crate) struct AssetInfoV1 {
+    token: Token,
+    token_name: Token,
+    chain: Chain,
+    decimals: Decimals,
+    desc: Desc,
+}
+
+impl AssetInfoV1 {
+    /// Upgrade to the current layout, the new metadata are left empty.
+    pub(crate) fn upgrade(self) -> AssetInfo {
+        AssetInfo {
+            token: self.token,
+            token_name: self.token_name,
+            chain: self.chain,
+            decimals: self.decimals,
+            desc: self.desc,
+            contract_address: ChainAddress::default(),
+            logo_uri: Url::default(),
+            website: Url::default(),
+        }
+    }
+}
+
+/// The storage version of the XAssetsRegistrar module.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    V1_0_0,
+    /// `AssetInfo` has the `contract_address`, `logo_uri` and `website` fields.
+    V2_0_0,
+    /// The token symbol of each asset is indexed by `AssetIdOfToken`.
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
 }
@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use xp_protocol::{ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN, ASSET_TOKEN_SYMBOL_MAX_LEN};
+use xp_protocol::{
+    ASSET_CONTRACT_ADDRESS_MAX_LEN, ASSET_DESC_MAX_LEN, ASSET_TOKEN_NAME_MAX_LEN,
+    ASSET_TOKEN_SYMBOL_MAX_LEN, ASSET_URL_MAX_LEN,
+};
 
 use super::*;
 
@@ -46,6 +49,35 @@ pub fn is_valid_desc<T: Trait>(desc: &[u8]) -> DispatchResult {
     Ok(())
 }
 
+/// A valid contract address should be visible ASCII chars only and not too long, empty
+/// means the asset is the native token of the source chain.
+pub fn is_valid_contract_address<T: Trait>(address: &[u8]) -> DispatchResult {
+    if address.len() > ASSET_CONTRACT_ADDRESS_MAX_LEN {
+        return Err(Error::<T>::InvalidAssetContractAddressLength.into());
+    }
+    xp_runtime::xss_check(address)?;
+    for c in address {
+        if !is_ascii_visible(c) {
+            return Err(Error::<T>::InvalidAscii.into());
+        }
+    }
+    Ok(())
+}
+
+/// A valid url should be visible ASCII chars only and not too long, empty means not set.
+pub fn is_valid_url<T: Trait>(url: &[u8]) -> DispatchResult {
+    if url.len() > ASSET_URL_MAX_LEN {
+        return Err(Error::<T>::InvalidAssetUrlLength.into());
+    }
+    xp_runtime::xss_check(url)?;
+    for c in url {
+        if !is_ascii_visible(c) {
+            return Err(Error::<T>::InvalidAscii.into());
+        }
+    }
+    Ok(())
+}
+
 /// Visible ASCII char [0x20, 0x7E]
 #[inline]
 fn is_ascii_visible(c: &u8) -> bool {
@@ -34,6 +34,8 @@ pub trait WeightInfo {
     fn deregister() -> Weight;
     fn recover() -> Weight;
     fn update_asset_info() -> Weight;
+    fn create() -> Weight;
+    fn transfer_ownership() -> Weight;
 }
 
 /// Weights for xpallet_assets_registrar using the Substrate node and recommended hardware.
@@ -59,6 +61,16 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn create() -> Weight {
+        (98_318_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn transfer_ownership() -> Weight {
+        (52_603_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -83,4 +95,14 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn create() -> Weight {
+        (98_318_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn transfer_ownership() -> Weight {
+        (52_603_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }
@@ -3,14 +3,16 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
 
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use codec::Codec;
 
 pub use chainx_primitives::AssetId;
-pub use xpallet_assets::{AssetType, TotalAssetInfo};
+pub use xpallet_assets::{AssetType, TotalAssetInfo, TotalBalanceMismatch};
 
 sp_api::decl_runtime_apis! {
+    /// The version 2 adds `allowance` and `audit_invariant`.
+    #[api_version(2)]
     pub trait XAssetsApi<AccountId, Balance>
     where
         AccountId: Codec,
@@ -19,5 +21,11 @@ sp_api::decl_runtime_apis! {
         fn assets_for_account(who: AccountId) -> BTreeMap<AssetId, BTreeMap<AssetType, Balance>>;
 
         fn assets() -> BTreeMap<AssetId, TotalAssetInfo<Balance>>;
+
+        /// Returns the balance of the asset which the spender is allowed to transfer from the owner.
+        fn allowance(owner: AccountId, spender: AccountId, asset_id: AssetId) -> Balance;
+
+        /// Returns the asset types whose total balance does not match the sum of all the accounts.
+        fn audit_invariant(asset_id: AssetId) -> Vec<TotalBalanceMismatch<Balance>>;
     }
 }
@@ -18,7 +18,7 @@ use sp_runtime::{
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance};
 
 use xpallet_assets_rpc_runtime_api::{
-    AssetId, AssetType, TotalAssetInfo, XAssetsApi as XAssetsRuntimeApi,
+    AssetId, AssetType, TotalAssetInfo, TotalBalanceMismatch, XAssetsApi as XAssetsRuntimeApi,
 };
 
 pub struct Assets<C, B> {
@@ -55,6 +55,24 @@ where
         &self,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<AssetId, TotalAssetInfo<RpcBalance<Balance>>>>;
+
+    /// Return the balance of the asset which the spender is allowed to transfer from the owner.
+    #[rpc(name = "xassets_getAllowance")]
+    fn allowance(
+        &self,
+        owner: AccountId,
+        spender: AccountId,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<RpcBalance<Balance>>;
+
+    /// Return the asset types whose total balance does not match the sum of all the accounts.
+    #[rpc(name = "xassets_auditInvariant")]
+    fn audit_invariant(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<Vec<TotalBalanceMismatch<RpcBalance<Balance>>>>;
 }
 
 impl<C, Block, AccountId, Balance> XAssetsApi<<Block as BlockT>::Hash, AccountId, Balance>
@@ -126,4 +144,38 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+    fn allowance(
+        &self,
+        owner: AccountId,
+        spender: AccountId,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<RpcBalance<Balance>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.allowance(&at, owner, spender, asset_id)
+            .map(Into::into)
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn audit_invariant(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<TotalBalanceMismatch<RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.audit_invariant(&at, asset_id)
+            .map(|mismatches| {
+                mismatches
+                    .into_iter()
+                    .map(|mismatch| TotalBalanceMismatch {
+                        asset_type: mismatch.asset_type,
+                        recorded: mismatch.recorded.into(),
+                        actual: mismatch.actual.into(),
+                    })
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
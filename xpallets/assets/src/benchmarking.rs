@@ -10,6 +10,25 @@ use crate::Module as XAssets;
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
 const SEED: u32 = 0;
 
+fn create_user_asset<T: Trait>(owner: &T::AccountId) -> AssetId {
+    let deposit = <T as xpallet_assets_registrar::Trait>::AssetDeposit::get();
+    <T as xpallet_assets_registrar::Trait>::DepositCurrency::make_free_balance_be(
+        owner,
+        deposit * 10u32.into(),
+    );
+    let asset = AssetInfo::new::<T>(
+        b"USER".to_vec(),
+        b"User Token".to_vec(),
+        Chain::ChainX,
+        8,
+        b"user token".to_vec(),
+    )
+    .unwrap();
+    xpallet_assets_registrar::Module::<T>::create(RawOrigin::Signed(owner.clone()).into(), asset)
+        .unwrap();
+    xpallet_assets_registrar::Module::<T>::next_user_asset_id() - 1
+}
+
 benchmarks! {
     _{ }
 
@@ -26,6 +45,119 @@ benchmarks! {
         assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), transfer_amount);
     }
 
+    transfer_with_memo {
+        let caller = whitelisted_caller();
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &caller, transfer_amount).unwrap();
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+        let memo: Memo = vec![b'1'; 128].into();
+    }: _(RawOrigin::Signed(caller.clone()), recipient_lookup, ASSET_ID, transfer_amount, memo)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), Zero::zero());
+        assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), transfer_amount);
+    }
+
+    batch_transfer {
+        let n in 1 .. 100;
+
+        let caller = whitelisted_caller();
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &caller, transfer_amount * n.into()).unwrap();
+
+        let transfers = (0..n)
+            .map(|i| {
+                let recipient: T::AccountId = account("recipient", i, SEED);
+                (T::Lookup::unlookup(recipient), transfer_amount)
+            })
+            .collect::<Vec<_>>();
+    }: _(RawOrigin::Signed(caller.clone()), ASSET_ID, transfers)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&caller, &ASSET_ID), Zero::zero());
+        let recipient: T::AccountId = account("recipient", n - 1, SEED);
+        assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), transfer_amount);
+    }
+
+    approve {
+        let caller: T::AccountId = whitelisted_caller();
+        let spender: T::AccountId = account("spender", 0, SEED);
+        let spender_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(spender.clone());
+        let value: BalanceOf<T> = (100000000 * 10_u32).into();
+    }: _(RawOrigin::Signed(caller.clone()), spender_lookup, ASSET_ID, value)
+    verify {
+        assert_eq!(XAssets::<T>::allowance((&caller, &spender), ASSET_ID), value);
+    }
+
+    transfer_from {
+        let owner: T::AccountId = account("owner", 0, SEED);
+        let owner_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(owner.clone());
+        let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &owner, transfer_amount).unwrap();
+
+        let caller: T::AccountId = whitelisted_caller();
+        let caller_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(caller.clone());
+        XAssets::<T>::approve(
+            RawOrigin::Signed(owner.clone()).into(),
+            caller_lookup,
+            ASSET_ID,
+            transfer_amount,
+        )
+        .unwrap();
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+    }: _(RawOrigin::Signed(caller.clone()), owner_lookup, recipient_lookup, ASSET_ID, transfer_amount)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&recipient, &ASSET_ID), transfer_amount);
+        assert_eq!(XAssets::<T>::allowance((&owner, &caller), ASSET_ID), Zero::zero());
+    }
+
+    vest {
+        let caller: T::AccountId = whitelisted_caller();
+        let source: T::AccountId = account("source", 0, SEED);
+        let locked: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &source, locked).unwrap();
+        let schedule = VestingSchedule {
+            locked,
+            per_block: 100000000.into(),
+            start: 0.into(),
+            cliff: 0.into(),
+        };
+        XAssets::<T>::vested_transfer(
+            RawOrigin::Root.into(),
+            T::Lookup::unlookup(source),
+            T::Lookup::unlookup(caller.clone()),
+            ASSET_ID,
+            schedule,
+        )
+        .unwrap();
+        frame_system::Module::<T>::set_block_number(5.into());
+    }: _(RawOrigin::Signed(caller.clone()), ASSET_ID)
+    verify {
+        assert_eq!(XAssets::<T>::locked_balance(&caller, &ASSET_ID), (100000000 * 5_u32).into());
+    }
+
+    vested_transfer {
+        let source: T::AccountId = account("source", 0, SEED);
+        let source_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(source.clone());
+        let locked: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
+        XAssets::<T>::issue(&ASSET_ID, &source, locked).unwrap();
+
+        let recipient: T::AccountId = account("recipient", 0, SEED);
+        let recipient_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(recipient.clone());
+        let schedule = VestingSchedule {
+            locked,
+            per_block: 100000000.into(),
+            start: 10.into(),
+            cliff: 0.into(),
+        };
+    }: _(RawOrigin::Root, source_lookup, recipient_lookup, ASSET_ID, schedule)
+    verify {
+        assert_eq!(XAssets::<T>::locked_balance(&recipient, &ASSET_ID), locked);
+        assert_eq!(XAssets::<T>::vesting(&recipient, ASSET_ID), Some(schedule));
+    }
+
     force_transfer {
         let caller = whitelisted_caller();
         let transfer_amount: BalanceOf<T> = (100000000 * 10_u32).into(); // e.g. 10 btc
@@ -62,6 +194,53 @@ benchmarks! {
     verify {
         assert_eq!(XAssets::<T>::asset_restrictions_of(&ASSET_ID), res);
     }
+
+    freeze {
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+    }: _(RawOrigin::Root, user_lookup, ASSET_ID)
+    verify {
+        assert!(XAssets::<T>::is_frozen(ASSET_ID, &user));
+    }
+
+    thaw {
+        let user: T::AccountId = account("user", 0, SEED);
+        let user_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(user.clone());
+        XAssets::<T>::freeze(RawOrigin::Root.into(), user_lookup.clone(), ASSET_ID).unwrap();
+    }: _(RawOrigin::Root, user_lookup, ASSET_ID)
+    verify {
+        assert!(!XAssets::<T>::is_frozen(ASSET_ID, &user));
+    }
+
+    mint {
+        let owner: T::AccountId = whitelisted_caller();
+        let id = create_user_asset::<T>(&owner);
+        let value: BalanceOf<T> = 100_000_000u32.into();
+        let dest: T::AccountId = account("dest", 0, SEED);
+        let dest_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(dest.clone());
+    }: _(RawOrigin::Signed(owner), id, dest_lookup, value)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&dest, &id), value);
+    }
+
+    burn {
+        let owner: T::AccountId = whitelisted_caller();
+        let id = create_user_asset::<T>(&owner);
+        let value: BalanceOf<T> = 100_000_000u32.into();
+        XAssets::<T>::issue(&id, &owner, value).unwrap();
+    }: _(RawOrigin::Signed(owner.clone()), id, value)
+    verify {
+        assert_eq!(XAssets::<T>::usable_balance(&owner, &id), Zero::zero());
+    }
+
+    set_owned_asset_limit {
+        let owner: T::AccountId = whitelisted_caller();
+        let id = create_user_asset::<T>(&owner);
+        let res = AssetRestrictions::TRANSFER;
+    }: _(RawOrigin::Signed(owner), id, res)
+    verify {
+        assert_eq!(XAssets::<T>::asset_restrictions_of(&id), res);
+    }
 }
 
 #[cfg(test)]
@@ -74,9 +253,20 @@ mod tests {
     fn test_benchmarks() {
         ExtBuilder::default().build_default().execute_with(|| {
             assert_ok!(test_benchmark_transfer::<Test>());
+            assert_ok!(test_benchmark_transfer_with_memo::<Test>());
+            assert_ok!(test_benchmark_batch_transfer::<Test>());
+            assert_ok!(test_benchmark_approve::<Test>());
+            assert_ok!(test_benchmark_transfer_from::<Test>());
+            assert_ok!(test_benchmark_vest::<Test>());
+            assert_ok!(test_benchmark_vested_transfer::<Test>());
             assert_ok!(test_benchmark_force_transfer::<Test>());
             assert_ok!(test_benchmark_set_balance::<Test>());
             assert_ok!(test_benchmark_set_asset_limit::<Test>());
+            assert_ok!(test_benchmark_freeze::<Test>());
+            assert_ok!(test_benchmark_thaw::<Test>());
+            assert_ok!(test_benchmark_mint::<Test>());
+            assert_ok!(test_benchmark_burn::<Test>());
+            assert_ok!(test_benchmark_set_owned_asset_limit::<Test>());
         });
     }
 }
@@ -31,30 +31,45 @@ use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
     ensure,
-    traits::{Currency, Get, Happened, IsDeadAccount, LockableCurrency, ReservableCurrency},
+    traits::{
+        Currency, Get, Happened, IsDeadAccount, LockIdentifier, LockableCurrency,
+        ReservableCurrency,
+    },
     Parameter, StorageDoubleMap,
 };
 use frame_system::{ensure_root, ensure_signed};
-use orml_traits::arithmetic::{Signed, SimpleArithmetic};
+use orml_traits::{
+    arithmetic::{Signed, SimpleArithmetic},
+    MultiLockableCurrency,
+};
+use orml_utilities::with_transaction_result;
 use sp_runtime::traits::{
     CheckedAdd, CheckedSub, MaybeSerializeDeserialize, Member, Saturating, StaticLookup, Zero,
 };
 
 use chainx_primitives::AssetId;
 use xp_logging::{debug, error, info};
+use xp_runtime::Memo;
 pub use xpallet_assets_registrar::{AssetInfo, Chain};
 use xpallet_support::traits::TreasuryAccount;
 
 pub use self::traits::{ChainT, OnAssetChanged};
 use self::trigger::AssetChangedTrigger;
 pub use self::types::{
-    AssetErr, AssetRestrictions, AssetType, BalanceLock, TotalAssetInfo, WithdrawalLimit,
+    AssetErr, AssetRestrictions, AssetType, BalanceLock, TotalAssetInfo, TotalBalanceMismatch,
+    VestingSchedule, WithdrawalLimit,
 };
 pub use self::weights::WeightInfo;
 
 pub type BalanceOf<T> =
     <<T as Trait>::Currency as Currency<<T as frame_system::Trait>::AccountId>>::Balance;
 
+pub type VestingScheduleOf<T> =
+    VestingSchedule<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// The lock identifier of the balance locked by the vesting schedule.
+pub const VESTING_ID: LockIdentifier = *b"vesting ";
+
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
@@ -107,6 +122,18 @@ decl_error! {
         DenyNativeAsset,
         /// Action is not allowed.
         ActionNotAllowed,
+        /// The allowance is less than the value to transfer
+        InsufficientAllowance,
+        /// The account has no vesting schedule of the asset
+        NotVesting,
+        /// The account already has a vesting schedule of the asset
+        ExistingVestingSchedule,
+        /// Nothing is locked or released by the vesting schedule
+        InvalidVestingSchedule,
+        /// The asset of the account is frozen
+        AccountFrozen,
+        /// The restrictions are beyond the ones which can be set by the asset owner
+        RestrictionsOutOfBound,
     }
 }
 
@@ -115,9 +142,22 @@ decl_event!(
     where
         <T as frame_system::Trait>::AccountId,
         Balance = BalanceOf<T>,
+        VestingSchedule = VestingScheduleOf<T>,
     {
         /// Some balances of an asset was moved from one to another. [asset_id, from, from_type, to, to_type, amount]
         Moved(AssetId, AccountId, AssetType, AccountId, AssetType, Balance),
+        /// Some balances of an asset were transferred with a memo. [asset_id, from, to, amount, memo]
+        TransferredWithMemo(AssetId, AccountId, AccountId, Balance, Memo),
+        /// An account approved another account to transfer some balances of an asset. [asset_id, owner, spender, amount]
+        Approved(AssetId, AccountId, AccountId, Balance),
+        /// A vesting schedule of an asset was added to an account. [asset_id, who, schedule]
+        VestingScheduleAdded(AssetId, AccountId, VestingSchedule),
+        /// The vesting schedule of an account was updated. [asset_id, who, locked]
+        Vested(AssetId, AccountId, Balance),
+        /// The asset of an account was frozen. [asset_id, who]
+        Frozen(AssetId, AccountId),
+        /// The asset of an account was thawed. [asset_id, who]
+        Thawed(AssetId, AccountId),
         /// New balances of an asset were issued. [asset_id, receiver, amount]
         Issued(AssetId, AccountId, Balance),
         /// Some balances of an asset were destoryed. [asset_id, who, amount]
@@ -149,6 +189,21 @@ decl_storage! {
         /// asset balance for an asset_id, use btree_map to accept different asset type
         pub TotalAssetBalance get(fn total_asset_balance):
             map hasher(twox_64_concat) AssetId => BTreeMap<AssetType, BalanceOf<T>>;
+
+        /// The balances of an asset which the spender is allowed to transfer from the owner.
+        pub Allowances get(fn allowance):
+            double_map hasher(blake2_128_concat) (T::AccountId, T::AccountId), hasher(twox_64_concat) AssetId
+            => BalanceOf<T>;
+
+        /// The accounts whose asset can not be moved out, but still can be received.
+        pub FrozenAccounts get(fn is_frozen):
+            double_map hasher(twox_64_concat) AssetId, hasher(blake2_128_concat) T::AccountId
+            => bool;
+
+        /// The vesting schedule of the asset for an account.
+        pub Vesting get(fn vesting):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId
+            => Option<VestingScheduleOf<T>>;
     }
     add_extra_genesis {
         config(assets_restrictions): Vec<(AssetId, AssetRestrictions)>;
@@ -196,6 +251,127 @@ decl_module! {
             Ok(())
         }
 
+        /// Transfer between account with a memo, e.g. the memo for the exchange to credit the user.
+        #[weight = <T as Trait>::WeightInfo::transfer_with_memo()]
+        pub fn transfer_with_memo(
+            origin,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>,
+            memo: Memo
+        ) -> DispatchResult {
+            let transactor = ensure_signed(origin)?;
+            let dest = T::Lookup::lookup(dest)?;
+            debug!("[transfer_with_memo] from:{:?}, to:{:?}, id:{}, value:{:?}, memo:{}", transactor, dest, id, value, memo);
+            memo.check_validity()?;
+            Self::can_transfer(&id)?;
+
+            Self::move_usable_balance(&id, &transactor, &dest, value).map_err::<Error::<T>, _>(Into::into)?;
+            Self::deposit_event(Event::<T>::TransferredWithMemo(id, transactor, dest, value, memo));
+            Ok(())
+        }
+
+        /// Transfer to multiple accounts, none of the transfers is done if any of them fails.
+        #[weight = <T as Trait>::WeightInfo::batch_transfer(transfers.len() as u32)]
+        pub fn batch_transfer(
+            origin,
+            #[compact] id: AssetId,
+            transfers: Vec<(<T::Lookup as StaticLookup>::Source, BalanceOf<T>)>
+        ) -> DispatchResult {
+            let transactor = ensure_signed(origin)?;
+            debug!("[batch_transfer] from:{:?}, id:{}, transfers:{}", transactor, id, transfers.len());
+            Self::can_transfer(&id)?;
+
+            with_transaction_result(|| {
+                for (dest, value) in transfers {
+                    let dest = T::Lookup::lookup(dest)?;
+                    Self::move_usable_balance(&id, &transactor, &dest, value).map_err::<Error::<T>, _>(Into::into)?;
+                }
+                Ok(())
+            })
+        }
+
+        /// Approve the spender to transfer `value` of the asset from the caller,
+        /// the previous allowance is replaced.
+        #[weight = <T as Trait>::WeightInfo::approve()]
+        pub fn approve(
+            origin,
+            spender: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            let spender = T::Lookup::lookup(spender)?;
+            debug!("[approve] owner:{:?}, spender:{:?}, id:{}, value:{:?}", owner, spender, id, value);
+            Self::ensure_not_native_asset(&id)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_exists(&id)?;
+
+            Self::set_allowance(&owner, &spender, &id, value);
+            Self::deposit_event(Event::<T>::Approved(id, owner, spender, value));
+            Ok(())
+        }
+
+        /// Transfer `value` of the asset from the owner within the allowance approved to the caller.
+        #[weight = <T as Trait>::WeightInfo::transfer_from()]
+        pub fn transfer_from(
+            origin,
+            owner: <T::Lookup as StaticLookup>::Source,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let spender = ensure_signed(origin)?;
+            let owner = T::Lookup::lookup(owner)?;
+            let dest = T::Lookup::lookup(dest)?;
+            debug!("[transfer_from] spender:{:?}, from:{:?}, to:{:?}, id:{}, value:{:?}", spender, owner, dest, id, value);
+            Self::can_transfer(&id)?;
+
+            let allowance = Self::allowance((&owner, &spender), id)
+                .checked_sub(&value)
+                .ok_or(Error::<T>::InsufficientAllowance)?;
+            Self::move_usable_balance(&id, &owner, &dest, value).map_err::<Error::<T>, _>(Into::into)?;
+            Self::set_allowance(&owner, &spender, &id, allowance);
+            Ok(())
+        }
+
+        /// Release the balance of the asset which has been vested for the caller.
+        #[weight = <T as Trait>::WeightInfo::vest()]
+        pub fn vest(origin, #[compact] id: AssetId) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let schedule = Self::vesting(&who, id).ok_or(Error::<T>::NotVesting)?;
+            Self::update_vesting(&who, id, schedule);
+            Ok(())
+        }
+
+        /// Transfer the asset to `dest` and lock it according to the vesting schedule.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::vested_transfer()]
+        pub fn vested_transfer(
+            origin,
+            source: <T::Lookup as StaticLookup>::Source,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] id: AssetId,
+            schedule: VestingScheduleOf<T>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            let source = T::Lookup::lookup(source)?;
+            let dest = T::Lookup::lookup(dest)?;
+            info!("[vested_transfer] from:{:?}, to:{:?}, id:{}, schedule:{:?}", source, dest, id, schedule);
+            Self::ensure_not_native_asset(&id)?;
+            ensure!(
+                !schedule.locked.is_zero() && !schedule.per_block.is_zero(),
+                Error::<T>::InvalidVestingSchedule
+            );
+            ensure!(!Vesting::<T>::contains_key(&dest, id), Error::<T>::ExistingVestingSchedule);
+            Self::can_transfer(&id)?;
+
+            Self::move_usable_balance(&id, &source, &dest, schedule.locked).map_err::<Error::<T>, _>(Into::into)?;
+            Self::deposit_event(Event::<T>::VestingScheduleAdded(id, dest.clone(), schedule));
+            Self::update_vesting(&dest, id, schedule);
+            Ok(())
+        }
+
         /// for transfer by root
         #[weight = 0]
         pub fn force_transfer(
@@ -236,6 +412,77 @@ decl_module! {
             ensure_root(origin)?;
             Self::set_asset_restrictions(id, restrictions)
         }
+
+        /// Freeze the asset of an account, the asset can not be transferred or withdrawn
+        /// by the account, but still can be deposited to the account.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::freeze()]
+        pub fn freeze(origin, who: <T::Lookup as StaticLookup>::Source, #[compact] id: AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_exists(&id)?;
+            info!("[freeze] who:{:?}, id:{}", who, id);
+            FrozenAccounts::<T>::insert(id, &who, true);
+            Self::deposit_event(Event::<T>::Frozen(id, who));
+            Ok(())
+        }
+
+        /// Thaw the frozen asset of an account.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::thaw()]
+        pub fn thaw(origin, who: <T::Lookup as StaticLookup>::Source, #[compact] id: AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+            let who = T::Lookup::lookup(who)?;
+            info!("[thaw] who:{:?}, id:{}", who, id);
+            FrozenAccounts::<T>::remove(id, &who);
+            Self::deposit_event(Event::<T>::Thawed(id, who));
+            Ok(())
+        }
+
+        /// Mint the asset registered permissionlessly to `dest`.
+        ///
+        /// This is an owner-only operation.
+        #[weight = <T as Trait>::WeightInfo::mint()]
+        pub fn mint(
+            origin,
+            #[compact] id: AssetId,
+            dest: <T::Lookup as StaticLookup>::Source,
+            #[compact] value: BalanceOf<T>
+        ) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_owner(&id, &owner)?;
+            let dest = T::Lookup::lookup(dest)?;
+            debug!("[mint] owner:{:?}, dest:{:?}, id:{}, value:{:?}", owner, dest, id, value);
+            Self::issue(&id, &dest, value)
+        }
+
+        /// Burn the asset registered permissionlessly from the usable balance of the owner.
+        ///
+        /// This is an owner-only operation.
+        #[weight = <T as Trait>::WeightInfo::burn()]
+        pub fn burn(origin, #[compact] id: AssetId, #[compact] value: BalanceOf<T>) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_owner(&id, &owner)?;
+            debug!("[burn] owner:{:?}, id:{}, value:{:?}", owner, id, value);
+            Self::destroy_usable(&id, &owner, value)
+        }
+
+        /// Set the restrictions of the asset registered permissionlessly, only the
+        /// restrictions of `AssetRestrictions::owner_settable()` can be set by the owner.
+        ///
+        /// This is an owner-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_owned_asset_limit()]
+        pub fn set_owned_asset_limit(origin, #[compact] id: AssetId, restrictions: AssetRestrictions) -> DispatchResult {
+            let owner = ensure_signed(origin)?;
+            xpallet_assets_registrar::Module::<T>::ensure_asset_owner(&id, &owner)?;
+            let settable = AssetRestrictions::owner_settable();
+            ensure!(settable.contains(restrictions), Error::<T>::RestrictionsOutOfBound);
+            // the restrictions out of bound are kept as they are.
+            let kept = Self::asset_restrictions_of(&id) - settable;
+            Self::set_asset_restrictions(id, kept | restrictions)
+        }
     }
 }
 
@@ -261,6 +508,39 @@ impl<T: Trait> Module<T> {
     }
 }
 
+// allowance related
+impl<T: Trait> Module<T> {
+    fn set_allowance(
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        id: &AssetId,
+        value: BalanceOf<T>,
+    ) {
+        if value.is_zero() {
+            Allowances::<T>::remove((owner, spender), id);
+        } else {
+            Allowances::<T>::insert((owner, spender), id, value);
+        }
+    }
+}
+
+// vesting related
+impl<T: Trait> Module<T> {
+    /// Lock the balance which has not been vested yet, the schedule is removed
+    /// once all the balance is released.
+    fn update_vesting(who: &T::AccountId, id: AssetId, schedule: VestingScheduleOf<T>) {
+        let locked = schedule.locked_at(frame_system::Module::<T>::block_number());
+        if locked.is_zero() {
+            <Self as MultiLockableCurrency<_>>::remove_lock(VESTING_ID, id, who);
+            Vesting::<T>::remove(who, id);
+        } else {
+            <Self as MultiLockableCurrency<_>>::set_lock(VESTING_ID, id, who, locked);
+            Vesting::<T>::insert(who, id, schedule);
+        }
+        Self::deposit_event(Event::<T>::Vested(id, who.clone(), locked));
+    }
+}
+
 // asset related
 impl<T: Trait> Module<T> {
     pub fn total_asset_infos() -> BTreeMap<AssetId, TotalAssetInfo<BalanceOf<T>>> {
@@ -317,6 +597,18 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    #[inline]
+    pub fn ensure_not_frozen(id: &AssetId, who: &T::AccountId) -> DispatchResult {
+        if Self::is_frozen(id, who) {
+            error!(
+                "The asset of the account is frozen, id:{}, who:{:?}",
+                id, who
+            );
+            return Err(Error::<T>::AccountFrozen.into());
+        }
+        Ok(())
+    }
+
     #[inline]
     pub fn can_destroy_withdrawal(id: &AssetId) -> DispatchResult {
         if !Self::can_do(id, AssetRestrictions::DESTROY_WITHDRAWAL) {
@@ -368,6 +660,111 @@ impl<T: Trait> Module<T> {
     }
 }
 
+impl<T: Trait> xpallet_assets_registrar::AssetIssuance for Module<T> {
+    fn is_issued(asset_id: &AssetId) -> bool {
+        !Self::total_issuance(asset_id).is_zero()
+    }
+}
+
+// invariant audit interface
+impl<T: Trait> Module<T> {
+    /// Sums up the balances of all the accounts per asset and asset type.
+    ///
+    /// NOTE: iterates the whole `AssetBalance`, only for RPC, tests and offline checks.
+    fn actual_total_asset_balances() -> BTreeMap<AssetId, BTreeMap<AssetType, BalanceOf<T>>> {
+        use frame_support::IterableStorageDoubleMap;
+        let mut actual = BTreeMap::<AssetId, BTreeMap<AssetType, BalanceOf<T>>>::new();
+        for (_, id, balances) in AssetBalance::<T>::iter() {
+            let total = actual.entry(id).or_default();
+            for (type_, balance) in balances {
+                let sum = total.entry(type_).or_default();
+                *sum = sum.saturating_add(balance);
+            }
+        }
+        actual
+    }
+
+    fn total_balance_mismatches(
+        recorded: &BTreeMap<AssetType, BalanceOf<T>>,
+        actual: &BTreeMap<AssetType, BalanceOf<T>>,
+    ) -> Vec<TotalBalanceMismatch<BalanceOf<T>>> {
+        AssetType::iter()
+            .filter_map(|type_| {
+                let recorded = recorded.get(type_).copied().unwrap_or_default();
+                let actual = actual.get(type_).copied().unwrap_or_default();
+                if recorded != actual {
+                    Some(TotalBalanceMismatch {
+                        asset_type: *type_,
+                        recorded,
+                        actual,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the asset types of `id` whose `TotalAssetBalance` does not equal
+    /// the sum of `AssetBalance` over all the accounts.
+    ///
+    /// NOTE: iterates the whole `AssetBalance`, but only sums up the balances of `id`.
+    pub fn audit_invariant(id: &AssetId) -> Vec<TotalBalanceMismatch<BalanceOf<T>>> {
+        use frame_support::IterableStorageDoubleMap;
+        let mut actual = BTreeMap::<AssetType, BalanceOf<T>>::new();
+        for (_, asset_id, balances) in AssetBalance::<T>::iter() {
+            if asset_id != *id {
+                continue;
+            }
+            for (type_, balance) in balances {
+                let sum = actual.entry(type_).or_default();
+                *sum = sum.saturating_add(balance);
+            }
+        }
+        Self::total_balance_mismatches(&Self::total_asset_balance(id), &actual)
+    }
+
+    /// Audits the total balance invariant of all the assets, only the assets
+    /// having mismatches are returned.
+    pub fn audit_all_invariants() -> BTreeMap<AssetId, Vec<TotalBalanceMismatch<BalanceOf<T>>>> {
+        use frame_support::IterableStorageMap;
+        let mut actual = Self::actual_total_asset_balances();
+        let mut ids = TotalAssetBalance::<T>::iter()
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+        ids.extend(actual.keys().copied());
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids.into_iter()
+            .filter_map(|id| {
+                let actual = actual.remove(&id).unwrap_or_default();
+                let mismatches =
+                    Self::total_balance_mismatches(&Self::total_asset_balance(&id), &actual);
+                if mismatches.is_empty() {
+                    None
+                } else {
+                    Some((id, mismatches))
+                }
+            })
+            .collect()
+    }
+
+    /// Checks the total balance invariant of all the assets, in the way of `try-runtime`.
+    pub fn check_invariants() -> Result<(), &'static str> {
+        let mismatches = Self::audit_all_invariants();
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            error!(
+                "[check_invariants] total asset balance mismatches:{:?}",
+                mismatches
+            );
+            Err("TotalAssetBalance does not match the sum of AssetBalance")
+        }
+    }
+}
+
 // public write interface
 impl<T: Trait> Module<T> {
     /// Sets the free balance of `who` without sanity checks and triggering the asset changed hook.
@@ -419,6 +816,10 @@ impl<T: Trait> Module<T> {
         xpallet_assets_registrar::Module::<T>::ensure_asset_is_valid(id)
             .map_err(|_| AssetErr::InvalidAsset)?;
         Self::can_move(id).map_err(|_| AssetErr::NotAllow)?;
+        // the frozen asset can only be moved between the types of the same account.
+        if from != to && Self::is_frozen(id, from) {
+            return Err(AssetErr::Frozen);
+        }
 
         if value == Zero::zero() {
             // value is zero, do not read storage, no event
@@ -609,47 +1010,51 @@ impl<T: Trait> Module<T> {
     }
 
     fn update_locks(currency_id: AssetId, who: &T::AccountId, locks: &[BalanceLock<BalanceOf<T>>]) {
-        // update locked balance
-        if let Some(max_locked) = locks.iter().map(|lock| lock.amount).max() {
-            use sp_std::cmp::Ordering;
-            let current_locked = Self::asset_balance_of(who, &currency_id, AssetType::Locked);
-
-            let result = match max_locked.cmp(&current_locked) {
-                Ordering::Greater => {
-                    // new lock more than current locked, move usable to locked
-                    Self::move_balance(
-                        &currency_id,
-                        who,
-                        AssetType::Usable,
-                        who,
-                        AssetType::Locked,
-                        max_locked - current_locked,
-                    )
-                }
-                Ordering::Less => {
-                    // new lock less then current locked, release locked to usable
-                    Self::move_balance(
-                        &currency_id,
-                        who,
-                        AssetType::Locked,
-                        who,
-                        AssetType::Usable,
-                        current_locked - max_locked,
-                    )
-                }
-                Ordering::Equal => {
-                    // if max_locked == locked, need do nothing
-                    Ok(())
-                }
-            };
-            if let Err(err) = result {
-                // should not fail, for set lock need to check free_balance, free_balance = usable + free
-                error!(
-                    "[update_locks] Should not be failed when move asset (usable <=> locked), \
-                    who:{:?}, asset:[id:{}, max_locked:{:?}, current_locked:{:?}], err:{:?}",
-                    who, currency_id, max_locked, current_locked, err
-                );
+        use sp_std::cmp::Ordering;
+
+        // update locked balance, all the locked balance is released if there is no lock
+        let max_locked = locks
+            .iter()
+            .map(|lock| lock.amount)
+            .max()
+            .unwrap_or_else(Zero::zero);
+        let current_locked = Self::asset_balance_of(who, &currency_id, AssetType::Locked);
+
+        let result = match max_locked.cmp(&current_locked) {
+            Ordering::Greater => {
+                // new lock more than current locked, move usable to locked
+                Self::move_balance(
+                    &currency_id,
+                    who,
+                    AssetType::Usable,
+                    who,
+                    AssetType::Locked,
+                    max_locked - current_locked,
+                )
+            }
+            Ordering::Less => {
+                // new lock less then current locked, release locked to usable
+                Self::move_balance(
+                    &currency_id,
+                    who,
+                    AssetType::Locked,
+                    who,
+                    AssetType::Usable,
+                    current_locked - max_locked,
+                )
+            }
+            Ordering::Equal => {
+                // if max_locked == locked, need do nothing
+                Ok(())
             }
+        };
+        if let Err(err) = result {
+            // should not fail, for set lock need to check free_balance, free_balance = usable + free
+            error!(
+                "[update_locks] Should not be failed when move asset (usable <=> locked), \
+                who:{:?}, asset:[id:{}, max_locked:{:?}, current_locked:{:?}], err:{:?}",
+                who, currency_id, max_locked, current_locked, err
+            );
         }
 
         // update locks
@@ -37,7 +37,7 @@ impl_outer_event! {
     pub enum MetaEvent for Test {
         system<T>,
         pallet_balances<T>,
-        xpallet_assets_registrar,
+        xpallet_assets_registrar<T>,
         assets<T>,
     }
 }
@@ -97,12 +97,16 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = ();
+    type AssetIssuance = XAssets;
     type WeightInfo = ();
 }
 
@@ -183,6 +187,7 @@ impl ExtBuilder {
         let mut ext = self.build_default();
         ext.execute_with(|| System::set_block_number(1));
         ext.execute_with(test);
+        ext.execute_with(|| debug_assert_eq!(XAssets::check_invariants(), Ok(())));
     }
 
     pub fn build_no_endowed_and_execute(self, test: impl FnOnce() -> ()) {
@@ -191,11 +196,13 @@ impl ExtBuilder {
         let mut ext = self.build(assets, Default::default());
         ext.execute_with(|| System::set_block_number(1));
         ext.execute_with(test);
+        ext.execute_with(|| debug_assert_eq!(XAssets::check_invariants(), Ok(())));
     }
 }
 
 pub type System = frame_system::Module<Test>;
 pub type Balances = pallet_balances::Module<Test>;
+pub type XAssetsRegistrar = xpallet_assets_registrar::Module<Test>;
 pub type XAssets = Module<Test>;
 pub type XAssetsErr = Error<Test>;
 
@@ -48,6 +48,7 @@ impl<T: Trait> MultiCurrency<T::AccountId> for Module<T> {
         if amount.is_zero() {
             return Ok(());
         }
+        Self::ensure_not_frozen(&currency_id, who)?;
 
         let new_balance = Self::free_balance(currency_id, who)
             .checked_sub(&amount)
@@ -532,6 +532,316 @@ fn test_transfer_to_self() {
     })
 }
 
+#[test]
+fn test_transfer_with_memo() {
+    ExtBuilder::default().build_no_endowed_and_execute(|| {
+        let a: u64 = 1; // accountid
+        let b: u64 = 2; // accountid
+        let btc_id = X_BTC;
+        XAssets::issue(&btc_id, &a, 50).unwrap();
+
+        assert_noop!(
+            XAssets::transfer_with_memo(
+                Origin::signed(a),
+                b.into(),
+                btc_id.into(),
+                25,
+                vec![b'1'; 129].into()
+            ),
+            DispatchError::Other("transaction memo too long, valid byte length range: [0, 128]")
+        );
+        assert_ok!(XAssets::transfer_with_memo(
+            Origin::signed(a),
+            b.into(),
+            btc_id.into(),
+            25,
+            b"exchange".to_vec().into()
+        ));
+        assert_eq!(XAssets::usable_balance(&a, &btc_id), 25);
+        assert_eq!(XAssets::usable_balance(&b, &btc_id), 25);
+        assert!(System::events().contains(&EventRecord {
+            phase: Phase::Initialization,
+            event: MetaEvent::assets(RawEvent::TransferredWithMemo(
+                btc_id,
+                a,
+                b,
+                25,
+                b"exchange".to_vec().into()
+            )),
+            topics: vec![],
+        }));
+    })
+}
+
+#[test]
+fn test_batch_transfer() {
+    ExtBuilder::default().build_no_endowed_and_execute(|| {
+        let a: u64 = 1; // accountid
+        let btc_id = X_BTC;
+        XAssets::issue(&btc_id, &a, 50).unwrap();
+
+        // all the transfers fail if any of them fails
+        assert_noop!(
+            XAssets::batch_transfer(
+                Origin::signed(a),
+                btc_id.into(),
+                vec![(2, 20), (3, 20), (4, 20)]
+            ),
+            XAssetsErr::InsufficientBalance
+        );
+
+        assert_ok!(XAssets::batch_transfer(
+            Origin::signed(a),
+            btc_id.into(),
+            vec![(2, 20), (3, 20)]
+        ));
+        assert_eq!(XAssets::usable_balance(&a, &btc_id), 10);
+        assert_eq!(XAssets::usable_balance(&2, &btc_id), 20);
+        assert_eq!(XAssets::usable_balance(&3, &btc_id), 20);
+        let moved = System::events()
+            .into_iter()
+            .filter(|record| matches!(record.event, MetaEvent::assets(RawEvent::Moved(..))))
+            .count();
+        assert_eq!(moved, 2);
+
+        assert_ok!(XAssets::set_asset_limit(
+            Origin::root(),
+            btc_id,
+            AssetRestrictions::TRANSFER
+        ));
+        assert_noop!(
+            XAssets::batch_transfer(Origin::signed(a), btc_id.into(), vec![(2, 10)]),
+            XAssetsErr::ActionNotAllowed
+        );
+    })
+}
+
+#[test]
+fn test_transfer_from() {
+    ExtBuilder::default().build_no_endowed_and_execute(|| {
+        let owner: u64 = 1;
+        let spender: u64 = 2;
+        let dest: u64 = 3;
+        let btc_id = X_BTC;
+        XAssets::issue(&btc_id, &owner, 50).unwrap();
+
+        assert_noop!(
+            XAssets::approve(Origin::signed(owner), spender, ChainXAssetId::get(), 10),
+            XAssetsErr::DenyNativeAsset
+        );
+        assert_ok!(XAssets::approve(Origin::signed(owner), spender, btc_id, 30));
+        assert_eq!(XAssets::allowance((owner, spender), btc_id), 30);
+
+        assert_ok!(XAssets::transfer_from(
+            Origin::signed(spender),
+            owner,
+            dest,
+            btc_id,
+            20
+        ));
+        assert_eq!(XAssets::usable_balance(&owner, &btc_id), 30);
+        assert_eq!(XAssets::usable_balance(&dest, &btc_id), 20);
+        assert_eq!(XAssets::allowance((owner, spender), btc_id), 10);
+        assert!(System::events().contains(&EventRecord {
+            phase: Phase::Initialization,
+            event: MetaEvent::assets(RawEvent::Moved(
+                btc_id,
+                owner,
+                AssetType::Usable,
+                dest,
+                AssetType::Usable,
+                20
+            )),
+            topics: vec![],
+        }));
+
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(spender), owner, dest, btc_id, 20),
+            XAssetsErr::InsufficientAllowance
+        );
+        // the allowance is not used if the owner has not enough balance
+        assert_ok!(XAssets::approve(
+            Origin::signed(owner),
+            spender,
+            btc_id,
+            100
+        ));
+        assert_noop!(
+            XAssets::transfer_from(Origin::signed(spender), owner, dest, btc_id, 40),
+            XAssetsErr::InsufficientBalance
+        );
+
+        assert_ok!(XAssets::approve(Origin::signed(owner), spender, btc_id, 0));
+        assert!(!Allowances::<Test>::contains_key((owner, spender), btc_id));
+    })
+}
+
+#[test]
+fn test_freeze() {
+    ExtBuilder::default().build_and_execute(|| {
+        let a: u64 = 1; // accountid
+        let b: u64 = 2; // accountid
+        let btc_id = X_BTC;
+        assert_noop!(
+            XAssets::freeze(Origin::signed(a), b, btc_id),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(XAssets::freeze(Origin::root(), a, btc_id));
+        assert!(XAssets::is_frozen(btc_id, &a));
+
+        // the frozen asset can not be moved out
+        assert_noop!(
+            XAssets::transfer(Origin::signed(a), b, btc_id, 10),
+            XAssetsErr::AccountFrozen
+        );
+        assert_noop!(
+            <XAssets as orml_traits::MultiCurrency<_>>::withdraw(btc_id, &a, 10),
+            XAssetsErr::AccountFrozen
+        );
+        // but still can be received
+        assert_ok!(XAssets::transfer(Origin::signed(b), a, btc_id, 10));
+        assert_ok!(XAssets::issue(&btc_id, &a, 10));
+        assert_eq!(XAssets::usable_balance(&a, &btc_id), 100 + 10 + 10);
+        // and moved between the types of the account
+        assert_ok!(XAssets::move_balance(
+            &btc_id,
+            &a,
+            AssetType::Usable,
+            &a,
+            AssetType::Locked,
+            10
+        ));
+
+        assert_ok!(XAssets::thaw(Origin::root(), a, btc_id));
+        assert!(!XAssets::is_frozen(btc_id, &a));
+        assert_ok!(XAssets::transfer(Origin::signed(a), b, btc_id, 10));
+    })
+}
+
+#[test]
+fn test_audit_invariant() {
+    // the drifted state would fail the invariant check of `build_and_execute`.
+    ExtBuilder::default().build_default().execute_with(|| {
+        assert!(XAssets::audit_invariant(&X_BTC).is_empty());
+        assert_eq!(XAssets::check_invariants(), Ok(()));
+
+        // make the account balance drift from the total balance.
+        AssetBalance::<Test>::mutate(&ALICE, &X_BTC, |balances| {
+            balances.insert(AssetType::Usable, 50);
+            balances.insert(AssetType::Locked, 10);
+        });
+        assert_eq!(
+            XAssets::audit_invariant(&X_BTC),
+            vec![
+                TotalBalanceMismatch {
+                    asset_type: AssetType::Usable,
+                    recorded: 1000,
+                    actual: 950,
+                },
+                TotalBalanceMismatch {
+                    asset_type: AssetType::Locked,
+                    recorded: 0,
+                    actual: 10,
+                },
+            ]
+        );
+        assert_eq!(XAssets::audit_all_invariants().len(), 1);
+        assert!(XAssets::check_invariants().is_err());
+
+        // repair the total balance
+        TotalAssetBalance::<Test>::mutate(&X_BTC, |total| {
+            total.insert(AssetType::Usable, 950);
+            total.insert(AssetType::Locked, 10);
+        });
+        assert!(XAssets::audit_invariant(&X_BTC).is_empty());
+        assert_eq!(XAssets::check_invariants(), Ok(()));
+    })
+}
+
+#[test]
+fn test_user_asset() {
+    ExtBuilder::default().build_and_execute(|| {
+        let _ = Balances::make_free_balance_be(&ALICE, 1000);
+        let asset = AssetInfo::new::<Test>(
+            b"USER".to_vec(),
+            b"User Token".to_vec(),
+            Chain::ChainX,
+            8,
+            b"user token".to_vec(),
+        )
+        .unwrap();
+        assert_ok!(XAssetsRegistrar::create(Origin::signed(ALICE), asset));
+        let id = xp_protocol::USER_ASSET_START;
+
+        // mint
+        assert_noop!(
+            XAssets::mint(Origin::signed(BOB), id, BOB, 100),
+            xpallet_assets_registrar::Error::<Test>::NotAssetOwner
+        );
+        assert_noop!(
+            XAssets::mint(Origin::signed(ALICE), X_BTC, ALICE, 100),
+            xpallet_assets_registrar::Error::<Test>::NotAssetOwner
+        );
+        assert_ok!(XAssets::mint(Origin::signed(ALICE), id, ALICE, 100));
+        assert_ok!(XAssets::mint(Origin::signed(ALICE), id, BOB, 50));
+        assert_eq!(XAssets::usable_balance(&ALICE, &id), 100);
+        assert_eq!(XAssets::usable_balance(&BOB, &id), 50);
+        assert_eq!(XAssets::total_issuance(&id), 150);
+
+        // burn
+        assert_noop!(
+            XAssets::burn(Origin::signed(BOB), id, 50),
+            xpallet_assets_registrar::Error::<Test>::NotAssetOwner
+        );
+        assert_ok!(XAssets::burn(Origin::signed(ALICE), id, 30));
+        assert_eq!(XAssets::usable_balance(&ALICE, &id), 70);
+        assert_eq!(XAssets::total_issuance(&id), 120);
+
+        // restrictions
+        assert_noop!(
+            XAssets::set_owned_asset_limit(Origin::signed(ALICE), id, AssetRestrictions::DEPOSIT),
+            XAssetsErr::RestrictionsOutOfBound
+        );
+        assert_ok!(XAssets::set_asset_limit(
+            Origin::root(),
+            id,
+            AssetRestrictions::DEPOSIT
+        ));
+        assert_ok!(XAssets::set_owned_asset_limit(
+            Origin::signed(ALICE),
+            id,
+            AssetRestrictions::TRANSFER
+        ));
+        assert_eq!(
+            XAssets::asset_restrictions_of(&id),
+            AssetRestrictions::DEPOSIT | AssetRestrictions::TRANSFER
+        );
+        assert_noop!(
+            XAssets::transfer(Origin::signed(ALICE), BOB, id, 10),
+            XAssetsErr::ActionNotAllowed
+        );
+
+        // the new owner takes over the rights
+        assert_ok!(XAssetsRegistrar::transfer_ownership(
+            Origin::signed(ALICE),
+            id,
+            BOB
+        ));
+        assert_noop!(
+            XAssets::mint(Origin::signed(ALICE), id, ALICE, 100),
+            xpallet_assets_registrar::Error::<Test>::NotAssetOwner
+        );
+        assert_ok!(XAssets::mint(Origin::signed(BOB), id, BOB, 100));
+        assert_eq!(XAssets::usable_balance(&BOB, &id), 150);
+
+        // the issued asset can not be deregistered
+        assert_noop!(
+            XAssetsRegistrar::deregister(Origin::signed(BOB), id),
+            xpallet_assets_registrar::Error::<Test>::AssetStillIssued
+        );
+    })
+}
+
 #[test]
 fn test_move() {
     ExtBuilder::default().build_and_execute(|| {
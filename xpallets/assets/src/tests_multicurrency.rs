@@ -51,6 +51,11 @@ fn remove_lock_should_work() {
         assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 2);
         XAssets::remove_lock(ID_2, X_BTC, &ALICE);
         assert_eq!(XAssets::locks(ALICE, X_BTC).len(), 1);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 10);
+        // the locked balance is released when the last lock is removed
+        XAssets::remove_lock(ID_1, X_BTC, &ALICE);
+        assert_eq!(XAssets::locked_balance(&ALICE, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100);
     });
 }
 
@@ -333,3 +338,96 @@ fn no_op_if_amount_is_zero() {
             assert_ok!(XAssets::update_balance(X_BTC, &ALICE, 0));
         });
 }
+
+#[test]
+fn vested_transfer_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let schedule = VestingSchedule {
+            locked: 50,
+            per_block: 10,
+            start: 10,
+            cliff: 2,
+        };
+        assert_noop!(
+            XAssets::vested_transfer(Origin::signed(ALICE), ALICE, BOB, X_BTC, schedule),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XAssets::vested_transfer(
+                Origin::root(),
+                ALICE,
+                BOB,
+                X_BTC,
+                VestingSchedule {
+                    per_block: 0,
+                    ..schedule
+                }
+            ),
+            XAssetsErr::InvalidVestingSchedule
+        );
+        assert_ok!(XAssets::vested_transfer(
+            Origin::root(),
+            ALICE,
+            BOB,
+            X_BTC,
+            schedule
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 50);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 200);
+        assert_eq!(XAssets::locked_balance(&BOB, &X_BTC), 50);
+        assert_eq!(XAssets::locks(BOB, X_BTC).len(), 1);
+        assert_eq!(XAssets::vesting(BOB, X_BTC), Some(schedule));
+        // the vesting balance can not be transferred
+        assert_noop!(
+            <XAssets as MultiCurrency<_>>::transfer(X_BTC, &BOB, &ALICE, 201),
+            XAssetsErr::LiquidityRestrictions
+        );
+
+        assert_noop!(
+            XAssets::vested_transfer(Origin::root(), ALICE, BOB, X_BTC, schedule),
+            XAssetsErr::ExistingVestingSchedule
+        );
+    });
+}
+
+#[test]
+fn vest_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let schedule = VestingSchedule {
+            locked: 50,
+            per_block: 10,
+            start: 10,
+            cliff: 2,
+        };
+        assert_noop!(
+            XAssets::vest(Origin::signed(BOB), X_BTC),
+            XAssetsErr::NotVesting
+        );
+        assert_ok!(XAssets::vested_transfer(
+            Origin::root(),
+            ALICE,
+            BOB,
+            X_BTC,
+            schedule
+        ));
+
+        // nothing is released before the cliff
+        System::set_block_number(11);
+        assert_ok!(XAssets::vest(Origin::signed(BOB), X_BTC));
+        assert_eq!(XAssets::locked_balance(&BOB, &X_BTC), 50);
+
+        // the balance since the start is released after the cliff
+        System::set_block_number(12);
+        assert_ok!(XAssets::vest(Origin::signed(BOB), X_BTC));
+        assert_eq!(XAssets::locked_balance(&BOB, &X_BTC), 30);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 220);
+
+        // the schedule and the lock are removed once all the balance is released
+        System::set_block_number(20);
+        assert_ok!(XAssets::vest(Origin::signed(BOB), X_BTC));
+        assert_eq!(XAssets::locked_balance(&BOB, &X_BTC), 0);
+        assert_eq!(XAssets::usable_balance(&BOB, &X_BTC), 250);
+        assert!(XAssets::locks(BOB, X_BTC).is_empty());
+        assert_eq!(XAssets::vesting(BOB, X_BTC), None);
+    });
+}
@@ -6,7 +6,10 @@ use codec::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 // Substrate
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{
+    traits::{AtLeast32BitUnsigned, UniqueSaturatedFrom, UniqueSaturatedInto},
+    RuntimeDebug,
+};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*, slice::Iter};
 
 // ChainX
@@ -61,6 +64,14 @@ bitflags! {
     }
 }
 
+impl AssetRestrictions {
+    /// Returns the restrictions which can be set by the owner of an asset registered
+    /// permissionlessly, the others are related to the gateways and left to root.
+    pub fn owner_settable() -> Self {
+        Self::MOVE | Self::TRANSFER | Self::DESTROY_USABLE
+    }
+}
+
 impl Default for AssetRestrictions {
     fn default() -> Self {
         AssetRestrictions::empty()
@@ -77,6 +88,17 @@ pub struct TotalAssetInfo<Balance> {
     pub restrictions: AssetRestrictions,
 }
 
+/// The mismatch between the recorded total balance of an asset type in `TotalAssetBalance`
+/// and the actual sum of that type over all the accounts in `AssetBalance`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TotalBalanceMismatch<Balance> {
+    pub asset_type: AssetType,
+    pub recorded: Balance,
+    pub actual: Balance,
+}
+
 #[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum AssetErr {
@@ -86,6 +108,7 @@ pub enum AssetErr {
     TotalAssetOverFlow,
     InvalidAsset,
     NotAllow,
+    Frozen,
 }
 
 impl<T: Trait> From<AssetErr> for Error<T> {
@@ -97,6 +120,7 @@ impl<T: Trait> From<AssetErr> for Error<T> {
             AssetErr::TotalAssetOverFlow => Error::<T>::TotalAssetOverflow,
             AssetErr::InvalidAsset => Error::<T>::InvalidAsset,
             AssetErr::NotAllow => Error::<T>::ActionNotAllowed,
+            AssetErr::Frozen => Error::<T>::AccountFrozen,
         }
     }
 }
@@ -113,6 +137,40 @@ pub struct BalanceLock<Balance> {
     pub amount: Balance,
 }
 
+/// The vesting schedule of an asset, the locked balance is released linearly
+/// from `start`, but nothing can be released until `start + cliff`.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    /// The balance locked at the beginning.
+    pub locked: Balance,
+    /// The balance released per block.
+    pub per_block: Balance,
+    /// The block from which the balance starts to be released.
+    pub start: BlockNumber,
+    /// The number of blocks after `start` before which nothing is released.
+    pub cliff: BlockNumber,
+}
+
+impl<Balance, BlockNumber> VestingSchedule<Balance, BlockNumber>
+where
+    Balance: AtLeast32BitUnsigned + Copy,
+    BlockNumber: AtLeast32BitUnsigned + Copy,
+{
+    /// Returns the balance which is still locked at the block `n`.
+    pub fn locked_at(&self, n: BlockNumber) -> Balance {
+        if n < self.start.saturating_add(self.cliff) {
+            return self.locked;
+        }
+        let elapsed: u128 = n.saturating_sub(self.start).unique_saturated_into();
+        let released = self
+            .per_block
+            .saturating_mul(Balance::unique_saturated_from(elapsed));
+        self.locked.saturating_sub(released)
+    }
+}
+
 #[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
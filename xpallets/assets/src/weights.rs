@@ -32,8 +32,19 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn transfer() -> Weight;
     fn force_transfer() -> Weight;
+    fn transfer_with_memo() -> Weight;
+    fn batch_transfer(n: u32) -> Weight;
+    fn approve() -> Weight;
+    fn transfer_from() -> Weight;
+    fn vest() -> Weight;
+    fn vested_transfer() -> Weight;
     fn set_balance(n: u32) -> Weight;
     fn set_asset_limit() -> Weight;
+    fn freeze() -> Weight;
+    fn thaw() -> Weight;
+    fn mint() -> Weight;
+    fn burn() -> Weight;
+    fn set_owned_asset_limit() -> Weight;
 }
 
 /// Weights for xpallet_assets using the Substrate node and recommended hardware.
@@ -49,6 +60,39 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn transfer_with_memo() -> Weight {
+        (261_032_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn batch_transfer(n: u32) -> Weight {
+        (31_806_000 as Weight)
+            .saturating_add((229_357_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    fn approve() -> Weight {
+        (38_415_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from() -> Weight {
+        (268_790_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(9 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
+    }
+    fn vest() -> Weight {
+        (83_522_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+    }
+    fn vested_transfer() -> Weight {
+        (318_409_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
     fn set_balance(_n: u32) -> Weight {
         (218_742_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(3 as Weight))
@@ -59,6 +103,29 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
             .saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn freeze() -> Weight {
+        (24_107_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn thaw() -> Weight {
+        (19_660_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn mint() -> Weight {
+        (61_520_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn burn() -> Weight {
+        (60_307_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn set_owned_asset_limit() -> Weight {
+        (24_384_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -73,6 +140,39 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn transfer_with_memo() -> Weight {
+        (261_032_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn batch_transfer(n: u32) -> Weight {
+        (31_806_000 as Weight)
+            .saturating_add((229_357_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((4 as Weight).saturating_mul(n as Weight)))
+    }
+    fn approve() -> Weight {
+        (38_415_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn transfer_from() -> Weight {
+        (268_790_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(9 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
+    }
+    fn vest() -> Weight {
+        (83_522_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+    }
+    fn vested_transfer() -> Weight {
+        (318_409_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
     fn set_balance(_n: u32) -> Weight {
         (218_742_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(3 as Weight))
@@ -83,4 +183,27 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
             .saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn freeze() -> Weight {
+        (24_107_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn thaw() -> Weight {
+        (19_660_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn mint() -> Weight {
+        (61_520_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn burn() -> Weight {
+        (60_307_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn set_owned_asset_limit() -> Weight {
+        (24_384_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
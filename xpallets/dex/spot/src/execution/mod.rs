@@ -4,7 +4,7 @@ mod asset;
 mod order;
 mod state;
 
-use xp_logging::debug;
+use xp_logging::{debug, error};
 
 use super::*;
 use crate::types::*;
@@ -170,6 +170,12 @@ impl<T: Trait> Module<T> {
                     "Opponent side should match the side of maker order."
                 );
 
+                // The frozen maker can not deliver the asset, cancel the order instead.
+                if Self::is_frozen_maker(pair, &maker_order) {
+                    Self::cancel_frozen_maker_order(pair, &mut maker_order, counterparty_price);
+                    continue;
+                }
+
                 let turnover = cmp::min(
                     taker_order.remaining_in_base(),
                     maker_order.remaining_in_base(),
@@ -274,6 +280,45 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    /// Returns true if the maker can not deliver the asset to the taker due to the freezing.
+    fn is_frozen_maker(pair: &TradingPairProfile, maker_order: &OrderInfo<T>) -> bool {
+        let deliver_asset = match maker_order.side() {
+            Side::Buy => pair.quote(),
+            Side::Sell => pair.base(),
+        };
+        xpallet_assets::Module::<T>::is_frozen(&deliver_asset, &maker_order.submitter())
+    }
+
+    /// Cancel the order of the frozen maker and refund the reserved asset.
+    fn cancel_frozen_maker_order(
+        pair: &TradingPairProfile,
+        maker_order: &mut OrderInfo<T>,
+        price: T::Price,
+    ) {
+        let who = maker_order.submitter();
+        info!(
+            "[cancel_frozen_maker_order] who:{:?}, pair_id:{}, order_id:{}",
+            who,
+            pair.id,
+            maker_order.id()
+        );
+        if let Err(e) = Self::update_order_and_unreserve_on_cancel(maker_order, pair, &who) {
+            error!(
+                "[cancel_frozen_maker_order] Failed to refund the order of {:?}: {:?}",
+                who, e
+            );
+            return;
+        }
+        Self::kill_order(
+            pair.id,
+            price,
+            who,
+            maker_order.id(),
+            pair.clone(),
+            maker_order.side(),
+        );
+    }
+
     /// Remove the order from quotations and clear the order info when it's canceled.
     pub(crate) fn kill_order(
         pair_id: TradingPairId,
@@ -259,6 +259,7 @@ decl_module! {
                 Side::Buy => (pair.quote(), Self::convert_base_to_quote(amount, price, &pair)?),
                 Side::Sell => (pair.base(), amount)
             };
+            xpallet_assets::Module::<T>::ensure_not_frozen(&reserve_asset, &who)?;
             Self::put_order_reserve(&who, reserve_asset, reserve_amount)?;
             Self::apply_put_order(who, pair_id, order_type, side, amount, price, reserve_amount)?;
         }
@@ -96,12 +96,16 @@ impl Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = XSpot;
+    type AssetIssuance = ();
     type WeightInfo = ();
 }
 
@@ -327,6 +327,34 @@ fn match_order_should_work() {
     })
 }
 
+#[test]
+fn frozen_maker_should_be_canceled_on_matching() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+
+        t_set_handicap(0, 1_000_000, 1_100_000);
+
+        t_generic_issue(trading_pair.quote(), 1, 10);
+        t_issue_pcx(2, 2000);
+
+        assert_ok!(t_put_order_buy(1, 0, 1000, 1_000_100));
+        assert_ok!(XAssets::freeze(Origin::root(), 1, trading_pair.quote()));
+        assert_noop!(
+            t_put_order_buy(1, 0, 1000, 1_000_100),
+            xpallet_assets::Error::<Test>::AccountFrozen
+        );
+
+        // The order of the frozen maker is canceled and refunded instead of failing the taker.
+        assert_ok!(t_put_order_sell(2, 0, 500, 1_000_100));
+        assert_eq!(XSpot::order_info_of(1, 0), None);
+        assert_eq!(t_generic_free_balance(1, trading_pair.quote()), 10);
+        assert_eq!(XSpot::quotations_of(0, 1_000_100), vec![(2, 0)]);
+        let order_2_0 = XSpot::order_info_of(2, 0).unwrap();
+        assert_eq!(order_2_0.already_filled, 0);
+        assert_eq!(order_2_0.status, OrderStatus::Created);
+    })
+}
+
 #[test]
 fn cancel_order_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -8,9 +8,10 @@ use sp_runtime::{AccountId32, SaturatedConversion};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use chainx_primitives::AssetId;
-use xp_gateway_bitcoin::BtcTxType;
-use xpallet_assets::{BalanceOf, Module as XAssets};
-use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalState};
+use xpallet_assets::{BalanceOf, Chain, Module as XAssets};
+use xpallet_gateway_records::{
+    Module as XGatewayRecords, WithdrawalFee, WithdrawalFeeMode, WithdrawalState,
+};
 
 use light_bitcoin::{
     chain::{BlockHeader, Transaction},
@@ -19,9 +20,7 @@ use light_bitcoin::{
     serialization::{self, Reader},
 };
 
-use crate::{
-    types::*, Call, Module, PendingDeposits, Trait, TxState, Verifier, WithdrawalProposal,
-};
+use crate::{types::*, Call, Module, PendingDeposits, Trait, Verifier, WithdrawalProposal};
 
 const ASSET_ID: AssetId = xp_protocol::X_BTC;
 
@@ -30,6 +29,13 @@ fn generate_blocks_576576_578692() -> BTreeMap<u32, BlockHeader> {
     Decode::decode(&mut &bytes[..]).unwrap()
 }
 
+fn deducted_fee<T: Trait>() -> WithdrawalFee<BalanceOf<T>> {
+    WithdrawalFee {
+        amount: Module::<T>::btc_withdrawal_fee().saturated_into(),
+        mode: WithdrawalFeeMode::Deducted,
+    }
+}
+
 fn account<T: Trait>(pubkey: &str) -> T::AccountId {
     let pubkey = hex::decode(pubkey).unwrap();
     let mut public = [0u8; 32];
@@ -94,6 +100,7 @@ fn prepare_withdrawal<T: Trait>() -> Transaction {
         balance1,
         b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
         b"memo".to_vec().into(),
+        deducted_fee::<T>(),
     )
     .unwrap();
     XGatewayRecords::<T>::withdraw(
@@ -102,6 +109,7 @@ fn prepare_withdrawal<T: Trait>() -> Transaction {
         balance2,
         b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
         b"memo".to_vec().into(),
+        deducted_fee::<T>(),
     )
     .unwrap();
 
@@ -169,8 +177,8 @@ benchmarks! {
 
         XGatewayRecords::<T>::deposit(&caller, ASSET_ID, 9778400.into()).unwrap();
         XGatewayRecords::<T>::deposit(&caller, ASSET_ID, 9900000.into()).unwrap();
-        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, 9778400.into(), b"".to_vec(), b"".to_vec().into()).unwrap();
-        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, 9900000.into(), b"".to_vec(), b"".to_vec().into()).unwrap();
+        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, 9778400.into(), b"".to_vec(), b"".to_vec().into(), deducted_fee::<T>()).unwrap();
+        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, 9900000.into(), b"".to_vec(), b"".to_vec().into(), deducted_fee::<T>()).unwrap();
         xpallet_gateway_records::WithdrawalStateOf::insert(0, WithdrawalState::Processing);
         xpallet_gateway_records::WithdrawalStateOf::insert(1, WithdrawalState::Processing);
 
@@ -185,13 +193,7 @@ benchmarks! {
     }: _(RawOrigin::Signed(caller), tx_raw, info, Some(prev_tx_raw))
     verify {
         assert!(WithdrawalProposal::<T>::get().is_none());
-        assert_eq!(
-            TxState::get(tx_hash),
-            Some(BtcTxState {
-                tx_type: BtcTxType::Withdrawal,
-                result: BtcTxResult::Success,
-            })
-        );
+        assert!(XGatewayRecords::<T>::is_tx_processed(Chain::Bitcoin, tx_hash.as_bytes()));
     }
 
     create_withdraw_tx {
@@ -210,8 +212,8 @@ benchmarks! {
         let second_withdraw = (9900000 + btc_withdrawal_fee).saturated_into();
         XGatewayRecords::<T>::deposit(&caller, ASSET_ID, first_withdraw).unwrap();
         XGatewayRecords::<T>::deposit(&caller, ASSET_ID, second_withdraw).unwrap();
-        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, first_withdraw, b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(), b"".to_vec().into()).unwrap();
-        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, second_withdraw, b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(), b"".to_vec().into()).unwrap();
+        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, first_withdraw, b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(), b"".to_vec().into(), deducted_fee::<T>()).unwrap();
+        XGatewayRecords::<T>::withdraw(&caller, ASSET_ID, second_withdraw, b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(), b"".to_vec().into(), deducted_fee::<T>()).unwrap();
 
         let tx = create_tx();
         let tx_raw: Vec<u8> = serialization::serialize(&tx).into();
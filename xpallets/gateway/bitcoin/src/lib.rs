@@ -48,8 +48,8 @@ use xp_gateway_common::AccountExtractor;
 use xp_logging::{debug, error, info};
 use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
 use xpallet_gateway_common::{
-    traits::{AddressBinding, ReferralBinding, TrusteeSession},
-    trustees::bitcoin::BtcTrusteeAddrInfo,
+    traits::{AddressBinding, ChainGateway, ReferralBinding, TrusteeSession},
+    trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeType},
 };
 use xpallet_support::try_addr;
 
@@ -210,7 +210,8 @@ decl_storage! {
         /// all valid blockheader (include forked blockheader)
         pub Headers get(fn headers): map hasher(identity) H256 => Option<BtcHeaderInfo>;
 
-        /// mark tx has been handled, in case re-handle this tx, and log handle result
+        /// the handle result of the txs handled before `xpallet_gateway_records::TxRecords`
+        /// became the replay guard, it is only read to reject the replays of these txs.
         pub TxState get(fn tx_state): map hasher(identity) H256 => Option<BtcTxState>;
         /// unclaimed deposit info, addr => tx_hash, btc value,
         pub PendingDeposits get(fn pending_deposits): map hasher(blake2_128_concat) BtcAddress => Vec<BtcDepositCache>;
@@ -460,6 +461,12 @@ impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
     }
 }
 
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    type TrusteeEntity = BtcTrusteeType;
+    type TrusteeAddress = BtcTrusteeAddrInfo;
+    type Trustee = Self;
+}
+
 impl<T: Trait> Module<T> {
     pub fn verify_btc_address(data: &[u8]) -> Result<Address, DispatchError> {
         let r = bs58::decode(data)
@@ -580,17 +587,20 @@ impl<T: Trait> Module<T> {
             return Err(Error::<T>::UnconfirmedTx.into());
         }
         // check whether replayed tx has been processed, just process failed and not processed tx;
-        match Self::tx_state(&tx_hash) {
-            None => { /* do nothing */ }
-            Some(state) => {
-                if state.result == BtcTxResult::Success {
-                    error!(
-                        "[apply_push_transaction] Reject processed tx (hash:{:?}, type:{:?}, result:{:?})", 
-                        tx_hash, state.tx_type, state.result
-                    );
-                    return Err(Error::<T>::ReplayedTx.into());
-                }
-            }
+        let legacy_processed = Self::tx_state(&tx_hash)
+            .map(|state| state.result == BtcTxResult::Success)
+            .unwrap_or(false);
+        if legacy_processed
+            || xpallet_gateway_records::Module::<T>::is_tx_processed(
+                Chain::Bitcoin,
+                tx_hash.as_bytes(),
+            )
+        {
+            error!(
+                "[apply_push_transaction] Reject processed tx (hash:{:?})",
+                tx_hash
+            );
+            return Err(Error::<T>::ReplayedTx.into());
         }
 
         let network = Module::<T>::network_id();
@@ -605,7 +615,12 @@ impl<T: Trait> Module<T> {
             current_trustee_pair,
             last_trustee_pair,
         );
-        TxState::insert(&tx_hash, state);
+        if state.result == BtcTxResult::Success {
+            xpallet_gateway_records::Module::<T>::record_processed_tx(
+                Chain::Bitcoin,
+                tx_hash.as_bytes().to_vec(),
+            );
+        }
         Self::deposit_event(Event::<T>::TxProcessed(tx_hash, block_hash, state));
         match state.result {
             BtcTxResult::Success => Ok(()),
@@ -97,12 +97,16 @@ impl pallet_balances::Trait for Test {
 // assets
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = ();
+    type AssetIssuance = ();
     type WeightInfo = ();
 }
 
@@ -118,6 +122,9 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type WithdrawalWindow = ();
+    type MaxWithdrawalHistory = ();
+    type WithdrawalFeeProvider = XGatewayCommon;
     type WeightInfo = ();
 }
 
@@ -125,8 +132,7 @@ impl xpallet_gateway_common::Trait for Test {
     type Event = ();
     type Validator = ();
     type DetermineMultisigAddress = ();
-    type Bitcoin = XGatewayBitcoin;
-    type BitcoinTrustee = XGatewayBitcoin;
+    type Gateways = (XGatewayBitcoin,);
     type WeightInfo = ();
 }
 
@@ -13,13 +13,14 @@ use light_bitcoin::{
 };
 
 use xpallet_gateway_common::traits::TrusteeForChain;
+use xpallet_gateway_records::WithdrawalFeeMode;
 
 use crate::mock::{
-    alice, bob, AccountId, ExtBuilder, Test, XGatewayBitcoin, XGatewayBitcoinErr, XGatewayCommon,
-    XGatewayRecords, X_BTC,
+    alice, bob, AccountId, Balance, ExtBuilder, Test, XGatewayBitcoin, XGatewayBitcoinErr,
+    XGatewayCommon, XGatewayRecords, X_BTC,
 };
 use crate::{
-    trustee::create_multi_address,
+    trustee::{check_withdraw_tx_impl, create_multi_address},
     tx::validator::parse_and_check_signed_tx_impl,
     types::{BtcTxVerifier, BtcWithdrawalProposal, VoteResult},
     Verifier, WithdrawalProposal,
@@ -191,6 +192,7 @@ fn force_replace_withdraw() {
             balance1,
             b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
             b"memo".to_vec().into(),
+            WithdrawalFeeMode::Deducted,
         ));
         assert_ok!(XGatewayCommon::withdraw(
             RawOrigin::Signed(bob.clone()).into(),
@@ -198,6 +200,7 @@ fn force_replace_withdraw() {
             balance2,
             b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
             b"memo".to_vec().into(),
+            WithdrawalFeeMode::Deducted,
         ));
 
         let proposal = BtcWithdrawalProposal::<AccountId> {
@@ -217,3 +220,63 @@ fn force_replace_withdraw() {
         assert_eq!(XGatewayBitcoin::withdrawal_proposal().unwrap().tx, new_withdraw);
     });
 }
+
+#[test]
+fn check_withdraw_tx_with_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        // https://btc.com/62c389f1974b8a44737d76f92da0f5cd7f6f48d065e7af6ba368298361141270.rawhex
+        const RAW_TX: &str = "0100000001052ceda6cf9c93012a994f4ffa2a29c9e31ecf96f472b175eb8e602bfa2b2c5100000000fdfd000047304402200e4d732c456f4722d376252be16554edb27fc93c55db97859e16682bc62b014502202b9c4b01ad55daa1f76e6a564b7762cd0a81240c947806ab3f3b056f2e77c1da01483045022100c7cd680992de60da8c33fc3ef7f5ead85b204660822d9fbda2d85f9fadba732a022021fdc49b20a6007ea971a385732a4065d1d7c792ac9dc391034fb78aa9f5034b014c69522102df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6210244d81efeb4171b1a8a433b87dd202117f94e44c909c49e42e77b69b5a6ce7d0d2103a36339f413da869df12b1ab0def91749413a0dee87f0bfa85ba7196e6cdad10253aeffffffff03e0349500000000001976a91413256ff2dee6e80c275ddb877abc1ffe453a731488ace00f9700000000001976a914ea6e8dd56703ace584eb9dff0224629f8486672988acc88a02000000000017a914cb94110435d0635223eebe25ed2aaabc03781c458700000000";
+        let tx = RAW_TX.parse::<Transaction>().unwrap();
+
+        let alice = alice();
+        let bob = bob();
+        let withdrawal_fee = XGatewayBitcoin::btc_withdrawal_fee();
+
+        let balance1 = (9778400 + withdrawal_fee).into();
+        let balance2 = (9900000 + withdrawal_fee).into();
+        XGatewayRecords::deposit(&alice, X_BTC, balance1).unwrap();
+        XGatewayRecords::deposit(&bob, X_BTC, balance2).unwrap();
+        assert_ok!(XGatewayCommon::withdraw(
+            RawOrigin::Signed(alice).into(),
+            X_BTC,
+            balance1,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+            b"memo".to_vec().into(),
+            WithdrawalFeeMode::Deducted,
+        ));
+        // the fee of the root withdrawal is deducted as well
+        assert_ok!(XGatewayRecords::root_withdraw(
+            RawOrigin::Root.into(),
+            bob,
+            X_BTC,
+            balance2,
+            b"1NNZZKR6pos2M4yiJhS76NjcRHxoJUATy4".to_vec(),
+            b"memo".to_vec().into(),
+        ));
+        let record = XGatewayRecords::pending_withdrawals(1).unwrap();
+        assert_eq!(record.fee().amount, withdrawal_fee as Balance);
+        assert_eq!(record.transfer_amount(), 9900000);
+
+        // the outputs of the tx are the withdrawals with the fee deducted
+        assert_ok!(check_withdraw_tx_impl::<Test>(&tx, &[0, 1]));
+
+        // the fee is raised, the outputs do not match the new withdrawal anymore
+        assert_ok!(XGatewayBitcoin::set_btc_withdrawal_fee(
+            RawOrigin::Root.into(),
+            withdrawal_fee * 2
+        ));
+        XGatewayRecords::deposit(&alice(), X_BTC, balance1).unwrap();
+        assert_ok!(XGatewayCommon::withdraw(
+            RawOrigin::Signed(alice()).into(),
+            X_BTC,
+            balance1,
+            b"12kEgqNShFw7BN27QCMQZCynQpSuV4x1Ax".to_vec(),
+            b"memo".to_vec().into(),
+            WithdrawalFeeMode::Deducted,
+        ));
+        assert_noop!(
+            check_withdraw_tx_impl::<Test>(&tx, &[2, 1]),
+            XGatewayBitcoinErr::InvalidProposal
+        );
+    });
+}
@@ -527,7 +527,7 @@ fn check_withdraw_tx<T: Trait>(tx: &Transaction, withdrawal_id_list: &[u32]) ->
     }
 }
 
-fn check_withdraw_tx_impl<T: Trait>(
+pub(crate) fn check_withdraw_tx_impl<T: Trait>(
     tx: &Transaction,
     withdrawal_id_list: &[u32],
 ) -> DispatchResult {
@@ -540,19 +540,18 @@ fn check_withdraw_tx_impl<T: Trait>(
         // verify btc address would conveRelayedTx a base58 addr to Address
         let addr: Address = Module::<T>::verify_btc_address(&record.addr())?;
 
-        appl_withdrawal_list.push((addr, record.balance().saturated_into::<u64>()));
+        appl_withdrawal_list.push((addr, record.transfer_amount().saturated_into::<u64>()));
     }
     // not allow deposit directly to cold address, only hot address allow
     let hot_trustee_address: Address = get_hot_trustee_address::<T>()?;
     // withdrawal addr list for tx outputs
-    let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
     let btc_network = Module::<T>::network_id();
     let mut tx_withdraw_list = Vec::new();
     for output in &tx.outputs {
         let addr = extract_output_addr(&output, btc_network).ok_or("not found addr in this out")?;
         if addr.hash != hot_trustee_address.hash {
             // expect change to trustee_addr output
-            tx_withdraw_list.push((addr, output.value + btc_withdrawal_fee));
+            tx_withdraw_list.push((addr, output.value));
         }
     }
 
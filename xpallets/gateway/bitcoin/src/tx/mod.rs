@@ -17,7 +17,7 @@ use chainx_primitives::AssetId;
 use xp_gateway_bitcoin::{BtcDepositInfo, BtcTxMetaType, BtcTxTypeDetector};
 use xp_gateway_common::AccountExtractor;
 use xp_logging::{debug, error, info, warn};
-use xpallet_assets::ChainT;
+use xpallet_assets::{Chain, ChainT};
 use xpallet_gateway_common::traits::{AddressBinding, ReferralBinding};
 use xpallet_support::try_str;
 
@@ -130,7 +130,14 @@ fn deposit_token<T: Trait>(txid: H256, who: &T::AccountId, balance: u64) -> Disp
     let id: AssetId = <Module<T> as ChainT<_>>::ASSET_ID;
 
     let value: BalanceOf<T> = balance.saturated_into();
-    match <xpallet_gateway_records::Module<T>>::deposit(&who, id, value) {
+    let deposited = <xpallet_gateway_records::Module<T>>::deposit_by_tx(
+        Chain::Bitcoin,
+        txid.as_bytes().to_vec(),
+        &who,
+        id,
+        value,
+    );
+    match deposited {
         Ok(()) => {
             Module::<T>::deposit_event(Event::<T>::Deposited(txid, who.clone(), value));
             Ok(())
@@ -203,11 +210,15 @@ fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
                 // just for event record
                 let withdraw_balance =
                     xpallet_gateway_records::Module::<T>::pending_withdrawals(number)
-                        .map(|record| record.balance())
+                        .map(|record| record.transfer_amount())
                         .unwrap_or(BalanceOf::<T>::zero());
                 total += withdraw_balance;
 
-                match xpallet_gateway_records::Module::<T>::finish_withdrawal(*number, None) {
+                match xpallet_gateway_records::Module::<T>::finish_withdrawal(
+                    *number,
+                    None,
+                    Some(tx_hash.as_bytes().to_vec()),
+                ) {
                     Ok(_) => {
                         info!("[withdraw] Withdrawal ({}) completion", *number);
                     }
@@ -220,10 +231,6 @@ fn withdraw<T: Trait>(tx: Transaction) -> BtcTxResult {
                 }
             }
 
-            let btc_withdrawal_fee = Module::<T>::btc_withdrawal_fee();
-            // real withdraw value would reduce withdraw_fee
-            total -=
-                (proposal.withdrawal_id_list.len() as u64 * btc_withdrawal_fee).saturated_into();
             Module::<T>::deposit_event(Event::<T>::Withdrawn(
                 tx_hash,
                 proposal.withdrawal_id_list,
@@ -5,12 +5,15 @@ use frame_benchmarking::benchmarks;
 use frame_support::storage::StorageMap;
 use frame_system::RawOrigin;
 use sp_core::crypto::AccountId32;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::{traits::StaticLookup, Permill};
 use sp_std::prelude::*;
 
 use chainx_primitives::AssetId;
 use xpallet_assets::{BalanceOf, Chain};
-use xpallet_gateway_records::{Module as XGatewayRecords, WithdrawalRecordId, WithdrawalState};
+use xpallet_gateway_records::{
+    Module as XGatewayRecords, WithdrawalCaps, WithdrawalCapsOf, WithdrawalFeeMode,
+    WithdrawalRecordId, WithdrawalState,
+};
 
 use crate::{types::*, Call, Module, Trait, TrusteeMultiSigAddr};
 
@@ -74,7 +77,7 @@ benchmarks! {
 
         let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
         let memo = b"".to_vec().into();
-    }: _(RawOrigin::Signed(caller.clone()), ASSET_ID, amount, addr, memo)
+    }: _(RawOrigin::Signed(caller.clone()), ASSET_ID, amount, addr, memo, WithdrawalFeeMode::Deducted)
     verify {
         assert!(XGatewayRecords::<T>::pending_withdrawals(0).is_some());
         assert_eq!(
@@ -93,7 +96,7 @@ benchmarks! {
         let memo = b"".to_vec().into();
         Module::<T>::withdraw(
             RawOrigin::Signed(caller.clone()).into(),
-            ASSET_ID, withdrawal, addr, memo,
+            ASSET_ID, withdrawal, addr, memo, WithdrawalFeeMode::Deducted,
         )
         .unwrap();
 
@@ -110,6 +113,29 @@ benchmarks! {
         assert!(XGatewayRecords::<T>::state_of(withdrawal_id).is_none());
     }
 
+    amend_withdrawal {
+        let caller: T::AccountId = alice::<T>();
+        let amount: BalanceOf<T> = 10_00000000.into();
+        XGatewayRecords::<T>::deposit(&caller, ASSET_ID, amount).unwrap();
+
+        let withdrawal = amount - 500.into();
+        let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
+        let memo = b"".to_vec().into();
+        Module::<T>::withdraw(
+            RawOrigin::Signed(caller.clone()).into(),
+            ASSET_ID, withdrawal, addr, memo, WithdrawalFeeMode::Deducted,
+        )
+        .unwrap();
+
+        let withdrawal_id: WithdrawalRecordId = 0;
+        let new_addr = b"1HeJ3ogYL2AqVEeJFHmpKYuJKkpNVuSEkU".to_vec();
+        let new_memo = b"memo".to_vec().into();
+    }: _(RawOrigin::Signed(caller.clone()), withdrawal_id, new_addr.clone(), new_memo)
+    verify {
+        let record = XGatewayRecords::<T>::pending_withdrawals(withdrawal_id).unwrap();
+        assert_eq!(record.addr(), &new_addr);
+    }
+
     setup_trustee {
         let caller: T::AccountId = alice::<T>();
         let hot = hex::decode("02df92e88c4380778c9c48268460a124a8f4e7da883f80477deaa644ced486efc6")
@@ -154,7 +180,7 @@ benchmarks! {
         let memo = b"".to_vec().into();
         Module::<T>::withdraw(
             RawOrigin::Signed(caller.clone()).into(),
-            ASSET_ID, withdrawal, addr, memo,
+            ASSET_ID, withdrawal, addr, memo, WithdrawalFeeMode::Deducted,
         )
         .unwrap();
         let withdrawal_id: WithdrawalRecordId = 0;
@@ -185,6 +211,38 @@ benchmarks! {
     verify {
         assert_eq!(Module::<T>::referral_binding_of(&who, Chain::Bitcoin), Some(who));
     }
+
+    set_withdrawal_fee_schedule {
+        let schedule = WithdrawalFeeSchedule {
+            flat: 1000.into(),
+            proportional: Permill::from_parts(1000),
+            native_rate: Some((1.into(), 10.into())),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(schedule.clone()))
+    verify {
+        assert_eq!(Module::<T>::withdrawal_fee_schedule_of(ASSET_ID), Some(schedule));
+    }
+
+    approve_withdrawal {
+        let caller: T::AccountId = alice::<T>();
+        TrusteeMultiSigAddr::<T>::insert(Chain::Bitcoin, caller.clone());
+        WithdrawalCapsOf::<T>::insert(ASSET_ID, WithdrawalCaps::default());
+
+        let amount: BalanceOf<T> = 10_00000000.into();
+        XGatewayRecords::<T>::deposit(&caller, ASSET_ID, amount).unwrap();
+        let addr = b"3PgYgJA6h5xPEc3HbnZrUZWkpRxuCZVyEP".to_vec();
+        let memo = b"".to_vec().into();
+        Module::<T>::withdraw(
+            RawOrigin::Signed(caller.clone()).into(),
+            ASSET_ID, amount, addr, memo, WithdrawalFeeMode::Deducted,
+        )
+        .unwrap();
+        let withdrawal_id: WithdrawalRecordId = 0;
+        assert!(XGatewayRecords::<T>::is_delayed(withdrawal_id));
+    }: _(RawOrigin::Signed(caller), withdrawal_id)
+    verify {
+        assert!(!XGatewayRecords::<T>::is_delayed(withdrawal_id));
+    }
 }
 
 #[cfg(test)]
@@ -198,11 +256,14 @@ mod tests {
         ExtBuilder::default().build().execute_with(|| {
             assert_ok!(test_benchmark_withdraw::<Test>());
             assert_ok!(test_benchmark_cancel_withdrawal::<Test>());
+            assert_ok!(test_benchmark_amend_withdrawal::<Test>());
             assert_ok!(test_benchmark_setup_trustee::<Test>());
             assert_ok!(test_benchmark_transition_trustee_session::<Test>());
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
             assert_ok!(test_benchmark_set_trustee_info_config::<Test>());
             assert_ok!(test_benchmark_force_set_referral_binding::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_fee_schedule::<Test>());
+            assert_ok!(test_benchmark_approve_withdrawal::<Test>());
         });
     }
 }
@@ -27,20 +27,25 @@ use frame_support::{
     ensure, IterableStorageMap,
 };
 use frame_system::{ensure_root, ensure_signed};
-use sp_runtime::traits::StaticLookup;
-use sp_std::{collections::btree_map::BTreeMap, convert::TryFrom, prelude::*};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{SaturatedConversion, Saturating, StaticLookup},
+};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use chainx_primitives::{AddrStr, AssetId, ChainAddress, Text};
 use xp_logging::{error, info};
 use xp_runtime::Memo;
-use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, ChainT, WithdrawalLimit};
-use xpallet_gateway_records::{WithdrawalRecordId, WithdrawalState};
+use xpallet_assets::{AssetRestrictions, BalanceOf, Chain, WithdrawalLimit};
+use xpallet_gateway_records::{
+    WithdrawalFee, WithdrawalFeeMode, WithdrawalFeeProvider, WithdrawalRecordId, WithdrawalState,
+};
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
-use self::traits::TrusteeForChain;
+use self::traits::ChainGateways;
 use self::types::{
     GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
-    TrusteeIntentionProps,
+    TrusteeIntentionProps, WithdrawalFeeSchedule,
 };
 pub use self::weights::WeightInfo;
 
@@ -51,13 +56,8 @@ pub trait Trait: xpallet_gateway_records::Trait {
 
     type DetermineMultisigAddress: MultisigAddressFor<Self::AccountId>;
 
-    // for bitcoin
-    type Bitcoin: ChainT<BalanceOf<Self>>;
-    type BitcoinTrustee: TrusteeForChain<
-        Self::AccountId,
-        trustees::bitcoin::BtcTrusteeType,
-        trustees::bitcoin::BtcTrusteeAddrInfo,
-    >;
+    /// The gateways of the supported cross chains, e.g. `(XGatewayBitcoin, XGatewayEthereum)`.
+    type Gateways: ChainGateways<Self::AccountId, BalanceOf<Self>>;
 
     type WeightInfo: WeightInfo;
 }
@@ -65,6 +65,7 @@ pub trait Trait: xpallet_gateway_records::Trait {
 decl_event!(
     pub enum Event<T> where
         <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>,
     {
         /// A (potential) trustee set the required properties. [who, chain, trustee_props]
         SetTrusteeProps(AccountId, Chain, GenericTrusteeIntentionProps),
@@ -72,6 +73,8 @@ decl_event!(
         ReferralBinded(AccountId, Chain, AccountId),
         /// The trustee set of a chain was changed. [chain, session_number, session_info]
         TrusteeSetChanged(Chain, u32, GenericTrusteeSessionInfo<AccountId>),
+        /// The withdrawal fee schedule of an asset was set. [asset_id, schedule]
+        WithdrawalFeeScheduleSet(AssetId, Option<WithdrawalFeeSchedule<Balance>>),
     }
 );
 
@@ -96,6 +99,8 @@ decl_error! {
         NotRegistered,
         /// just allow validator to register trustee
         NotValidator,
+        /// the withdrawal fee can not be paid in PCX for this asset
+        NativeFeeNotSupported,
     }
 }
 
@@ -142,6 +147,12 @@ decl_storage! {
         pub ReferralBindingOf get(fn referral_binding_of):
             double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) Chain
             => Option<T::AccountId>;
+
+        /// The withdrawal fee schedule of the corresponding asset.
+        ///
+        /// The flat fee of `WithdrawalLimit` is used if the schedule is not set.
+        pub WithdrawalFeeScheduleOf get(fn withdrawal_fee_schedule_of):
+            map hasher(twox_64_concat) AssetId => Option<WithdrawalFeeSchedule<BalanceOf<T>>>;
     }
     add_extra_genesis {
         config(trustees): Vec<(Chain, TrusteeInfoConfig, Vec<(T::AccountId, Text, Vec<u8>, Vec<u8>)>)>;
@@ -173,11 +184,19 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn integrity_test() {
+            if let Some(chain) = T::Gateways::duplicate_chain() {
+                panic!("The gateway of {:?} is registered more than once in `Gateways`", chain);
+            }
+        }
+
         /// Create a withdrawal.
         /// Withdraws some balances of `asset_id` to address `addr` of target chain.
         ///
         /// WithdrawalRecord State: `Applying`
         ///
+        /// The withdrawal fee is deducted from `value` or paid in PCX according to `fee_mode`.
+        ///
         /// NOTE: `ext` is for the compatibility purpose, e.g., EOS requires a memo when doing the transfer.
         #[weight = <T as Trait>::WeightInfo::withdraw()]
         pub fn withdraw(
@@ -185,7 +204,8 @@ decl_module! {
             #[compact] asset_id: AssetId,
             #[compact] value: BalanceOf<T>,
             addr: AddrStr,
-            ext: Memo
+            ext: Memo,
+            fee_mode: WithdrawalFeeMode
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
 
@@ -194,8 +214,9 @@ decl_module! {
                 xpallet_assets::Error::<T>::ActionNotAllowed,
             );
             Self::verify_withdrawal(asset_id, value, &addr, &ext)?;
+            let fee = Self::withdrawal_fee(asset_id, value, fee_mode)?;
 
-            xpallet_gateway_records::Module::<T>::withdraw(&who, asset_id, value, addr, ext)?;
+            xpallet_gateway_records::Module::<T>::withdraw(&who, asset_id, value, addr, ext, fee)?;
             Ok(())
         }
 
@@ -208,6 +229,24 @@ decl_module! {
             xpallet_gateway_records::Module::<T>::cancel_withdrawal(id, &from)
         }
 
+        /// Amend the address and memo of the withdrawal by the applicant.
+        ///
+        /// WithdrawalRecord State: `Applying`
+        #[weight = <T as Trait>::WeightInfo::amend_withdrawal()]
+        pub fn amend_withdrawal(
+            origin,
+            #[compact] id: WithdrawalRecordId,
+            addr: AddrStr,
+            ext: Memo
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(id)
+                .ok_or(xpallet_gateway_records::Error::<T>::NotExisted)?;
+            Self::verify_withdrawal(record.asset_id(), record.balance(), &addr, &ext)?;
+
+            xpallet_gateway_records::Module::<T>::amend_withdrawal(id, &who, addr, ext)
+        }
+
         /// Setup the trustee.
         #[weight = <T as Trait>::WeightInfo::setup_trustee()]
         pub fn setup_trustee(
@@ -256,16 +295,18 @@ decl_module! {
             state: WithdrawalState
         ) -> DispatchResult {
             let from = ensure_signed(origin)?;
-
-            let map = Self::trustee_multisigs();
-            let chain = map
-                .into_iter()
-                .find_map(|(chain, multisig)| if from == multisig { Some(chain) } else { None })
-                .ok_or(Error::<T>::InvalidMultisig)?;
-
+            let chain = Self::chain_of_trustee_multisig(&from)?;
             xpallet_gateway_records::Module::<T>::set_withdrawal_state_by_trustees(id, chain, state)
         }
 
+        /// Approve the withdrawal delayed by the withdrawal caps by the trustees.
+        #[weight = <T as Trait>::WeightInfo::approve_withdrawal()]
+        pub fn approve_withdrawal(origin, #[compact] id: WithdrawalRecordId) -> DispatchResult {
+            let from = ensure_signed(origin)?;
+            let chain = Self::chain_of_trustee_multisig(&from)?;
+            xpallet_gateway_records::Module::<T>::approve_withdrawal_by_trustees(id, chain)
+        }
+
         /// Set the config of trustee information.
         ///
         /// This is a root-only operation.
@@ -292,6 +333,24 @@ decl_module! {
             Self::set_referral_binding(chain, who, referral);
             Ok(())
         }
+
+        /// Set the withdrawal fee schedule of an asset, remove it if `schedule` is `None`.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee_schedule()]
+        pub fn set_withdrawal_fee_schedule(
+            origin,
+            #[compact] asset_id: AssetId,
+            schedule: Option<WithdrawalFeeSchedule<BalanceOf<T>>>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match schedule {
+                Some(ref schedule) => WithdrawalFeeScheduleOf::<T>::insert(asset_id, schedule),
+                None => WithdrawalFeeScheduleOf::<T>::remove(asset_id),
+            }
+            Self::deposit_event(Event::<T>::WithdrawalFeeScheduleSet(asset_id, schedule));
+            Ok(())
+        }
     }
 }
 
@@ -301,10 +360,48 @@ impl<T: Trait> Module<T> {
         asset_id: &AssetId,
     ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
         let chain = xpallet_assets_registrar::Module::<T>::chain_of(asset_id)?;
-        match chain {
-            Chain::Bitcoin => T::Bitcoin::withdrawal_limit(&asset_id),
-            _ => Err(Error::<T>::NotSupportedChain.into()),
+        let mut limit = T::Gateways::withdrawal_limit(chain, asset_id)
+            .ok_or(Error::<T>::NotSupportedChain)??;
+        if let Some(schedule) = Self::withdrawal_fee_schedule_of(asset_id) {
+            limit.fee = schedule.flat;
         }
+        Ok(limit)
+    }
+
+    /// Returns the fee of withdrawing `value` of the asset, paid in the way of `mode`.
+    pub fn withdrawal_fee(
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+        mode: WithdrawalFeeMode,
+    ) -> Result<WithdrawalFee<BalanceOf<T>>, DispatchError> {
+        let schedule = match Self::withdrawal_fee_schedule_of(asset_id) {
+            Some(schedule) => schedule,
+            None => WithdrawalFeeSchedule {
+                flat: Self::withdrawal_limit(&asset_id)?.fee,
+                ..Default::default()
+            },
+        };
+        let fee = schedule.flat.saturating_add(schedule.proportional * value);
+        let amount = match mode {
+            WithdrawalFeeMode::Deducted => {
+                // the rest of the withdrawal should be transferred to the target chain
+                ensure!(fee < value, Error::<T>::InvalidWithdrawal);
+                fee
+            }
+            WithdrawalFeeMode::Native => {
+                let (pcx, asset) = schedule
+                    .native_rate
+                    .ok_or(Error::<T>::NativeFeeNotSupported)?;
+                multiply_by_rational(
+                    fee.saturated_into(),
+                    pcx.saturated_into(),
+                    asset.saturated_into(),
+                )
+                .map_err(|_| Error::<T>::NativeFeeNotSupported)?
+                .saturated_into()
+            }
+        };
+        Ok(WithdrawalFee { amount, mode })
     }
 
     pub fn verify_withdrawal(
@@ -316,13 +413,8 @@ impl<T: Trait> Module<T> {
         ext.check_validity()?;
 
         let chain = xpallet_assets_registrar::Module::<T>::chain_of(&asset_id)?;
-        match chain {
-            Chain::Bitcoin => {
-                // bitcoin do not need memo
-                T::Bitcoin::check_addr(&addr, b"")?;
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
+        // the supported chains do not need memo
+        T::Gateways::check_addr(chain, &addr, b"").ok_or(Error::<T>::NotSupportedChain)??;
         // we could only split withdrawal limit due to a runtime-api would call `withdrawal_limit`
         // to export `WithdrawalLimit` for an asset.
         let limit = Self::withdrawal_limit(&asset_id)?;
@@ -334,6 +426,16 @@ impl<T: Trait> Module<T> {
     }
 }
 
+impl<T: Trait> WithdrawalFeeProvider<BalanceOf<T>> for Module<T> {
+    fn withdrawal_fee(
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+        mode: WithdrawalFeeMode,
+    ) -> Result<WithdrawalFee<BalanceOf<T>>, DispatchError> {
+        Self::withdrawal_fee(asset_id, value, mode)
+    }
+}
+
 pub fn is_valid_about<T: Trait>(about: &[u8]) -> DispatchResult {
     // TODO
     if about.len() > 128 {
@@ -354,14 +456,10 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         is_valid_about::<T>(&about)?;
 
-        let (hot, cold) = match chain {
-            Chain::Bitcoin => {
-                let hot = T::BitcoinTrustee::check_trustee_entity(&hot_entity)?;
-                let cold = T::BitcoinTrustee::check_trustee_entity(&cold_entity)?;
-                (hot.into(), cold.into())
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
+        let hot = T::Gateways::check_trustee_entity(chain, &hot_entity)
+            .ok_or(Error::<T>::NotSupportedChain)??;
+        let cold = T::Gateways::check_trustee_entity(chain, &cold_entity)
+            .ok_or(Error::<T>::NotSupportedChain)??;
 
         let props = GenericTrusteeIntentionProps(TrusteeIntentionProps::<Vec<u8>> {
             about,
@@ -399,25 +497,8 @@ impl<T: Trait> Module<T> {
             })?;
             props.push((accountid, p));
         }
-        let info = match chain {
-            Chain::Bitcoin => {
-                let props = props
-                    .into_iter()
-                    .map(|(id, prop)| {
-                        (
-                            id,
-                            TrusteeIntentionProps::<_>::try_from(prop)
-                                .expect("must decode succss from storage data"),
-                        )
-                    })
-                    .collect();
-                let session_info = T::BitcoinTrustee::generate_trustee_session_info(props, config)?;
-
-                session_info.into()
-            }
-            _ => return Err(Error::<T>::NotSupportedChain.into()),
-        };
-        Ok(info)
+        T::Gateways::generate_trustee_session_info(chain, props, config)
+            .ok_or(Error::<T>::NotSupportedChain)?
     }
 
     fn transition_trustee_session_impl(
@@ -464,6 +545,13 @@ impl<T: Trait> Module<T> {
 }
 
 impl<T: Trait> Module<T> {
+    fn chain_of_trustee_multisig(who: &T::AccountId) -> Result<Chain, DispatchError> {
+        Self::trustee_multisigs()
+            .into_iter()
+            .find_map(|(chain, multisig)| if *who == multisig { Some(chain) } else { None })
+            .ok_or_else(|| Error::<T>::InvalidMultisig.into())
+    }
+
     pub fn trustee_multisigs() -> BTreeMap<Chain, T::AccountId> {
         TrusteeMultiSigAddr::<T>::iter().collect()
     }
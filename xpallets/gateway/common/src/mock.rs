@@ -15,13 +15,15 @@ use sp_runtime::{
 };
 
 use chainx_primitives::AssetId;
-pub use xp_protocol::{X_BTC, X_ETH};
+pub use xp_protocol::{X_BTC, X_DOT, X_ETH};
 use xpallet_assets::{AssetRestrictions, BalanceOf, ChainT, WithdrawalLimit};
 use xpallet_assets_registrar::{AssetInfo, Chain};
+use xpallet_gateway_ethereum::{EthTrusteeAddrInfo, EthTrusteeType};
+use xpallet_gateway_polkadot::{DotTrusteeAddrInfo, DotTrusteeType};
 use xpallet_support::traits::{MultisigAddressFor, Validator};
 
 use crate::{
-    traits::TrusteeForChain,
+    traits::{ChainGateway, TrusteeForChain},
     trustees::bitcoin::{BtcTrusteeAddrInfo, BtcTrusteeMultisig, BtcTrusteeType},
     types::*,
 };
@@ -89,11 +91,15 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = ();
+    type AssetIssuance = ();
     type WeightInfo = ();
 }
 impl xpallet_assets::Trait for Test {
@@ -108,6 +114,9 @@ impl xpallet_assets::Trait for Test {
 
 impl xpallet_gateway_records::Trait for Test {
     type Event = ();
+    type WithdrawalWindow = ();
+    type MaxWithdrawalHistory = ();
+    type WithdrawalFeeProvider = crate::Module<Test>;
     type WeightInfo = ();
 }
 
@@ -173,6 +182,13 @@ impl<T: xpallet_gateway_bitcoin::Trait> ChainT<BalanceOf<T>> for MockBitcoin<T>
         xpallet_gateway_bitcoin::Module::<T>::withdrawal_limit(asset_id)
     }
 }
+impl<T: xpallet_gateway_bitcoin::Trait> ChainGateway<T::AccountId, BalanceOf<T>>
+    for MockBitcoin<T>
+{
+    type TrusteeEntity = BtcTrusteeType;
+    type TrusteeAddress = BtcTrusteeAddrInfo;
+    type Trustee = Self;
+}
 impl<T: xpallet_gateway_bitcoin::Trait>
     TrusteeForChain<T::AccountId, BtcTrusteeType, BtcTrusteeAddrInfo> for MockBitcoin<T>
 {
@@ -201,12 +217,91 @@ impl<T: xpallet_gateway_bitcoin::Trait>
         })
     }
 }
+pub struct MockEthereum;
+impl ChainT<Balance> for MockEthereum {
+    const ASSET_ID: u32 = X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+impl ChainGateway<AccountId, Balance> for MockEthereum {
+    type TrusteeEntity = EthTrusteeType;
+    type TrusteeAddress = EthTrusteeAddrInfo;
+    type Trustee = Self;
+}
+impl TrusteeForChain<AccountId, EthTrusteeType, EthTrusteeAddrInfo> for MockEthereum {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        let trustee_type =
+            EthTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| "InvalidPublicKey")?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let len = props.len();
+        let (trustee_list, props): (Vec<_>, Vec<_>) = props.into_iter().unzip();
+        Ok(TrusteeSessionInfo {
+            trustee_list,
+            threshold: len as u16,
+            hot_address: EthTrusteeAddrInfo {
+                signers: props.iter().map(|p| p.hot_entity.address()).collect(),
+                threshold: len as u16,
+            },
+            cold_address: EthTrusteeAddrInfo {
+                signers: props.iter().map(|p| p.cold_entity.address()).collect(),
+                threshold: len as u16,
+            },
+        })
+    }
+}
+pub struct MockPolkadot;
+impl ChainT<Balance> for MockPolkadot {
+    const ASSET_ID: u32 = X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+impl ChainGateway<AccountId, Balance> for MockPolkadot {
+    type TrusteeEntity = DotTrusteeType;
+    type TrusteeAddress = DotTrusteeAddrInfo;
+    type Trustee = Self;
+}
+impl TrusteeForChain<AccountId, DotTrusteeType, DotTrusteeAddrInfo> for MockPolkadot {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        let trustee_type =
+            DotTrusteeType::try_from(raw_addr.to_vec()).map_err(|_| "InvalidPublicKey")?;
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        _: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        let len = props.len();
+        let (trustee_list, props): (Vec<_>, Vec<_>) = props.into_iter().unzip();
+        Ok(TrusteeSessionInfo {
+            trustee_list,
+            threshold: len as u16,
+            hot_address: DotTrusteeAddrInfo::new(
+                props.iter().map(|p| p.hot_entity.account()).collect(),
+                len as u16,
+            ),
+            cold_address: DotTrusteeAddrInfo::new(
+                props.iter().map(|p| p.cold_entity.account()).collect(),
+                len as u16,
+            ),
+        })
+    }
+}
 impl crate::Trait for Test {
     type Event = ();
     type Validator = AlwaysValidator;
     type DetermineMultisigAddress = MultisigAddr;
-    type Bitcoin = MockBitcoin<Test>;
-    type BitcoinTrustee = MockBitcoin<Test>;
+    type Gateways = (MockBitcoin<Test>, MockEthereum, MockPolkadot);
     type WeightInfo = ();
 }
 
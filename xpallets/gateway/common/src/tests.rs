@@ -1,8 +1,123 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use crate::mock::ExtBuilder;
+use frame_support::{assert_noop, assert_ok, traits::IntegrityTest};
+use frame_system::RawOrigin;
+use sp_core::{crypto::UncheckedInto, H256};
+use sp_runtime::Permill;
+
+use xpallet_assets::Chain;
+use xpallet_gateway_records::{WithdrawalFee, WithdrawalFeeMode};
+
+use crate::mock::{
+    AccountId, Balance, ExtBuilder, MockBitcoin, MockEthereum, MockPolkadot, Test, X_BTC,
+};
+use crate::traits::ChainGateways;
+use crate::types::WithdrawalFeeSchedule;
+use crate::{Error, Module};
 
 #[test]
 fn base() {
     ExtBuilder::default().build().execute_with(|| {})
 }
+
+#[test]
+fn test_chain_gateways() {
+    ExtBuilder::default().build().execute_with(|| {
+        let who: AccountId = H256::repeat_byte(4).unchecked_into();
+        let key = vec![4; 32];
+        assert_ok!(Module::<Test>::setup_trustee_impl(
+            who.clone(),
+            Chain::Polkadot,
+            b"".to_vec(),
+            key.clone(),
+            key.clone(),
+        ));
+        assert!(Module::<Test>::trustee_intention_props_of(&who, Chain::Polkadot).is_some());
+
+        // no gateway for ChainX
+        assert_noop!(
+            Module::<Test>::setup_trustee_impl(who, Chain::ChainX, b"".to_vec(), key.clone(), key),
+            Error::<Test>::NotSupportedChain
+        );
+    })
+}
+
+#[test]
+fn test_duplicate_chain_gateways() {
+    <Module<Test> as IntegrityTest>::integrity_test();
+
+    type Gateways = (MockBitcoin<Test>, MockEthereum, MockPolkadot);
+    assert_eq!(
+        <Gateways as ChainGateways<AccountId, Balance>>::chains(),
+        vec![Chain::Bitcoin, Chain::Ethereum, Chain::Polkadot]
+    );
+    assert_eq!(
+        <Gateways as ChainGateways<AccountId, Balance>>::duplicate_chain(),
+        None
+    );
+
+    type DuplicateGateways = (MockEthereum, MockPolkadot, MockEthereum);
+    assert_eq!(
+        <DuplicateGateways as ChainGateways<AccountId, Balance>>::duplicate_chain(),
+        Some(Chain::Ethereum)
+    );
+}
+
+#[test]
+fn test_withdrawal_fee_schedule() {
+    ExtBuilder::default().build().execute_with(|| {
+        let fee = |value, mode| Module::<Test>::withdrawal_fee(X_BTC, value, mode);
+        // fallback to the flat fee of the withdrawal limit (0 in the mock)
+        assert_eq!(
+            fee(1000, WithdrawalFeeMode::Deducted),
+            Ok(WithdrawalFee {
+                amount: 0,
+                mode: WithdrawalFeeMode::Deducted,
+            })
+        );
+        assert_noop!(
+            fee(1000, WithdrawalFeeMode::Native),
+            Error::<Test>::NativeFeeNotSupported
+        );
+
+        let schedule = WithdrawalFeeSchedule {
+            flat: 1000,
+            proportional: Permill::from_percent(1),
+            native_rate: Some((3, 1)),
+        };
+        assert_ok!(Module::<Test>::set_withdrawal_fee_schedule(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(schedule)
+        ));
+        assert_eq!(Module::<Test>::withdrawal_limit(&X_BTC).unwrap().fee, 1000);
+
+        assert_eq!(
+            fee(100_000, WithdrawalFeeMode::Deducted),
+            Ok(WithdrawalFee {
+                amount: 2000,
+                mode: WithdrawalFeeMode::Deducted,
+            })
+        );
+        assert_eq!(
+            fee(100_000, WithdrawalFeeMode::Native),
+            Ok(WithdrawalFee {
+                amount: 6000,
+                mode: WithdrawalFeeMode::Native,
+            })
+        );
+        // the deducted fee must be less than the withdrawal
+        assert_noop!(
+            fee(1000, WithdrawalFeeMode::Deducted),
+            Error::<Test>::InvalidWithdrawal
+        );
+        assert_eq!(fee(1000, WithdrawalFeeMode::Native).unwrap().amount, 3030);
+
+        assert_ok!(Module::<Test>::set_withdrawal_fee_schedule(
+            RawOrigin::Root.into(),
+            X_BTC,
+            None
+        ));
+        assert_eq!(Module::<Test>::withdrawal_fee_schedule_of(X_BTC), None);
+    })
+}
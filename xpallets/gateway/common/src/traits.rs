@@ -1,12 +1,15 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
-use frame_support::dispatch::DispatchError;
-use sp_std::{convert::TryFrom, prelude::Vec};
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_std::{convert::TryFrom, prelude::*};
 
 use chainx_primitives::{AssetId, ReferralId};
-use xpallet_assets::Chain;
+use xpallet_assets::{Chain, ChainT, WithdrawalLimit};
 
-use crate::types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo};
+use crate::types::{
+    GenericTrusteeIntentionProps, GenericTrusteeSessionInfo, TrusteeInfoConfig,
+    TrusteeIntentionProps, TrusteeSessionInfo,
+};
 
 pub trait BytesLike: Into<Vec<u8>> + TryFrom<Vec<u8>> {}
 impl<T: Into<Vec<u8>> + TryFrom<Vec<u8>>> BytesLike for T {}
@@ -24,6 +27,178 @@ pub trait TrusteeForChain<AccountId, TrusteeEntity: BytesLike, TrusteeAddress: B
     ) -> Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, DispatchError>;
 }
 
+/// The gateway of a cross chain, i.e. the `ChainT` and `TrusteeForChain` implementer.
+pub trait ChainGateway<AccountId, Balance: Default>: ChainT<Balance> {
+    type TrusteeEntity: BytesLike;
+    type TrusteeAddress: BytesLike;
+    type Trustee: TrusteeForChain<AccountId, Self::TrusteeEntity, Self::TrusteeAddress>;
+
+    /// Check the raw trustee entity and returns the generic bytes of it.
+    fn generic_trustee_entity(raw_entity: &[u8]) -> Result<Vec<u8>, DispatchError> {
+        Self::Trustee::check_trustee_entity(raw_entity).map(Into::into)
+    }
+
+    /// Generate the generic trustee session info from the generic intention props in storage.
+    fn generic_trustee_session_info(
+        props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<GenericTrusteeSessionInfo<AccountId>, DispatchError> {
+        let props = props
+            .into_iter()
+            .map(|(id, prop)| {
+                (
+                    id,
+                    TrusteeIntentionProps::<_>::try_from(prop)
+                        .expect("must decode succss from storage data"),
+                )
+            })
+            .collect();
+        let session_info = Self::Trustee::generate_trustee_session_info(props, config)?;
+        Ok(session_info.into())
+    }
+}
+
+/// The registry of the cross chain gateways, which is implemented for the tuples of
+/// `ChainGateway`, e.g. `(XGatewayBitcoin, XGatewayEthereum)`.
+///
+/// All methods return `None` if there is no gateway for the `chain`.
+pub trait ChainGateways<AccountId, Balance> {
+    /// Returns the chains of the gateways in the order of the tuple.
+    fn chains() -> Vec<Chain>;
+
+    /// Returns the first chain which has more than one gateway.
+    fn duplicate_chain() -> Option<Chain> {
+        let chains = Self::chains();
+        chains
+            .iter()
+            .enumerate()
+            .find(|(i, chain)| chains[i + 1..].contains(chain))
+            .map(|(_, chain)| *chain)
+    }
+
+    fn withdrawal_limit(
+        chain: Chain,
+        asset_id: &AssetId,
+    ) -> Option<Result<WithdrawalLimit<Balance>, DispatchError>>;
+
+    fn check_addr(chain: Chain, addr: &[u8], ext: &[u8]) -> Option<DispatchResult>;
+
+    fn check_trustee_entity(
+        chain: Chain,
+        raw_entity: &[u8],
+    ) -> Option<Result<Vec<u8>, DispatchError>>;
+
+    fn generate_trustee_session_info(
+        chain: Chain,
+        props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        config: TrusteeInfoConfig,
+    ) -> Option<Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>>;
+}
+
+impl<AccountId, Balance> ChainGateways<AccountId, Balance> for () {
+    fn chains() -> Vec<Chain> {
+        Vec::new()
+    }
+
+    fn withdrawal_limit(
+        _: Chain,
+        _: &AssetId,
+    ) -> Option<Result<WithdrawalLimit<Balance>, DispatchError>> {
+        None
+    }
+
+    fn check_addr(_: Chain, _: &[u8], _: &[u8]) -> Option<DispatchResult> {
+        None
+    }
+
+    fn check_trustee_entity(_: Chain, _: &[u8]) -> Option<Result<Vec<u8>, DispatchError>> {
+        None
+    }
+
+    fn generate_trustee_session_info(
+        _: Chain,
+        _: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+        _: TrusteeInfoConfig,
+    ) -> Option<Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>> {
+        None
+    }
+}
+
+macro_rules! impl_chain_gateways_for_tuples {
+    ($($gateway:ident),+) => {
+        impl<AccountId, Balance: Default, $($gateway: ChainGateway<AccountId, Balance>),+>
+            ChainGateways<AccountId, Balance> for ($($gateway,)+)
+        {
+            fn chains() -> Vec<Chain> {
+                vec![$(<$gateway as ChainT<Balance>>::chain()),+]
+            }
+
+            fn withdrawal_limit(
+                chain: Chain,
+                asset_id: &AssetId,
+            ) -> Option<Result<WithdrawalLimit<Balance>, DispatchError>> {
+                $(
+                    if <$gateway as ChainT<Balance>>::chain() == chain {
+                        return Some(<$gateway as ChainT<Balance>>::withdrawal_limit(asset_id));
+                    }
+                )+
+                None
+            }
+
+            fn check_addr(chain: Chain, addr: &[u8], ext: &[u8]) -> Option<DispatchResult> {
+                $(
+                    if <$gateway as ChainT<Balance>>::chain() == chain {
+                        return Some(<$gateway as ChainT<Balance>>::check_addr(addr, ext));
+                    }
+                )+
+                None
+            }
+
+            fn check_trustee_entity(
+                chain: Chain,
+                raw_entity: &[u8],
+            ) -> Option<Result<Vec<u8>, DispatchError>> {
+                $(
+                    if <$gateway as ChainT<Balance>>::chain() == chain {
+                        return Some(
+                            <$gateway as ChainGateway<AccountId, Balance>>::generic_trustee_entity(
+                                raw_entity,
+                            ),
+                        );
+                    }
+                )+
+                None
+            }
+
+            fn generate_trustee_session_info(
+                chain: Chain,
+                props: Vec<(AccountId, GenericTrusteeIntentionProps)>,
+                config: TrusteeInfoConfig,
+            ) -> Option<Result<GenericTrusteeSessionInfo<AccountId>, DispatchError>> {
+                $(
+                    if <$gateway as ChainT<Balance>>::chain() == chain {
+                        return Some(
+                            <$gateway as ChainGateway<AccountId, Balance>>::generic_trustee_session_info(
+                                props, config,
+                            ),
+                        );
+                    }
+                )+
+                None
+            }
+        }
+    };
+}
+
+impl_chain_gateways_for_tuples!(A);
+impl_chain_gateways_for_tuples!(A, B);
+impl_chain_gateways_for_tuples!(A, B, C);
+impl_chain_gateways_for_tuples!(A, B, C, D);
+impl_chain_gateways_for_tuples!(A, B, C, D, E);
+impl_chain_gateways_for_tuples!(A, B, C, D, E, F);
+impl_chain_gateways_for_tuples!(A, B, C, D, E, F, G);
+impl_chain_gateways_for_tuples!(A, B, C, D, E, F, G, H);
+
 pub trait TrusteeSession<AccountId, TrusteeAddress: BytesLike> {
     fn trustee_session(
         number: u32,
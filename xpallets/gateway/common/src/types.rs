@@ -4,7 +4,7 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{Permill, RuntimeDebug};
 use sp_std::{convert::TryFrom, prelude::Vec};
 
 use chainx_primitives::Text;
@@ -20,6 +20,20 @@ pub struct TrusteeInfoConfig {
     pub max_trustee_count: u32,
 }
 
+/// The withdrawal fee schedule of an asset.
+///
+/// The fee of withdrawing `value` is `flat + proportional * value` in the withdrawn asset.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalFeeSchedule<Balance> {
+    pub flat: Balance,
+    pub proportional: Permill,
+    /// The exchange rate `(pcx, asset)` used to pay the fee in PCX,
+    /// paying in PCX is not allowed if it's `None`.
+    pub native_rate: Option<(Balance, Balance)>,
+}
+
 /// The trustee session info.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -32,26 +32,34 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
     fn withdraw() -> Weight;
     fn cancel_withdrawal() -> Weight;
+    fn amend_withdrawal() -> Weight;
     fn setup_trustee() -> Weight;
     fn transition_trustee_session(u: u32) -> Weight;
     fn set_withdrawal_state() -> Weight;
     fn set_trustee_info_config() -> Weight;
     fn force_set_referral_binding() -> Weight;
+    fn set_withdrawal_fee_schedule() -> Weight;
+    fn approve_withdrawal() -> Weight;
 }
 
 /// Weights for xpallet_gateway_common using the Substrate node and recommended hardware.
 pub struct SubstrateWeight<T>(PhantomData<T>);
 impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn withdraw() -> Weight {
-        (226_245_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(10 as Weight))
-            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+        (258_430_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
     }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(6 as Weight))
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
+    fn amend_withdrawal() -> Weight {
+        (61_290_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
     fn setup_trustee() -> Weight {
         (40_920_000 as Weight)
             .saturating_add(T::DbWeight::get().reads(1 as Weight))
@@ -74,20 +82,33 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_withdrawal_fee_schedule() -> Weight {
+        (16_825_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn approve_withdrawal() -> Weight {
+        (52_136_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
     fn withdraw() -> Weight {
-        (226_245_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+        (258_430_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
     }
     fn cancel_withdrawal() -> Weight {
         (130_921_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(6 as Weight))
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
+    fn amend_withdrawal() -> Weight {
+        (61_290_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
     fn setup_trustee() -> Weight {
         (40_920_000 as Weight)
             .saturating_add(RocksDbWeight::get().reads(1 as Weight))
@@ -110,4 +131,12 @@ impl WeightInfo for () {
     fn force_set_referral_binding() -> Weight {
         (30_667_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_withdrawal_fee_schedule() -> Weight {
+        (16_825_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn approve_withdrawal() -> Weight {
+        (52_136_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
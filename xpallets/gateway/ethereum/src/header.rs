@@ -0,0 +1,159 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{StorageMap, StorageValue};
+use rlp::{DecoderError, Rlp};
+use sp_core::{H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::DispatchResult;
+use sp_std::{cmp::Ordering, prelude::*};
+
+use xp_logging::{error, info};
+
+use crate::types::{EthHeader, EthHeaderIndex};
+use crate::{ConfirmedIndex, Error, MainChain, Module, Trait};
+
+fn decode_h256(rlp: &Rlp) -> Result<H256, DecoderError> {
+    let data = rlp.data()?;
+    if data.len() != 32 {
+        return Err(DecoderError::RlpInvalidLength);
+    }
+    Ok(H256::from_slice(data))
+}
+
+fn decode_u256(rlp: &Rlp) -> Result<U256, DecoderError> {
+    let data = rlp.data()?;
+    if data.len() > 32 {
+        return Err(DecoderError::RlpIsTooBig);
+    }
+    Ok(U256::from_big_endian(data))
+}
+
+/// Decode the rlp encoded Ethereum block header.
+///
+/// The header hash is the keccak256 of the whole rlp encoded header, so that
+/// the extra fields of the later hard forks (e.g. `baseFeePerGas`) are accepted.
+pub fn decode_header(raw: &[u8]) -> Result<EthHeader, DecoderError> {
+    let rlp = Rlp::new(raw);
+    if rlp.payload_info()?.total() != raw.len() {
+        return Err(DecoderError::RlpInconsistentLengthAndData);
+    }
+    if rlp.item_count()? < 15 {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+    Ok(EthHeader {
+        hash: H256::from(keccak_256(raw)),
+        parent_hash: decode_h256(&rlp.at(0)?)?,
+        transactions_root: decode_h256(&rlp.at(4)?)?,
+        receipts_root: decode_h256(&rlp.at(5)?)?,
+        difficulty: decode_u256(&rlp.at(7)?)?,
+        number: rlp.val_at(8)?,
+        timestamp: rlp.val_at(11)?,
+    })
+}
+
+/// Basic checks of a new header against its parent.
+///
+/// NOTE: the proof-of-work is not verified, the headers are only accepted from the
+/// trustees, and the chain is anchored by a checkpoint set by root.
+pub fn check_header<T: Trait>(header: &EthHeader, parent: &EthHeader) -> DispatchResult {
+    if header.number != parent.number + 1 {
+        error!(
+            "[check_header] Header number ({}) must be parent number ({}) + 1",
+            header.number, parent.number
+        );
+        return Err(Error::<T>::InvalidHeaderNumber.into());
+    }
+    if header.timestamp <= parent.timestamp {
+        error!(
+            "[check_header] Header timestamp ({}) must be greater than parent's ({})",
+            header.timestamp, parent.timestamp
+        );
+        return Err(Error::<T>::InvalidTimestamp.into());
+    }
+    Ok(())
+}
+
+/// Look back the headers to pick the confirmed index,
+/// return the header indexes on the look back path.
+///
+/// Same as the bitcoin gateway:
+/// confirmed_number = now_number - (confirmations - 1)
+fn look_back_confirmed_header<T: Trait>(
+    header: &EthHeader,
+) -> (Option<EthHeaderIndex>, Vec<EthHeaderIndex>) {
+    let confirmations = Module::<T>::confirmation_number();
+    let mut chain = Vec::with_capacity(confirmations as usize);
+    let mut prev_hash = header.parent_hash;
+
+    chain.push(EthHeaderIndex {
+        hash: header.hash,
+        number: header.number,
+    });
+    for cnt in 1..confirmations {
+        if let Some(current) = Module::<T>::headers(&prev_hash) {
+            chain.push(EthHeaderIndex {
+                hash: prev_hash,
+                number: current.number,
+            });
+            prev_hash = current.parent_hash;
+        } else {
+            // reach the checkpoint
+            info!(
+                "[update_confirmed_header] Can not find header ({:?}), current reverse count:{}",
+                prev_hash, cnt
+            );
+            break;
+        }
+    }
+    if chain.len() == confirmations as usize {
+        (chain.last().cloned(), chain)
+    } else {
+        (None, chain)
+    }
+}
+
+pub fn update_confirmed_header<T: Trait>(header: &EthHeader) -> Option<EthHeaderIndex> {
+    let (confirmed, chain) = look_back_confirmed_header::<T>(header);
+    for index in chain {
+        set_main_chain::<T>(index.number, index.hash);
+    }
+    confirmed.map(|index| {
+        // the confirmed index never goes back to the ancestors of the checkpoint.
+        match ConfirmedIndex::get() {
+            Some(current) if current.number >= index.number => current,
+            _ => {
+                ConfirmedIndex::put(index);
+                index
+            }
+        }
+    })
+}
+
+pub fn set_main_chain<T: Trait>(number: u64, main_hash: H256) {
+    let hashes = Module::<T>::block_hash_for(&number);
+    for hash in hashes {
+        if hash == main_hash {
+            MainChain::insert(&hash, true);
+        } else {
+            MainChain::remove(&hash);
+        }
+    }
+}
+
+pub fn check_confirmed_header<T: Trait>(header: &EthHeader) -> DispatchResult {
+    let (confirmed, _) = look_back_confirmed_header::<T>(header);
+    if let (Some(current_confirmed), Some(now_confirmed)) = (ConfirmedIndex::get(), confirmed) {
+        return match current_confirmed.number.cmp(&now_confirmed.number) {
+            Ordering::Greater => Ok(()),
+            Ordering::Equal if current_confirmed.hash == now_confirmed.hash => Ok(()),
+            _ => {
+                error!(
+                    "[check_confirmed_header] Fork before the confirmed header, current:{:?}, now:{:?}",
+                    current_confirmed, now_confirmed
+                );
+                Err(Error::<T>::AncientFork.into())
+            }
+        };
+    }
+    Ok(())
+}
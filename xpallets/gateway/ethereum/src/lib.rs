@@ -0,0 +1,410 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for eth-bridge
+//!
+//! The Ethereum headers are relayed by the trustees on top of a checkpoint set by root,
+//! the deposits and withdrawals are proved by the Merkle-Patricia proofs of the receipts
+//! which contain the events of the lock contract.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod header;
+mod proof;
+pub mod trustee;
+mod tx;
+mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::EnsureOrigin,
+    weights::Pays,
+};
+use frame_system::ensure_root;
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::traits::ChainGateway;
+use xpallet_gateway_records::WithdrawalRecordId;
+use xpallet_support::try_addr;
+
+pub use self::trustee::{EthTrusteeAddrInfo, EthTrusteeType};
+pub use self::types::{EthAddress, EthHeader, EthHeaderIndex, EthReceiptProof, EthTxResult};
+pub use self::weights::WeightInfo;
+
+pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Ethereum module
+    pub enum Error for Module<T: Trait> {
+        /// Cannot decode the rlp encoded header or receipt
+        DeserializeErr,
+        /// Header already exists
+        ExistingHeader,
+        /// Can't find previous header
+        PrevHeaderNotExisted,
+        /// The header number is not the successor of the parent
+        InvalidHeaderNumber,
+        /// The header timestamp is not greater than the parent
+        InvalidTimestamp,
+        /// Not Found
+        HeaderNotFound,
+        /// Fork is too long to proceed
+        AncientFork,
+        /// The tx is not yet confirmed, i.e, the block of which is not confirmed.
+        UnconfirmedTx,
+        /// Invalid Merkle-Patricia proof of the tx or receipt
+        BadMerkleProof,
+        /// The tx was reverted on Ethereum
+        RevertedTx,
+        /// reject replay proccessed tx
+        ReplayedTx,
+        /// process tx failed
+        ProcessTxFailed,
+        /// invalid ethereum address
+        InvalidAddress,
+        /// invalid ethereum public key
+        InvalidPublicKey,
+        /// duplicated pubkey for trustees
+        DuplicatedKeys,
+        /// invalid trustee count
+        InvalidTrusteeCount,
+        /// the token contract is not mapped to any asset
+        UnknownToken,
+        /// the asset does not belong to Ethereum
+        InvalidAsset,
+        /// the recipient of the deposit is not a valid account
+        InvalidRecipient,
+        /// the deposit amount overflows
+        InvalidAmount,
+        /// the unlocked event does not match the withdrawal record
+        MismatchedWithdrawal,
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>
+    {
+        /// An Ethereum header was inserted. [eth_header_hash]
+        HeaderInserted(H256),
+        /// An Ethereum checkpoint was set. [eth_header_hash, number]
+        CheckpointSet(H256, u64),
+        /// An Ethereum transaction was processed. [tx_hash, block_hash, tx_result]
+        TxProcessed(H256, H256, EthTxResult),
+        /// An account deposited some token. [tx_hash, who, asset_id, amount]
+        Deposited(H256, AccountId, AssetId, Balance),
+        /// A withdrawal was released by the lock contract. [tx_hash, withdrawal_id]
+        Withdrawn(H256, WithdrawalRecordId),
+        /// An ERC-20 token contract was mapped to an asset. [token, asset_id]
+        Erc20Registered(EthAddress, AssetId),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayEthereum {
+        /// best header info
+        pub BestIndex get(fn best_index): EthHeaderIndex;
+        /// confirmed header info
+        pub ConfirmedIndex get(fn confirmed_index): Option<EthHeaderIndex>;
+        /// block hash list for a number, include forked header hash
+        pub BlockHashFor get(fn block_hash_for): map hasher(twox_64_concat) u64 => Vec<H256>;
+        /// mark this blockhash is in mainchain
+        pub MainChain get(fn main_chain): map hasher(identity) H256 => bool;
+        /// all valid headers (include forked headers)
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<EthHeader>;
+
+        /// the number of confirmations of a header
+        pub ConfirmationNumber get(fn confirmation_number): u32 = 12;
+        /// the address of the lock contract on Ethereum
+        pub LockContract get(fn lock_contract): EthAddress;
+        /// the asset corresponding to the ERC-20 token contract, the zero address stands for ETH
+        pub Erc20Assets get(fn erc20_assets): map hasher(identity) EthAddress => Option<AssetId>;
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+        fn deposit_event() = default;
+
+        /// Push a rlp encoded header, the parent of which must exist.
+        #[weight = <T as Trait>::WeightInfo::push_header()]
+        pub fn push_header(origin, header: Vec<u8>) -> DispatchResultWithPostInfo {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            let header = header::decode_header(&header).map_err(|_| Error::<T>::DeserializeErr)?;
+            debug!("[push_header] header:{:?}", header);
+
+            Self::apply_push_header(header)?;
+
+            // Relayer does not pay a fee.
+            Ok(Pays::No.into())
+        }
+
+        /// Push the proof of a transaction and its receipt, which would be processed
+        /// according to the events of the lock contract.
+        #[weight = <T as Trait>::WeightInfo::push_transaction()]
+        pub fn push_transaction(origin, proof: EthReceiptProof) -> DispatchResultWithPostInfo {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            debug!(
+                "[push_transaction] block_hash:{:?}, tx_index:{}",
+                proof.block_hash, proof.tx_index
+            );
+
+            Self::apply_push_transaction(proof)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Dangerous! Set a trusted rlp encoded header as the checkpoint,
+        /// which would be the best and confirmed header.
+        #[weight = <T as Trait>::WeightInfo::set_checkpoint()]
+        pub fn set_checkpoint(origin, header: Vec<u8>) -> DispatchResult {
+            ensure_root(origin)?;
+            let header = header::decode_header(&header).map_err(|_| Error::<T>::DeserializeErr)?;
+            Self::apply_checkpoint(header);
+            Ok(())
+        }
+
+        /// Set the confirmation number of the headers.
+        #[weight = <T as Trait>::WeightInfo::set_confirmation_number()]
+        pub fn set_confirmation_number(origin, #[compact] confirmations: u32) -> DispatchResult {
+            ensure_root(origin)?;
+            ConfirmationNumber::put(confirmations);
+            Ok(())
+        }
+
+        /// Set the address of the lock contract.
+        #[weight = <T as Trait>::WeightInfo::set_lock_contract()]
+        pub fn set_lock_contract(origin, contract: EthAddress) -> DispatchResult {
+            ensure_root(origin)?;
+            LockContract::put(contract);
+            Ok(())
+        }
+
+        /// Map an ERC-20 token contract to an asset of Ethereum, the zero address stands for ETH.
+        #[weight = <T as Trait>::WeightInfo::register_erc20()]
+        pub fn register_erc20(origin, token: EthAddress, #[compact] asset_id: AssetId) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::ensure_ethereum_asset(&asset_id)?;
+            Erc20Assets::insert(&token, asset_id);
+            Self::deposit_event(Event::<T>::Erc20Registered(token, asset_id));
+            Ok(())
+        }
+
+        /// Trustees mark the withdrawals as `Processing` before signing the release on Ethereum.
+        #[weight = <T as Trait>::WeightInfo::process_withdrawals(withdrawal_id_list.len() as u32)]
+        pub fn process_withdrawals(origin, withdrawal_id_list: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            info!("[process_withdrawals] withdrawal list:{:?}", withdrawal_id_list);
+            xpallet_gateway_records::Module::<T>::process_withdrawals(
+                &withdrawal_id_list,
+                Chain::Ethereum,
+            )
+        }
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_ETH;
+
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        let address = Self::verify_eth_address(addr).map_err(|err| {
+            error!(
+                "[verify_eth_address] Verify failed, error:{:?}, source addr:{:?}",
+                err,
+                try_addr(addr)
+            );
+            err
+        })?;
+        // do not allow withdraw to the lock contract
+        if address.is_zero() || address == Self::lock_contract() {
+            return Err(Error::<T>::InvalidAddress.into());
+        }
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        if xpallet_assets_registrar::Module::<T>::chain_of(asset_id)? != Chain::Ethereum {
+            return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
+        }
+        // the fee of the Ethereum assets is set by the withdrawal fee schedule of gateway-common.
+        Ok(WithdrawalLimit::default())
+    }
+}
+
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    type TrusteeEntity = EthTrusteeType;
+    type TrusteeAddress = EthTrusteeAddrInfo;
+    type Trustee = Self;
+}
+
+impl<T: Trait> Module<T> {
+    /// Verify the hex encoded address, e.g. "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf".
+    pub fn verify_eth_address(data: &[u8]) -> Result<EthAddress, DispatchError> {
+        let data = if data.starts_with(b"0x") {
+            &data[2..]
+        } else {
+            data
+        };
+        if data.len() != 40 {
+            return Err(Error::<T>::InvalidAddress.into());
+        }
+        let raw = hex::decode(data).map_err(|_| Error::<T>::InvalidAddress)?;
+        Ok(EthAddress::from_slice(&raw))
+    }
+
+    fn ensure_ethereum_asset(asset_id: &AssetId) -> DispatchResult {
+        let chain = xpallet_assets_registrar::Module::<T>::chain_of(asset_id)?;
+        ensure!(chain == Chain::Ethereum, Error::<T>::InvalidAsset);
+        Ok(())
+    }
+
+    /// Returns the asset of the token contract, the zero address stands for ETH.
+    pub fn asset_of_token(token: &EthAddress) -> Result<AssetId, DispatchError> {
+        match Self::erc20_assets(token) {
+            Some(asset_id) => Ok(asset_id),
+            None if token.is_zero() => Ok(<Self as ChainT<_>>::ASSET_ID),
+            None => Err(Error::<T>::UnknownToken.into()),
+        }
+    }
+
+    fn insert_header(header: &EthHeader) {
+        Headers::insert(&header.hash, header.clone());
+        BlockHashFor::mutate(header.number, |v| {
+            if !v.contains(&header.hash) {
+                v.push(header.hash);
+            }
+        });
+    }
+
+    fn apply_checkpoint(header: EthHeader) {
+        let index = EthHeaderIndex {
+            hash: header.hash,
+            number: header.number,
+        };
+        Self::insert_header(&header);
+        header::set_main_chain::<T>(header.number, header.hash);
+        BestIndex::put(index);
+        ConfirmedIndex::put(index);
+        info!("[apply_checkpoint] Set checkpoint:{:?}", index);
+        Self::deposit_event(Event::<T>::CheckpointSet(header.hash, header.number));
+    }
+
+    fn apply_push_header(header: EthHeader) -> DispatchResult {
+        if Self::headers(&header.hash).is_some() {
+            error!(
+                "[apply_push_header] The ETH header already exists, hash:{:?}",
+                header.hash
+            );
+            return Err(Error::<T>::ExistingHeader.into());
+        }
+        let parent = Self::headers(&header.parent_hash).ok_or_else(|| {
+            error!(
+                "[apply_push_header] Can not find parent header, current header:{:?}",
+                header
+            );
+            Error::<T>::PrevHeaderNotExisted
+        })?;
+        header::check_header::<T>(&header, &parent)?;
+
+        with_transaction_result(|| {
+            Self::insert_header(&header);
+
+            let best_index = Self::best_index();
+            if header.number > best_index.number {
+                let confirmed_index = header::update_confirmed_header::<T>(&header);
+                info!(
+                    "[apply_push_header] Update new number:{}, hash:{:?}, confirm:{:?}",
+                    header.number, header.hash, confirmed_index
+                );
+                BestIndex::put(EthHeaderIndex {
+                    hash: header.hash,
+                    number: header.number,
+                });
+            } else {
+                // forked chain
+                info!(
+                    "[apply_push_header] Best index {} larger than this number {}",
+                    best_index.number, header.number
+                );
+                header::check_confirmed_header::<T>(&header)?;
+            }
+            Self::deposit_event(Event::<T>::HeaderInserted(header.hash));
+            Ok(())
+        })
+    }
+
+    fn apply_push_transaction(proof: EthReceiptProof) -> DispatchResult {
+        let block_hash = proof.block_hash;
+        let header = Self::headers(&block_hash).ok_or_else(|| {
+            error!(
+                "[apply_push_transaction] Tx's block header ({:?}) must exist before",
+                block_hash
+            );
+            Error::<T>::HeaderNotFound
+        })?;
+
+        ensure!(Self::main_chain(&block_hash), Error::<T>::UnconfirmedTx);
+        let confirmed = Self::confirmed_index().ok_or(Error::<T>::UnconfirmedTx)?;
+        if header.number > confirmed.number {
+            error!(
+                "[apply_push_transaction] Receive an unconfirmed tx (number:{}), confirmed index:{:?}",
+                header.number, confirmed
+            );
+            return Err(Error::<T>::UnconfirmedTx.into());
+        }
+
+        let key = rlp::encode(&proof.tx_index);
+        let raw_tx = proof::verify_proof(&header.transactions_root, &key, &proof.tx_proof)
+            .ok_or(Error::<T>::BadMerkleProof)?;
+        let tx_hash = H256::from(keccak_256(&raw_tx));
+        if xpallet_gateway_records::Module::<T>::is_tx_processed(
+            Chain::Ethereum,
+            tx_hash.as_bytes(),
+        ) {
+            error!(
+                "[apply_push_transaction] Reject processed tx (hash:{:?})",
+                tx_hash
+            );
+            return Err(Error::<T>::ReplayedTx.into());
+        }
+
+        let raw_receipt = proof::verify_proof(&header.receipts_root, &key, &proof.receipt_proof)
+            .ok_or(Error::<T>::BadMerkleProof)?;
+        let receipt = tx::decode_receipt(&raw_receipt).map_err(|_| Error::<T>::DeserializeErr)?;
+        ensure!(receipt.success, Error::<T>::RevertedTx);
+
+        let result = tx::process_receipt::<T>(tx_hash, receipt);
+        Self::deposit_event(Event::<T>::TxProcessed(tx_hash, block_hash, result));
+        match result {
+            EthTxResult::Success => Ok(()),
+            EthTxResult::Failure => Err(Error::<T>::ProcessTxFailed.into()),
+        }
+    }
+}
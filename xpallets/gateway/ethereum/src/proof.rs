@@ -0,0 +1,157 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verification of the Ethereum Merkle-Patricia trie proofs.
+
+use rlp::Rlp;
+use sp_core::H256;
+use sp_io::hashing::keccak_256;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
+
+enum NodeRef<'a> {
+    Hash(H256),
+    Inline(&'a [u8]),
+}
+
+/// Convert the key into nibbles.
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    key.iter().flat_map(|b| vec![b >> 4, b & 0x0f]).collect()
+}
+
+/// Decode the hex-prefix encoded path, returns the nibbles and whether it's a leaf.
+fn decode_hex_prefix(encoded: &[u8]) -> Option<(Vec<u8>, bool)> {
+    let first = *encoded.first()?;
+    let flag = first >> 4;
+    let is_leaf = flag & 0x02 != 0;
+    let is_odd = flag & 0x01 != 0;
+    if flag > 3 {
+        return None;
+    }
+    let mut nibbles = Vec::with_capacity(encoded.len() * 2);
+    if is_odd {
+        nibbles.push(first & 0x0f);
+    }
+    nibbles.extend(to_nibbles(&encoded[1..]));
+    Some((nibbles, is_leaf))
+}
+
+fn child_ref<'a>(item: &Rlp<'a>) -> Option<NodeRef<'a>> {
+    if item.is_list() {
+        // node whose encoding is less than 32 bytes is inlined.
+        Some(NodeRef::Inline(item.as_raw()))
+    } else {
+        let data = item.data().ok()?;
+        if data.len() == 32 {
+            Some(NodeRef::Hash(H256::from_slice(data)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Verify the Merkle-Patricia `proof` of `key` against the trie `root`.
+///
+/// Returns the value of the key if the proof is valid, otherwise returns `None`.
+pub fn verify_proof(root: &H256, key: &[u8], proof: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let nodes = proof
+        .iter()
+        .map(|node| (H256::from(keccak_256(node)), node.as_slice()))
+        .collect::<BTreeMap<_, _>>();
+
+    let nibbles = to_nibbles(key);
+    let mut path = nibbles.as_slice();
+    let mut node_ref = NodeRef::Hash(*root);
+
+    loop {
+        let raw = match node_ref {
+            NodeRef::Hash(hash) => *nodes.get(&hash)?,
+            NodeRef::Inline(raw) => raw,
+        };
+        let node = Rlp::new(raw);
+        match node.item_count().ok()? {
+            // branch node
+            17 => {
+                if path.is_empty() {
+                    let value = node.at(16).ok()?.data().ok()?;
+                    return if value.is_empty() {
+                        None
+                    } else {
+                        Some(value.to_vec())
+                    };
+                }
+                node_ref = child_ref(&node.at(path[0] as usize).ok()?)?;
+                path = &path[1..];
+            }
+            // extension or leaf node
+            2 => {
+                let (partial, is_leaf) = decode_hex_prefix(node.at(0).ok()?.data().ok()?)?;
+                if is_leaf {
+                    return if partial.as_slice() == path {
+                        Some(node.at(1).ok()?.data().ok()?.to_vec())
+                    } else {
+                        None
+                    };
+                }
+                if !path.starts_with(&partial) {
+                    return None;
+                }
+                path = &path[partial.len()..];
+                node_ref = child_ref(&node.at(1).ok()?)?;
+            }
+            _ => return None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn h(hex_str: &str) -> Vec<u8> {
+        hex::decode(hex_str).unwrap()
+    }
+
+    #[test]
+    fn test_single_leaf_proof() {
+        let leaf = h(
+            "e9822080a5e4018504a817c80082520894000000000000000000000000000000000000000080801b0101",
+        );
+        let root = H256::from_slice(&h(
+            "406ea1b0973096979f257deee5033d7810e9a53affc2c59e572176ea33fbdeef",
+        ));
+        let key = rlp::encode(&0u64);
+        let value = verify_proof(&root, &key, &[leaf.clone()]).unwrap();
+        assert_eq!(value, leaf[5..].to_vec());
+
+        // wrong key
+        assert!(verify_proof(&root, &rlp::encode(&1u64), &[leaf.clone()]).is_none());
+        // wrong root
+        assert!(verify_proof(&H256::zero(), &key, &[leaf]).is_none());
+    }
+
+    #[test]
+    fn test_extension_branch_proof() {
+        let root = H256::from_slice(&h(
+            "1403018cbf166ba7f0aef75b1c1890504e8e283048c1a3dc9857cf9f51bdb41d",
+        ));
+        let ext = h("e210a06a73a63bbe6aee5621af402f0560ff998b8ea85f4c0f1802f288654abd9b4427");
+        let branch = h("f85180a0d4e4c2fbc81c7710c44df9b82e12a63d88f2be6ee3b94d5a3435aa5b6759a0f9a075dbb2eddf937b288db3c8771ab61a9fe2ce26d005d37908e7b1026de9cc92e18080808080808080808080808080");
+        let leaf1 = h("ea20a801010101010101010101010101010101010101010101010101010101010101010101010101010101");
+        let leaf2 = h("ea20a802020202020202020202020202020202020202020202020202020202020202020202020202020202");
+
+        let proof = vec![ext.clone(), branch.clone(), leaf2.clone()];
+        assert_eq!(
+            verify_proof(&root, &rlp::encode(&2u64), &proof),
+            Some(vec![2u8; 40])
+        );
+        // the leaf of key 1 is missing in the proof
+        assert!(verify_proof(&root, &rlp::encode(&1u64), &proof).is_none());
+        // key 3 does not exist
+        assert!(verify_proof(&root, &rlp::encode(&3u64), &proof).is_none());
+
+        let proof = vec![ext, branch, leaf1];
+        assert_eq!(
+            verify_proof(&root, &rlp::encode(&1u64), &proof),
+            Some(vec![1u8; 40])
+        );
+    }
+}
@@ -0,0 +1,335 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{assert_err, assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_core::{H160, H256};
+
+use xpallet_assets::{Chain, ChainT};
+use xpallet_gateway_records::{WithdrawalFee, WithdrawalFeeMode};
+
+use sp_io::hashing::keccak_256;
+
+use crate::mock::*;
+use crate::tx;
+use crate::types::{EthLog, EthReceipt, EthReceiptProof, EthTxResult};
+
+// The block 100 contains a deposit tx of 1000000 wei (ETH) to the account `[1; 32]`,
+// the block 101 is an empty block on top of it.
+const HEADER_100: &str = "f901f6a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a0406ea1b0973096979f257deee5033d7810e9a53affc2c59e572176ea33fbdeefa0afd82f729abbd37f39da1387ac2ecc5d3882e20aea7b68e0505f650670c54d59b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000164837a1200825208845f5e100080a00000000000000000000000000000000000000000000000000000000000000000880000000000000000";
+const HEADER_100_HASH: &str = "b93f6a8cf40358d9aacd96153d152bfbc8ef88dcc63d81a87fb354113ce7874d";
+const HEADER_101: &str = "f901f6a0b93f6a8cf40358d9aacd96153d152bfbc8ef88dcc63d81a87fb354113ce7874da01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a00000000000000000000000000000000000000000000000000000000000000000a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000165837a1200825208845f5e100d80a00000000000000000000000000000000000000000000000000000000000000000880000000000000000";
+const HEADER_101_HASH: &str = "f88cc6ddb0096b2156b3e6812a04a62b24a01af8138b6dff34607334db715884";
+const TX_PROOF: &str =
+    "e9822080a5e4018504a817c80082520894000000000000000000000000000000000000000080801b0101";
+const TX_HASH: &str = "e71f12a411e5ea57d5e0b43a17a2928f3610b91fc77cd0963cca0c92646136d7";
+const RECEIPT_PROOF: &str = "f901d0822080b901caf901c701825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f8bef8bc941111111111111111111111111111111111111111f863a01e7b27577112ed83d53de87b38aee59ab80d8a9ba4acd90aad6cfee917534c79a00000000000000000000000000000000000000000000000000000000000000000a00000000000000000000000002222222222222222222222222222222222222222b840010101010101010101010101010101010101010101010101010101010101010100000000000000000000000000000000000000000000000000000000000f4240";
+
+fn h256(hex_str: &str) -> H256 {
+    H256::from_slice(&hex::decode(hex_str).unwrap())
+}
+
+fn lock_contract() -> H160 {
+    H160::repeat_byte(0x11)
+}
+
+fn deposit_proof() -> EthReceiptProof {
+    EthReceiptProof {
+        block_hash: h256(HEADER_100_HASH),
+        tx_index: 0,
+        tx_proof: vec![hex::decode(TX_PROOF).unwrap()],
+        receipt_proof: vec![hex::decode(RECEIPT_PROOF).unwrap()],
+    }
+}
+
+fn setup() {
+    assert_ok!(XGatewayEthereum::set_checkpoint(
+        RawOrigin::Root.into(),
+        hex::decode(HEADER_100).unwrap()
+    ));
+    assert_ok!(XGatewayEthereum::set_lock_contract(
+        RawOrigin::Root.into(),
+        lock_contract()
+    ));
+}
+
+#[test]
+fn test_set_checkpoint() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let index = XGatewayEthereum::best_index();
+        assert_eq!(index.hash, h256(HEADER_100_HASH));
+        assert_eq!(index.number, 100);
+        assert_eq!(XGatewayEthereum::confirmed_index(), Some(index));
+        assert!(XGatewayEthereum::main_chain(&index.hash));
+
+        // only root could set the checkpoint
+        assert!(XGatewayEthereum::set_checkpoint(
+            RawOrigin::Signed(trustee()).into(),
+            hex::decode(HEADER_100).unwrap()
+        )
+        .is_err());
+    })
+}
+
+#[test]
+fn test_push_header() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let header = hex::decode(HEADER_101).unwrap();
+        assert_noop!(
+            XGatewayEthereum::push_header(RawOrigin::Signed(alice()).into(), header.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            XGatewayEthereum::push_header(RawOrigin::Signed(trustee()).into(), vec![0xc0]),
+            XGatewayEthereumErr::DeserializeErr
+        );
+
+        assert_ok!(XGatewayEthereum::push_header(
+            RawOrigin::Signed(trustee()).into(),
+            header.clone()
+        ));
+        assert_eq!(XGatewayEthereum::best_index().hash, h256(HEADER_101_HASH));
+        assert_eq!(XGatewayEthereum::best_index().number, 101);
+        // not enough confirmations yet
+        assert_eq!(XGatewayEthereum::confirmed_index().unwrap().number, 100);
+
+        assert_noop!(
+            XGatewayEthereum::push_header(RawOrigin::Signed(trustee()).into(), header),
+            XGatewayEthereumErr::ExistingHeader
+        );
+    })
+}
+
+#[test]
+fn test_push_header_without_parent() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the parent (block 100) has not been set as checkpoint
+        assert_noop!(
+            XGatewayEthereum::push_header(RawOrigin::Root.into(), hex::decode(HEADER_101).unwrap()),
+            XGatewayEthereumErr::PrevHeaderNotExisted
+        );
+    })
+}
+
+#[test]
+fn test_push_deposit_transaction() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        assert_ok!(XGatewayEthereum::push_transaction(
+            RawOrigin::Signed(trustee()).into(),
+            deposit_proof()
+        ));
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 1_000_000);
+        let record =
+            XGatewayRecords::tx_record(Chain::Ethereum, hex::decode(TX_HASH).unwrap()).unwrap();
+        assert_eq!(record.deposits.len(), 1);
+        assert_eq!(record.deposits[0].asset_id, X_ETH);
+        assert_eq!(record.deposits[0].beneficiary, alice());
+        assert_eq!(record.deposits[0].balance, 1_000_000);
+
+        // replay
+        assert_noop!(
+            XGatewayEthereum::push_transaction(
+                RawOrigin::Signed(trustee()).into(),
+                deposit_proof()
+            ),
+            XGatewayEthereumErr::ReplayedTx
+        );
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 1_000_000);
+    })
+}
+
+#[test]
+fn test_push_transaction_with_bad_proof() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let mut proof = deposit_proof();
+        proof.tx_index = 1;
+        assert_noop!(
+            XGatewayEthereum::push_transaction(RawOrigin::Signed(trustee()).into(), proof),
+            XGatewayEthereumErr::BadMerkleProof
+        );
+
+        let mut proof = deposit_proof();
+        proof.receipt_proof = vec![];
+        assert_noop!(
+            XGatewayEthereum::push_transaction(RawOrigin::Signed(trustee()).into(), proof),
+            XGatewayEthereumErr::BadMerkleProof
+        );
+
+        let mut proof = deposit_proof();
+        proof.block_hash = H256::repeat_byte(1);
+        assert_noop!(
+            XGatewayEthereum::push_transaction(RawOrigin::Signed(trustee()).into(), proof),
+            XGatewayEthereumErr::HeaderNotFound
+        );
+    })
+}
+
+#[test]
+fn test_push_transaction_of_other_contract() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        assert_ok!(XGatewayEthereum::set_lock_contract(
+            RawOrigin::Root.into(),
+            H160::repeat_byte(0x22)
+        ));
+        assert_err!(
+            XGatewayEthereum::push_transaction(
+                RawOrigin::Signed(trustee()).into(),
+                deposit_proof()
+            ),
+            XGatewayEthereumErr::ProcessTxFailed
+        );
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 0);
+        assert!(!XGatewayRecords::is_tx_processed(
+            Chain::Ethereum,
+            &hex::decode(TX_HASH).unwrap()
+        ));
+    })
+}
+
+fn locked_log(recipient: [u8; 32], amount: u64) -> EthLog {
+    let mut data = recipient.to_vec();
+    data.extend_from_slice(H256::from_low_u64_be(amount).as_bytes());
+    EthLog {
+        address: lock_contract(),
+        topics: vec![
+            H256::from(keccak_256(b"Locked(address,address,bytes32,uint256)")),
+            H256::zero(),
+            H256::repeat_byte(0x22),
+        ],
+        data,
+    }
+}
+
+#[test]
+fn test_process_receipt_with_multiple_deposits() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let tx_hash = H256::repeat_byte(0x33);
+        let receipt = EthReceipt {
+            success: true,
+            logs: vec![locked_log([1; 32], 100), locked_log([1; 32], 200)],
+        };
+        assert_eq!(
+            tx::process_receipt::<Test>(tx_hash, receipt),
+            EthTxResult::Success
+        );
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 300);
+        let record =
+            XGatewayRecords::tx_record(Chain::Ethereum, tx_hash.as_bytes().to_vec()).unwrap();
+        assert_eq!(
+            record
+                .deposits
+                .iter()
+                .map(|deposit| deposit.balance)
+                .collect::<Vec<_>>(),
+            vec![100, 200]
+        );
+    })
+}
+
+fn unlocked_log(withdrawal_id: u32, token: H160, recipient: H160, amount: u64) -> EthLog {
+    let mut data = H256::from(recipient).as_bytes().to_vec();
+    data.extend_from_slice(H256::from_low_u64_be(amount).as_bytes());
+    EthLog {
+        address: lock_contract(),
+        topics: vec![
+            H256::from(keccak_256(b"Unlocked(uint32,address,address,uint256)")),
+            H256::from_low_u64_be(withdrawal_id as u64),
+            H256::from(token),
+        ],
+        data,
+    }
+}
+
+#[test]
+fn test_process_receipt_with_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let receipt = EthReceipt {
+            success: true,
+            logs: vec![locked_log([1; 32], 300)],
+        };
+        assert_eq!(
+            tx::process_receipt::<Test>(H256::repeat_byte(0x33), receipt),
+            EthTxResult::Success
+        );
+
+        let recipient = H160::repeat_byte(0x44);
+        let addr = format!("0x{}", hex::encode(recipient.as_bytes())).into_bytes();
+        let fee = WithdrawalFee {
+            amount: 10,
+            mode: WithdrawalFeeMode::Deducted,
+        };
+        let id = XGatewayRecords::id();
+        assert_ok!(XGatewayRecords::withdraw(
+            &alice(),
+            X_ETH,
+            100,
+            addr,
+            Default::default(),
+            fee
+        ));
+        assert_ok!(XGatewayEthereum::process_withdrawals(
+            RawOrigin::Root.into(),
+            vec![id]
+        ));
+
+        let unlocked = |token, recipient, amount| EthReceipt {
+            success: true,
+            logs: vec![unlocked_log(id, token, recipient, amount)],
+        };
+        // the released token, recipient and amount must match the withdrawal record,
+        // the fee is deducted from the amount.
+        for receipt in &[
+            unlocked(H160::repeat_byte(0x55), recipient, 90),
+            unlocked(H160::zero(), H160::repeat_byte(0x55), 90),
+            unlocked(H160::zero(), recipient, 100),
+        ] {
+            assert_eq!(
+                tx::process_receipt::<Test>(H256::repeat_byte(0x34), receipt.clone()),
+                EthTxResult::Failure
+            );
+            assert!(XGatewayRecords::pending_withdrawals(id).is_some());
+        }
+
+        assert_eq!(
+            tx::process_receipt::<Test>(
+                H256::repeat_byte(0x34),
+                unlocked(H160::zero(), recipient, 90)
+            ),
+            EthTxResult::Success
+        );
+        assert!(XGatewayRecords::pending_withdrawals(id).is_none());
+        assert_eq!(XAssets::usable_balance(&alice(), &X_ETH), 200);
+    })
+}
+
+#[test]
+fn test_verify_eth_address() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup();
+        let address = b"0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+        assert!(XGatewayEthereum::verify_eth_address(address).is_ok());
+        assert!(XGatewayEthereum::verify_eth_address(&address[2..]).is_ok());
+        assert!(XGatewayEthereum::verify_eth_address(&address[..40]).is_err());
+        assert!(XGatewayEthereum::verify_eth_address(
+            b"0x7e5f4552091a69125d5dfcb7b8c2659029395bzz"
+        )
+        .is_err());
+
+        assert_ok!(XGatewayEthereum::check_addr(address, b""));
+        assert!(
+            XGatewayEthereum::check_addr(b"0x0000000000000000000000000000000000000000", b"")
+                .is_err()
+        );
+        // withdraw to the lock contract is forbidden
+        assert!(
+            XGatewayEthereum::check_addr(b"0x1111111111111111111111111111111111111111", b"")
+                .is_err()
+        );
+    })
+}
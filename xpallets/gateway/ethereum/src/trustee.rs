@@ -0,0 +1,187 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_core::H160;
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::*};
+
+use xp_logging::{error, info};
+use xpallet_assets::Chain;
+use xpallet_gateway_common::{
+    traits::{ChainProvider, TrusteeForChain},
+    trustees::{TrusteeMultisigProvider, TrusteeSessionManager},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+
+use crate::types::EthAddress;
+use crate::{Error, Module, Trait};
+
+pub type EthTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, EthTrusteeAddrInfo>;
+pub type EthTrusteeIntentionProps = TrusteeIntentionProps<EthTrusteeType>;
+pub type EthTrusteeSessionManager<T> = TrusteeSessionManager<T, EthTrusteeAddrInfo>;
+pub type EthTrusteeMultisig<T> = TrusteeMultisigProvider<T, EthTrusteeType>;
+
+/// The signer set of the lock contract on Ethereum.
+///
+/// Ethereum has no native multisig address, the lock contract is controlled by
+/// `threshold` of `signers`, thus the signer list is the "address" of the trustees.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthTrusteeAddrInfo {
+    pub signers: Vec<EthAddress>,
+    pub threshold: u16,
+}
+
+impl From<EthTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: EthTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for EthTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+/// The uncompressed secp256k1 public key (64 bytes, without the `0x04` prefix).
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthTrusteeType(
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] pub Vec<u8>,
+);
+
+impl EthTrusteeType {
+    /// Returns the Ethereum address of this public key, i.e. the last 20 bytes of
+    /// `keccak256(public)`.
+    pub fn address(&self) -> EthAddress {
+        let hash = sp_io::hashing::keccak_256(&self.0);
+        H160::from_slice(&hash[12..])
+    }
+}
+
+impl From<EthTrusteeType> for Vec<u8> {
+    fn from(value: EthTrusteeType) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for EthTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        match value.len() {
+            64 => Ok(EthTrusteeType(value)),
+            // strip the uncompressed prefix
+            65 if value[0] == 4 => Ok(EthTrusteeType(value[1..].to_vec())),
+            _ => Err(()),
+        }
+    }
+}
+
+impl ChainProvider for EthTrusteeType {
+    fn chain() -> Chain {
+        Chain::Ethereum
+    }
+}
+
+fn check_keys<T: Trait>(keys: &[EthTrusteeType]) -> DispatchResult {
+    let has_duplicate = (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+    if has_duplicate {
+        error!("[generate_trustee_session_info] Keys contains duplicate pubkey");
+        return Err(Error::<T>::DuplicatedKeys.into());
+    }
+    Ok(())
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, EthTrusteeType, EthTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<EthTrusteeType, DispatchError> {
+        let trustee_type = EthTrusteeType::try_from(raw_addr.to_vec())
+            .map_err(|_| Error::<T>::InvalidPublicKey)?;
+        if trustee_type.0.iter().all(|b| *b == 0) {
+            error!("[check_trustee_entity] Invalid public key (Zero64)");
+            return Err(Error::<T>::InvalidPublicKey.into());
+        }
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<EthTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, EthTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<EthTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<EthTrusteeType>, Vec<EthTrusteeType>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_keys::<T>(&hot_keys)?;
+        check_keys::<T>(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let threshold = two_thirds_unsafe(trustees.len() as u32) as u16;
+        let hot_address = EthTrusteeAddrInfo {
+            signers: hot_keys.iter().map(EthTrusteeType::address).collect(),
+            threshold,
+        };
+        let cold_address = EthTrusteeAddrInfo {
+            signers: cold_keys.iter().map(EthTrusteeType::address).collect(),
+            threshold,
+        };
+        info!(
+            "[generate_trustee_session_info] hot_addr:{:?}, cold_addr:{:?}, trustee_list:{:?}",
+            hot_address, cold_address, trustees
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold,
+            hot_address,
+            cold_address,
+        })
+    }
+}
+
+#[test]
+fn test_eth_trustee_type() {
+    let raw = hex::decode(
+        "0479be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798\
+         483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8",
+    )
+    .unwrap();
+    let trustee = EthTrusteeType::try_from(raw.clone()).unwrap();
+    assert_eq!(trustee.0, raw[1..].to_vec());
+    // the well-known address of the private key `1`
+    assert_eq!(
+        trustee.address(),
+        H160::from_slice(&hex::decode("7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap())
+    );
+    assert!(EthTrusteeType::try_from(raw[..33].to_vec()).is_err());
+}
@@ -0,0 +1,224 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::Decode;
+use frame_support::dispatch::DispatchResult;
+use rlp::{DecoderError, Rlp};
+use sp_core::{H160, H256, U256};
+use sp_io::hashing::keccak_256;
+use sp_runtime::SaturatedConversion;
+use sp_std::prelude::*;
+
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{error, info, warn};
+use xpallet_assets::Chain;
+
+use crate::types::{EthAddress, EthLockEvent, EthLog, EthReceipt, EthTxResult};
+use crate::{BalanceOf, Error, Event, Module, Trait};
+
+/// The signature of the deposit event emitted by the lock contract.
+const LOCKED_EVENT: &[u8] = b"Locked(address,address,bytes32,uint256)";
+/// The signature of the withdrawal event emitted by the lock contract.
+const UNLOCKED_EVENT: &[u8] = b"Unlocked(uint32,address,address,uint256)";
+
+fn decode_log(rlp: &Rlp) -> Result<EthLog, DecoderError> {
+    let address = rlp.at(0)?.data()?;
+    if address.len() != 20 {
+        return Err(DecoderError::RlpInvalidLength);
+    }
+    let topics = rlp
+        .at(1)?
+        .iter()
+        .map(|topic| {
+            let topic = topic.data()?;
+            if topic.len() != 32 {
+                return Err(DecoderError::RlpInvalidLength);
+            }
+            Ok(H256::from_slice(topic))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(EthLog {
+        address: H160::from_slice(address),
+        topics,
+        data: rlp.at(2)?.data()?.to_vec(),
+    })
+}
+
+/// Decode the receipt, both the legacy and the EIP-2718 typed receipts are supported.
+pub fn decode_receipt(raw: &[u8]) -> Result<EthReceipt, DecoderError> {
+    let payload = match raw.first() {
+        // EIP-2718: TransactionType || ReceiptPayload
+        Some(ty) if *ty < 0x80 => &raw[1..],
+        _ => raw,
+    };
+    let rlp = Rlp::new(payload);
+    if rlp.item_count()? != 4 {
+        return Err(DecoderError::RlpIncorrectListLen);
+    }
+    // the receipts before Byzantium fork contain the intermediate state root
+    // instead of the status code, which are treated as failed.
+    let success = rlp.at(0)?.data()? == &[1u8][..];
+    let logs = rlp
+        .at(3)?
+        .iter()
+        .map(|log| decode_log(&log))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(EthReceipt { success, logs })
+}
+
+fn topic_to_address(topic: &H256) -> EthAddress {
+    H160::from_slice(&topic[12..])
+}
+
+/// Parse the event of the lock contract, returns `None` for the unknown events.
+pub fn parse_lock_event(log: &EthLog) -> Option<EthLockEvent> {
+    let signature = log.topics.first()?;
+    if signature.as_bytes() == keccak_256(LOCKED_EVENT) {
+        if log.topics.len() != 3 || log.data.len() != 64 {
+            return None;
+        }
+        let mut recipient = [0u8; 32];
+        recipient.copy_from_slice(&log.data[..32]);
+        Some(EthLockEvent::Locked {
+            token: topic_to_address(&log.topics[1]),
+            recipient,
+            amount: U256::from_big_endian(&log.data[32..]),
+        })
+    } else if signature.as_bytes() == keccak_256(UNLOCKED_EVENT) {
+        if log.topics.len() != 3 || log.data.len() != 64 {
+            return None;
+        }
+        let id = U256::from_big_endian(log.topics[1].as_bytes());
+        if id > U256::from(u32::max_value()) {
+            return None;
+        }
+        Some(EthLockEvent::Unlocked {
+            withdrawal_id: id.low_u32(),
+            token: topic_to_address(&log.topics[2]),
+            recipient: H160::from_slice(&log.data[12..32]),
+            amount: U256::from_big_endian(&log.data[32..]),
+        })
+    } else {
+        None
+    }
+}
+
+/// Process all the events of the lock contract in the receipt.
+///
+/// The receipt is processed atomically, any failed event would revert the whole receipt.
+/// The successfully processed receipt is recorded in case it is processed again.
+pub fn process_receipt<T: Trait>(tx_hash: H256, receipt: EthReceipt) -> EthTxResult {
+    let lock_contract = Module::<T>::lock_contract();
+    let events = receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == lock_contract)
+        .filter_map(parse_lock_event)
+        .collect::<Vec<_>>();
+    if events.is_empty() {
+        warn!(
+            "[process_receipt] Tx ({:?}) does not contain any event of the lock contract",
+            tx_hash
+        );
+        // mark irrelevant tx be `Failure` so that it could be replayed in the future
+        return EthTxResult::Failure;
+    }
+
+    let result = with_transaction_result(|| {
+        for event in events {
+            match event {
+                EthLockEvent::Locked {
+                    token,
+                    recipient,
+                    amount,
+                } => deposit::<T>(tx_hash, token, recipient, amount)?,
+                EthLockEvent::Unlocked {
+                    withdrawal_id,
+                    token,
+                    recipient,
+                    amount,
+                } => withdraw::<T>(tx_hash, withdrawal_id, token, recipient, amount)?,
+            }
+        }
+        // the receipt containing only the withdrawals is recorded here.
+        xpallet_gateway_records::Module::<T>::record_processed_tx(
+            Chain::Ethereum,
+            tx_hash.as_bytes().to_vec(),
+        );
+        Ok(())
+    });
+    match result {
+        Ok(_) => EthTxResult::Success,
+        Err(err) => {
+            error!(
+                "[process_receipt] Process tx ({:?}) error:{:?}",
+                tx_hash, err
+            );
+            EthTxResult::Failure
+        }
+    }
+}
+
+fn deposit<T: Trait>(
+    tx_hash: H256,
+    token: EthAddress,
+    recipient: [u8; 32],
+    amount: U256,
+) -> DispatchResult {
+    let asset_id: AssetId = Module::<T>::asset_of_token(&token)?;
+    let who =
+        T::AccountId::decode(&mut &recipient[..]).map_err(|_| Error::<T>::InvalidRecipient)?;
+    if amount > U256::from(u128::max_value()) {
+        return Err(Error::<T>::InvalidAmount.into());
+    }
+    let value: BalanceOf<T> = amount.low_u128().saturated_into();
+
+    xpallet_gateway_records::Module::<T>::deposit_by_tx(
+        Chain::Ethereum,
+        tx_hash.as_bytes().to_vec(),
+        &who,
+        asset_id,
+        value,
+    )?;
+    info!(
+        "[deposit] Deposit tx ({:?}) success, who:{:?}, asset_id:{}, balance:{:?}",
+        tx_hash, who, asset_id, value
+    );
+    Module::<T>::deposit_event(Event::<T>::Deposited(tx_hash, who, asset_id, value));
+    Ok(())
+}
+
+fn withdraw<T: Trait>(
+    tx_hash: H256,
+    withdrawal_id: u32,
+    token: EthAddress,
+    recipient: EthAddress,
+    amount: U256,
+) -> DispatchResult {
+    let record = xpallet_gateway_records::Module::<T>::pending_withdrawals(withdrawal_id)
+        .ok_or(xpallet_gateway_records::Error::<T>::NotExisted)?;
+    // the released token, recipient and amount must be the same as the withdrawal applied
+    let asset_id = Module::<T>::asset_of_token(&token)?;
+    let addr = Module::<T>::verify_eth_address(record.addr())?;
+    let expected = U256::from(record.transfer_amount().saturated_into::<u128>());
+    if asset_id != record.asset_id() || recipient != addr || amount != expected {
+        error!(
+            "[withdraw] Withdrawal ({}) mismatches the unlocked event, token:{:?}, recipient:{:?}, amount:{:?}, record:{:?}",
+            withdrawal_id, token, recipient, amount, record
+        );
+        return Err(Error::<T>::MismatchedWithdrawal.into());
+    }
+
+    xpallet_gateway_records::Module::<T>::finish_withdrawal(
+        withdrawal_id,
+        Some(Chain::Ethereum),
+        Some(tx_hash.as_bytes().to_vec()),
+    )?;
+    info!(
+        "[withdraw] Withdrawal ({}) completion by tx ({:?})",
+        withdrawal_id, tx_hash
+    );
+    Module::<T>::deposit_event(Event::<T>::Withdrawn(tx_hash, withdrawal_id));
+    Ok(())
+}
@@ -0,0 +1,89 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::{H160, H256, U256};
+use sp_runtime::RuntimeDebug;
+use sp_std::prelude::*;
+
+/// The address of an Ethereum account or contract.
+pub type EthAddress = H160;
+
+/// The subset of the Ethereum block header fields that the light client relies on.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthHeader {
+    pub hash: H256,
+    pub parent_hash: H256,
+    pub number: u64,
+    pub timestamp: u64,
+    pub difficulty: U256,
+    pub transactions_root: H256,
+    pub receipts_root: H256,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthHeaderIndex {
+    pub hash: H256,
+    pub number: u64,
+}
+
+/// The proof of a transaction and its receipt in an Ethereum block.
+///
+/// Both proofs are the Merkle-Patricia trie nodes from the root to the leaf,
+/// the key of which is `rlp(tx_index)`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct EthReceiptProof {
+    pub block_hash: H256,
+    pub tx_index: u64,
+    pub tx_proof: Vec<Vec<u8>>,
+    pub receipt_proof: Vec<Vec<u8>>,
+}
+
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct EthLog {
+    pub address: EthAddress,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct EthReceipt {
+    pub success: bool,
+    pub logs: Vec<EthLog>,
+}
+
+/// The decoded event of the lock contract.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub enum EthLockEvent {
+    /// `Locked(address indexed token, address indexed from, bytes32 recipient, uint256 amount)`
+    Locked {
+        token: EthAddress,
+        recipient: [u8; 32],
+        amount: U256,
+    },
+    /// `Unlocked(uint32 indexed withdrawalId, address indexed token, address to, uint256 amount)`
+    Unlocked {
+        withdrawal_id: u32,
+        token: EthAddress,
+        recipient: EthAddress,
+        amount: U256,
+    },
+}
+
+#[derive(PartialEq, Clone, Copy, Eq, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum EthTxResult {
+    Success,
+    Failure,
+}
+
+impl Default for EthTxResult {
+    fn default() -> Self {
+        EthTxResult::Failure
+    }
+}
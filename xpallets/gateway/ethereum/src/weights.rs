@@ -0,0 +1,101 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_gateway_ethereum
+//!
+//! NOTE: not benchmarked yet, the weights are estimated from the similar calls
+//! of xpallet_gateway_bitcoin.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_gateway_ethereum.
+pub trait WeightInfo {
+    fn push_header() -> Weight;
+    fn push_transaction() -> Weight;
+    fn set_checkpoint() -> Weight;
+    fn set_confirmation_number() -> Weight;
+    fn set_lock_contract() -> Weight;
+    fn register_erc20() -> Weight;
+    fn process_withdrawals(u: u32) -> Weight;
+}
+
+/// Weights for xpallet_gateway_ethereum using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn push_header() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn push_transaction() -> Weight {
+        (600_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(14 as Weight))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+    }
+    fn set_checkpoint() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn set_confirmation_number() -> Weight {
+        (4_500_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_lock_contract() -> Weight {
+        (4_500_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn register_erc20() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn process_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((60_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn push_header() -> Weight {
+        (150_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn push_transaction() -> Weight {
+        (600_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(14 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+    }
+    fn set_checkpoint() -> Weight {
+        (30_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn set_confirmation_number() -> Weight {
+        (4_500_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_lock_contract() -> Weight {
+        (4_500_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn register_erc20() -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn process_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((60_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! SS58 address of the Polkadot relay chain.
+
+use sp_std::prelude::*;
+
+use crate::types::DotAccountId;
+
+const PREFIX: &[u8] = b"SS58PRE";
+const CHECKSUM_LEN: usize = 2;
+
+fn ss58hash(data: &[u8]) -> blake2_rfc::blake2b::Blake2bResult {
+    let mut context = blake2_rfc::blake2b::Blake2b::new(64);
+    context.update(PREFIX);
+    context.update(data);
+    context.finalize()
+}
+
+/// Same as `Ss58Codec::from_ss58check_with_version` of `sp_core`, but only the simple
+/// account format (i.e. one byte `version`, 32 bytes account) is accepted.
+pub fn from_ss58check(addr: &[u8], version: u8) -> Option<DotAccountId> {
+    let data = bs58::decode(addr).into_vec().ok()?;
+    if data.len() != 1 + 32 + CHECKSUM_LEN || data[0] != version {
+        return None;
+    }
+    let body_len = data.len() - CHECKSUM_LEN;
+    if ss58hash(&data[..body_len]).as_bytes()[..CHECKSUM_LEN] != data[body_len..] {
+        return None;
+    }
+    let mut account = [0u8; 32];
+    account.copy_from_slice(&data[1..body_len]);
+    Some(DotAccountId::new(account))
+}
+
+/// Same as `Ss58Codec::to_ss58check_with_version` of `sp_core`.
+pub fn to_ss58check(account: &DotAccountId, version: u8) -> Vec<u8> {
+    let mut data = vec![version];
+    data.extend_from_slice(account.as_ref());
+    let checksum = ss58hash(&data);
+    data.extend_from_slice(&checksum.as_bytes()[..CHECKSUM_LEN]);
+    bs58::encode(data).into_string().into_bytes()
+}
+
+#[test]
+fn test_ss58check() {
+    let account = DotAccountId::new(hex_literal::hex!(
+        "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+    ));
+    // Alice on Polkadot
+    let polkadot = b"15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    // Alice on Substrate
+    let substrate = b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+
+    assert_eq!(from_ss58check(polkadot, 0), Some(account.clone()));
+    assert_eq!(from_ss58check(substrate, 42), Some(account.clone()));
+    assert_eq!(from_ss58check(substrate, 0), None);
+    assert_eq!(to_ss58check(&account, 0), polkadot.to_vec());
+
+    // invalid checksum
+    let mut invalid = polkadot.to_vec();
+    invalid[47] = b'6';
+    assert_eq!(from_ss58check(&invalid, 0), None);
+}
@@ -0,0 +1,139 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verification of the events of the Polkadot relay chain, which prove the dispatch
+//! result of the transfer extrinsics.
+
+use codec::{Decode, Input};
+use sp_core::H256;
+use sp_io::hashing::twox_128;
+use sp_runtime::traits::BlakeTwo256;
+use sp_std::prelude::*;
+use sp_trie::{read_trie_value, Layout, StorageProof};
+
+use crate::types::{DotAccountId, DotTransfer};
+
+/// The `[pallet_index, event_index]` of `System::ExtrinsicSuccess`.
+const EXTRINSIC_SUCCESS_EVENT: [u8; 2] = [0, 0];
+/// The index of `Balances::Transfer` in the events of the balances pallet.
+const BALANCES_TRANSFER_EVENT: u8 = 2;
+
+/// Returns the storage key of `System::Events`.
+fn events_key() -> Vec<u8> {
+    let mut key = twox_128(b"System").to_vec();
+    key.extend_from_slice(&twox_128(b"Events"));
+    key
+}
+
+/// Read the encoded `System::Events` from the trie proof against the `state_root`.
+pub fn read_events(state_root: &H256, proof: Vec<Vec<u8>>) -> Option<Vec<u8>> {
+    let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+    read_trie_value::<Layout<BlakeTwo256>, _>(&db, state_root, &events_key())
+        .ok()
+        .flatten()
+}
+
+/// Decode the beginning of the event record at `offset`, returns the rest of the record
+/// if it is the `event` emitted in the phase of applying the extrinsic at `index`.
+fn decode_record(events: &[u8], offset: u32, index: u32, event: [u8; 2]) -> Option<&[u8]> {
+    let mut input = events.get(offset as usize..)?;
+    // Phase::ApplyExtrinsic
+    if input.read_byte().ok()? != 0 || u32::decode(&mut input).ok()? != index {
+        return None;
+    }
+    let mut event_index = [0u8; 2];
+    input.read(&mut event_index).ok()?;
+    if event_index != event {
+        return None;
+    }
+    Some(input)
+}
+
+/// Returns whether the record at `offset` is the `System::ExtrinsicSuccess` of the extrinsic
+/// at `index`.
+pub fn is_success_event(events: &[u8], offset: u32, index: u32) -> bool {
+    let check = || -> Option<()> {
+        let input = &mut decode_record(events, offset, index, EXTRINSIC_SUCCESS_EVENT)?;
+        // DispatchInfo { weight: u64, class: DispatchClass, pays_fee: Pays }
+        u64::decode(input).ok()?;
+        if input.read_byte().ok()? > 2 || input.read_byte().ok()? > 1 {
+            return None;
+        }
+        Vec::<H256>::decode(input).ok()?;
+        Some(())
+    };
+    check().is_some()
+}
+
+/// Returns whether the record at `offset` is the `Balances::Transfer` of the extrinsic
+/// at `index`, which transfers the same as `transfer`.
+pub fn is_transfer_event(events: &[u8], offset: u32, index: u32, transfer: &DotTransfer) -> bool {
+    let check = || -> Option<()> {
+        let event = [transfer.pallet_index, BALANCES_TRANSFER_EVENT];
+        let input = &mut decode_record(events, offset, index, event)?;
+        let (from, to, value) = <(DotAccountId, DotAccountId, u128)>::decode(input).ok()?;
+        if from != transfer.from || to != transfer.to || value != transfer.value {
+            return None;
+        }
+        Vec::<H256>::decode(input).ok()?;
+        Some(())
+    };
+    check().is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codec::Encode;
+
+    fn record(index: u32, event: [u8; 2], data: &[u8]) -> Vec<u8> {
+        let mut record = vec![0];
+        record.extend(index.encode());
+        record.extend_from_slice(&event);
+        record.extend_from_slice(data);
+        // topics
+        record.push(0);
+        record
+    }
+
+    #[test]
+    fn test_decode_events() {
+        let transfer = DotTransfer {
+            from: DotAccountId::new([1; 32]),
+            to: DotAccountId::new([2; 32]),
+            value: 10_000_000_000,
+            pallet_index: 5,
+        };
+        let transfer_data = (transfer.from.clone(), transfer.to.clone(), transfer.value).encode();
+        // DispatchInfo
+        let success_data = (1_000u64, 0u8, 0u8).encode();
+
+        // compact length of 2 records
+        let mut events = vec![2 << 2];
+        let transfer_offset = events.len() as u32;
+        events.extend(record(1, [5, 2], &transfer_data));
+        let success_offset = events.len() as u32;
+        events.extend(record(1, [0, 0], &success_data));
+
+        assert!(is_transfer_event(&events, transfer_offset, 1, &transfer));
+        assert!(is_success_event(&events, success_offset, 1));
+        // other extrinsic
+        assert!(!is_transfer_event(&events, transfer_offset, 2, &transfer));
+        assert!(!is_success_event(&events, success_offset, 2));
+        // not the beginning of the record
+        assert!(!is_transfer_event(
+            &events,
+            transfer_offset + 1,
+            1,
+            &transfer
+        ));
+        assert!(!is_success_event(&events, transfer_offset, 1));
+        // different transfer
+        let other = DotTransfer {
+            value: 1,
+            ..transfer.clone()
+        };
+        assert!(!is_transfer_event(&events, transfer_offset, 1, &other));
+        // out of range
+        assert!(!is_success_event(&events, events.len() as u32 + 1, 1));
+    }
+}
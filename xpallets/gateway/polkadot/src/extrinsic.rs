@@ -0,0 +1,150 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verification and decoding of the transfer extrinsics of the Polkadot relay chain.
+
+use codec::{Compact, Decode, Encode, Input};
+use sp_core::H256;
+use sp_runtime::traits::BlakeTwo256;
+use sp_std::prelude::*;
+use sp_trie::{read_trie_value, Layout, StorageProof};
+
+use crate::types::{DotAccountId, DotTransfer};
+
+/// The version byte of the signed extrinsic of the transaction version 4.
+const SIGNED_EXTRINSIC_V4: u8 = 0b1000_0000 + 4;
+
+/// Verify the trie proof of the extrinsic at `index` against the `extrinsics_root`.
+///
+/// The extrinsics root is the ordered trie root of the encoded extrinsics, i.e.
+/// the key is `Compact(index)`.
+pub fn verify_extrinsic_proof(
+    extrinsics_root: &H256,
+    index: u32,
+    extrinsic: &[u8],
+    proof: Vec<Vec<u8>>,
+) -> bool {
+    let db = StorageProof::new(proof).into_memory_db::<BlakeTwo256>();
+    let key = Compact(index).encode();
+    match read_trie_value::<Layout<BlakeTwo256>, _>(&db, extrinsics_root, &key) {
+        Ok(Some(value)) => value.as_slice() == extrinsic,
+        _ => false,
+    }
+}
+
+fn skip(input: &mut &[u8], len: usize) -> Option<()> {
+    if input.len() < len {
+        return None;
+    }
+    *input = &input[len..];
+    Some(())
+}
+
+/// Decode the `MultiAddress::Id` or the legacy `Address::Id` of the indices pallet.
+fn decode_account(input: &mut &[u8]) -> Option<DotAccountId> {
+    match input.read_byte().ok()? {
+        0x00 | 0xff => DotAccountId::decode(input).ok(),
+        // the account index is not supported
+        _ => None,
+    }
+}
+
+/// Decode the signed transfer extrinsic, returns `None` if the extrinsic is not
+/// one of the `transfer_calls`, e.g. `balances.transfer` or `balances.transfer_keep_alive`.
+///
+/// NOTE: the signed extensions of the relay chain must be `(era, nonce, tip)`.
+pub fn decode_transfer(extrinsic: &[u8], transfer_calls: &[[u8; 2]]) -> Option<DotTransfer> {
+    let input = &mut &extrinsic[..];
+    let body = Vec::<u8>::decode(input).ok()?;
+    if !input.is_empty() {
+        return None;
+    }
+
+    let input = &mut &body[..];
+    if input.read_byte().ok()? != SIGNED_EXTRINSIC_V4 {
+        return None;
+    }
+    let from = decode_account(input)?;
+    // MultiSignature
+    match input.read_byte().ok()? {
+        // Ed25519 | Sr25519
+        0 | 1 => skip(input, 64)?,
+        // Ecdsa
+        2 => skip(input, 65)?,
+        _ => return None,
+    }
+    // Era
+    if input.read_byte().ok()? != 0 {
+        skip(input, 1)?;
+    }
+    // nonce
+    Compact::<u32>::decode(input).ok()?;
+    // tip
+    Compact::<u128>::decode(input).ok()?;
+
+    let mut call_index = [0u8; 2];
+    input.read(&mut call_index).ok()?;
+    if !transfer_calls.contains(&call_index) {
+        return None;
+    }
+    let to = decode_account(input)?;
+    let value = Compact::<u128>::decode(input).ok()?.0;
+    if !input.is_empty() {
+        return None;
+    }
+    Some(DotTransfer {
+        from,
+        to,
+        value,
+        pallet_index: call_index[0],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transfer(address_prefix: u8, call: [u8; 2], era: &[u8]) -> Vec<u8> {
+        let mut body = vec![SIGNED_EXTRINSIC_V4, address_prefix];
+        body.extend_from_slice(&[1; 32]);
+        // sr25519 signature
+        body.push(1);
+        body.extend_from_slice(&[0; 64]);
+        body.extend_from_slice(era);
+        body.extend(Compact(5u32).encode());
+        body.extend(Compact(0u128).encode());
+        body.extend_from_slice(&call);
+        body.push(address_prefix);
+        body.extend_from_slice(&[2; 32]);
+        body.extend(Compact(10_000_000_000u128).encode());
+        body.encode()
+    }
+
+    #[test]
+    fn test_decode_transfer() {
+        let calls = [[5, 0], [5, 3]];
+        let expected = DotTransfer {
+            from: DotAccountId::new([1; 32]),
+            to: DotAccountId::new([2; 32]),
+            value: 10_000_000_000,
+            pallet_index: 5,
+        };
+        // immortal
+        assert_eq!(
+            decode_transfer(&transfer(0x00, [5, 0], &[0]), &calls),
+            Some(expected.clone())
+        );
+        // mortal with the legacy address
+        assert_eq!(
+            decode_transfer(&transfer(0xff, [5, 3], &[0xe5, 0x03]), &calls),
+            Some(expected)
+        );
+        // not a transfer
+        assert!(decode_transfer(&transfer(0x00, [5, 1], &[0]), &calls).is_none());
+        // account index
+        assert!(decode_transfer(&transfer(0x01, [5, 0], &[0]), &calls).is_none());
+
+        let mut raw = transfer(0x00, [5, 0], &[0]);
+        raw.push(0);
+        assert!(decode_transfer(&raw, &calls).is_none());
+    }
+}
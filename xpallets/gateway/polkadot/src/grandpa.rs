@@ -0,0 +1,142 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Verification of the GRANDPA justifications of the Polkadot relay chain.
+
+use codec::Encode;
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityList, ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID};
+use sp_runtime::{generic::OpaqueDigestItemId, traits::Header as HeaderT, RuntimeAppPublic};
+use sp_std::{
+    collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+    prelude::*,
+};
+
+use xp_logging::error;
+
+use crate::types::{DotBlockNumber, DotHeader, GrandpaJustification};
+use crate::{Error, Trait};
+
+/// The `Message::Precommit` variant index of `finality_grandpa::Message`.
+const PRECOMMIT_MESSAGE_INDEX: u8 = 1;
+
+/// Returns whether `block` is `base` or a descendant of `base` according to the `ancestry`.
+fn is_descendant_of(
+    ancestry: &BTreeMap<H256, &DotHeader>,
+    base: (H256, DotBlockNumber),
+    mut block: H256,
+) -> bool {
+    loop {
+        if block == base.0 {
+            return true;
+        }
+        match ancestry.get(&block) {
+            Some(header) if header.number > base.1 => block = header.parent_hash,
+            _ => return false,
+        }
+    }
+}
+
+/// Verify the GRANDPA justification of the header `(hash, number)` by the authority set.
+///
+/// The justification is valid when more than 2/3 weight of the authorities precommit
+/// the header or its descendants.
+pub fn verify_justification<T: Trait>(
+    target: (H256, DotBlockNumber),
+    set_id: SetId,
+    authorities: &AuthorityList,
+    justification: &GrandpaJustification,
+) -> Result<(), Error<T>> {
+    let commit = &justification.commit;
+    if (commit.target_hash, commit.target_number) != target {
+        error!(
+            "[verify_justification] The target ({:?}, {}) of the commit is not the header ({:?}, {})",
+            commit.target_hash, commit.target_number, target.0, target.1
+        );
+        return Err(Error::<T>::InvalidJustification);
+    }
+
+    let weights = authorities.iter().cloned().collect::<BTreeMap<_, _>>();
+    let ancestry = justification
+        .votes_ancestries
+        .iter()
+        .map(|header| (header.hash(), header))
+        .collect::<BTreeMap<_, _>>();
+
+    let mut voted = BTreeSet::new();
+    let mut signed_weight = 0u64;
+    for signed in commit.precommits.iter() {
+        let weight = weights
+            .get(&signed.id)
+            .ok_or(Error::<T>::UnknownAuthority)?;
+        if !voted.insert(signed.id.clone()) {
+            error!(
+                "[verify_justification] Duplicate precommit of the authority:{:?}",
+                signed.id
+            );
+            return Err(Error::<T>::DuplicateAuthorityVote);
+        }
+        if !is_descendant_of(&ancestry, target, signed.precommit.target_hash) {
+            error!(
+                "[verify_justification] The precommit ({:?}) is not the descendant of the commit target",
+                signed.precommit
+            );
+            return Err(Error::<T>::InvalidJustification);
+        }
+        // Same as `sp_finality_grandpa::localized_payload(round, set_id, &Message::Precommit(_))`
+        let payload = (
+            PRECOMMIT_MESSAGE_INDEX,
+            &signed.precommit,
+            justification.round,
+            set_id,
+        )
+            .encode();
+        if !signed.id.verify(&payload, &signed.signature) {
+            error!(
+                "[verify_justification] Invalid signature of the authority:{:?}",
+                signed.id
+            );
+            return Err(Error::<T>::InvalidAuthoritySignature);
+        }
+        signed_weight = signed_weight.saturating_add(*weight);
+    }
+
+    let total_weight = authorities
+        .iter()
+        .fold(0u64, |acc, (_, weight)| acc.saturating_add(*weight));
+    let threshold = total_weight - total_weight.saturating_sub(1) / 3;
+    if signed_weight < threshold {
+        error!(
+            "[verify_justification] Not enough weight, signed:{}, threshold:{}",
+            signed_weight, threshold
+        );
+        return Err(Error::<T>::NotEnoughWeight);
+    }
+    Ok(())
+}
+
+fn find_consensus_log<F, R>(header: &DotHeader, filter: F) -> Option<R>
+where
+    F: Fn(ConsensusLog<DotBlockNumber>) -> Option<R>,
+{
+    let id = OpaqueDigestItemId::Consensus(&GRANDPA_ENGINE_ID);
+    header
+        .digest()
+        .convert_first(|log| log.try_to(id).and_then(&filter))
+}
+
+/// Returns the authority set change scheduled by the header.
+pub fn find_scheduled_change(header: &DotHeader) -> Option<ScheduledChange<DotBlockNumber>> {
+    find_consensus_log(header, |log| match log {
+        ConsensusLog::ScheduledChange(change) => Some(change),
+        _ => None,
+    })
+}
+
+/// Returns whether the header contains a forced authority set change.
+pub fn has_forced_change(header: &DotHeader) -> bool {
+    find_consensus_log(header, |log| match log {
+        ConsensusLog::ForcedChange(_, _) => Some(()),
+        _ => None,
+    })
+    .is_some()
+}
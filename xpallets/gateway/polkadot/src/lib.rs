@@ -0,0 +1,484 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! this module is for dot-bridge
+//!
+//! The finalized headers of the Polkadot relay chain are imported with the GRANDPA
+//! justifications, which are verified by the authority set tracked from the checkpoint.
+//! The deposits (i.e. the transfers to the trustee multisig account) are proved by the
+//! trie proofs of the extrinsics against the `extrinsics_root` of the finalized headers,
+//! and their dispatch results are proved by the events against the `state_root`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod address;
+mod events;
+mod extrinsic;
+mod grandpa;
+pub mod trustee;
+mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use codec::Decode;
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityList, SetId};
+use sp_io::hashing::blake2_256;
+use sp_runtime::{traits::Header as HeaderT, SaturatedConversion};
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
+    ensure,
+    traits::{EnsureOrigin, Get},
+    weights::Pays,
+};
+use frame_system::{ensure_root, ensure_signed};
+use orml_utilities::with_transaction_result;
+
+use chainx_primitives::AssetId;
+use xp_logging::{debug, error, info};
+use xpallet_assets::{BalanceOf, Chain, ChainT, WithdrawalLimit};
+use xpallet_gateway_common::traits::{ChainGateway, TrusteeSession};
+use xpallet_gateway_records::WithdrawalRecordId;
+use xpallet_support::try_addr;
+
+pub use self::trustee::{DotTrusteeAddrInfo, DotTrusteeType};
+pub use self::types::{
+    DotAccountId, DotBlockNumber, DotHeader, DotHeaderIndex, DotHeaderInfo, DotScheduledChange,
+    DotTransferProof, GrandpaJustification,
+};
+pub use self::weights::WeightInfo;
+
+pub trait Trait: xpallet_assets::Trait + xpallet_gateway_records::Trait {
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+    /// The ss58 address version of the relay chain, e.g. `0` for Polkadot.
+    type NetworkPrefix: Get<u8>;
+    type TrusteeSessionProvider: TrusteeSession<Self::AccountId, DotTrusteeAddrInfo>;
+    type TrusteeOrigin: EnsureOrigin<Self::Origin, Success = Self::AccountId>;
+    type WeightInfo: WeightInfo;
+}
+
+decl_error! {
+    /// Error for the XBridge Polkadot module
+    pub enum Error for Module<T: Trait> {
+        /// The checkpoint has not been set
+        NotInitialized,
+        /// The header is not newer than the best finalized header
+        OldHeader,
+        /// The header skips the header which enacts the scheduled authority set change
+        ScheduledChangeSkipped,
+        /// Another authority set change is scheduled before the pending one is enacted
+        UnsupportedScheduledChange,
+        /// The forced authority set change is not supported, root needs to reset the checkpoint
+        UnsupportedForcedChange,
+        /// The justification does not finalize the header
+        InvalidJustification,
+        /// The precommit is not signed by the current authority set
+        UnknownAuthority,
+        /// The authority precommits more than once
+        DuplicateAuthorityVote,
+        /// Invalid signature of the precommit
+        InvalidAuthoritySignature,
+        /// The signed weight of the precommits does not reach the threshold
+        NotEnoughWeight,
+        /// Not Found
+        HeaderNotFound,
+        /// Invalid trie proof of the extrinsic
+        BadMerkleProof,
+        /// The extrinsic is not a transfer
+        NotTransfer,
+        /// The transfer is not to the trustees
+        InvalidRecipient,
+        /// The sender of the transfer is not a valid account
+        InvalidSender,
+        /// reject replay proccessed tx
+        ReplayedTx,
+        /// invalid ss58 address
+        InvalidAddress,
+        /// invalid public key
+        InvalidPublicKey,
+        /// duplicated pubkey for trustees
+        DuplicatedKeys,
+        /// invalid trustee count
+        InvalidTrusteeCount,
+        /// Invalid trie proof of the events
+        BadEventProof,
+        /// The events do not prove the transfer succeeded
+        FailedTransfer,
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        <T as frame_system::Trait>::AccountId,
+        Balance = BalanceOf<T>
+    {
+        /// A Polkadot checkpoint was set. [header_hash, number, set_id]
+        CheckpointSet(H256, DotBlockNumber, SetId),
+        /// A finalized Polkadot header was imported. [header_hash, number]
+        HeaderFinalized(H256, DotBlockNumber),
+        /// The GRANDPA authority set was changed. [set_id]
+        AuthoritySetChanged(SetId),
+        /// An account deposited some DOT. [tx_hash, who, amount]
+        Deposited(H256, AccountId, Balance),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XGatewayPolkadot {
+        /// best finalized header info
+        pub BestFinalized get(fn best_finalized): DotHeaderIndex;
+        /// all finalized headers
+        pub Headers get(fn headers): map hasher(identity) H256 => Option<DotHeaderInfo>;
+
+        /// the id of the current GRANDPA authority set
+        pub CurrentSetId get(fn current_set_id): SetId;
+        /// the current GRANDPA authority set
+        pub Authorities get(fn authorities): AuthorityList;
+        /// the authority set change which is signaled but not enacted yet
+        pub NextScheduledChange get(fn next_scheduled_change): Option<DotScheduledChange>;
+
+        /// the `[pallet_index, call_index]` of the transfer calls on the relay chain,
+        /// default is `balances.transfer` and `balances.transfer_keep_alive` of Polkadot.
+        pub TransferCalls get(fn transfer_calls): Vec<[u8; 2]> = vec![[5, 0], [5, 3]];
+        /// the withdrawal fee of DOT
+        pub WithdrawalFee get(fn withdrawal_fee): BalanceOf<T>;
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+        fn deposit_event() = default;
+
+        /// Import a finalized header with the GRANDPA justification.
+        ///
+        /// Anyone could relay the headers since the justification is verified by the
+        /// authority set, the relayer does not pay a fee if the header is imported.
+        #[weight = <T as Trait>::WeightInfo::submit_finality_proof(
+            justification.commit.precommits.len() as u32
+        )]
+        pub fn submit_finality_proof(
+            origin,
+            header: DotHeader,
+            justification: GrandpaJustification
+        ) -> DispatchResultWithPostInfo {
+            ensure_signed(origin)?;
+            debug!("[submit_finality_proof] header:{:?}", header);
+
+            Self::apply_finality_proof(header, justification)?;
+
+            Ok(Pays::No.into())
+        }
+
+        /// Push the proof of a transfer to the trustees, which would be deposited to the
+        /// account of the sender on ChainX.
+        ///
+        /// The proof contains the `Balances::Transfer` and `System::ExtrinsicSuccess` events
+        /// of the extrinsic, since the failed extrinsics are included in the block as well.
+        #[weight = <T as Trait>::WeightInfo::push_transaction()]
+        pub fn push_transaction(origin, proof: DotTransferProof) -> DispatchResultWithPostInfo {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            debug!(
+                "[push_transaction] block_hash:{:?}, index:{}",
+                proof.block_hash, proof.index
+            );
+
+            Self::apply_push_transaction(proof)?;
+
+            // Relayer does not pay a fee.
+            Ok(Pays::No.into())
+        }
+
+        /// Dangerous! Set a trusted finalized header and the authority set of it as the checkpoint.
+        #[weight = <T as Trait>::WeightInfo::set_checkpoint()]
+        pub fn set_checkpoint(
+            origin,
+            header: DotHeader,
+            authorities: AuthorityList,
+            set_id: SetId
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            Self::apply_checkpoint(header, authorities, set_id);
+            Ok(())
+        }
+
+        /// Set the `[pallet_index, call_index]` of the transfer calls on the relay chain.
+        #[weight = <T as Trait>::WeightInfo::set_transfer_calls()]
+        pub fn set_transfer_calls(origin, calls: Vec<[u8; 2]>) -> DispatchResult {
+            ensure_root(origin)?;
+            TransferCalls::put(calls);
+            Ok(())
+        }
+
+        /// Set the withdrawal fee of DOT.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_fee()]
+        pub fn set_withdrawal_fee(origin, #[compact] fee: BalanceOf<T>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            WithdrawalFee::<T>::put(fee);
+            Ok(())
+        }
+
+        /// Trustees mark the withdrawals as `Processing` before signing the transfers on Polkadot.
+        #[weight = <T as Trait>::WeightInfo::process_withdrawals(withdrawal_id_list.len() as u32)]
+        pub fn process_withdrawals(origin, withdrawal_id_list: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            info!("[process_withdrawals] withdrawal list:{:?}", withdrawal_id_list);
+            xpallet_gateway_records::Module::<T>::process_withdrawals(
+                &withdrawal_id_list,
+                Chain::Polkadot,
+            )
+        }
+
+        /// Trustees finish the withdrawals after the transfers on Polkadot are finalized.
+        #[weight = <T as Trait>::WeightInfo::finish_withdrawals(withdrawal_id_list.len() as u32)]
+        pub fn finish_withdrawals(origin, withdrawal_id_list: Vec<WithdrawalRecordId>) -> DispatchResult {
+            T::TrusteeOrigin::try_origin(origin).map(|_| ()).or_else(ensure_root)?;
+            info!("[finish_withdrawals] withdrawal list:{:?}", withdrawal_id_list);
+            xpallet_gateway_records::Module::<T>::finish_withdrawals(
+                &withdrawal_id_list,
+                Some(Chain::Polkadot),
+            )
+        }
+    }
+}
+
+impl<T: Trait> ChainT<BalanceOf<T>> for Module<T> {
+    const ASSET_ID: AssetId = xp_protocol::X_DOT;
+
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+
+    fn check_addr(addr: &[u8], _: &[u8]) -> DispatchResult {
+        Self::verify_dot_address(addr).map_err(|err| {
+            error!(
+                "[verify_dot_address] Verify failed, error:{:?}, source addr:{:?}",
+                err,
+                try_addr(addr)
+            );
+            err
+        })?;
+        Ok(())
+    }
+
+    fn withdrawal_limit(
+        asset_id: &AssetId,
+    ) -> Result<WithdrawalLimit<BalanceOf<T>>, DispatchError> {
+        if *asset_id != Self::ASSET_ID {
+            return Err(xpallet_assets::Error::<T>::ActionNotAllowed.into());
+        }
+        let fee = Self::withdrawal_fee();
+        let limit = WithdrawalLimit::<BalanceOf<T>> {
+            minimal_withdrawal: fee * 3u32.saturated_into() / 2u32.saturated_into(),
+            fee,
+        };
+        Ok(limit)
+    }
+}
+
+impl<T: Trait> ChainGateway<T::AccountId, BalanceOf<T>> for Module<T> {
+    type TrusteeEntity = DotTrusteeType;
+    type TrusteeAddress = DotTrusteeAddrInfo;
+    type Trustee = Self;
+}
+
+impl<T: Trait> Module<T> {
+    /// Verify the ss58 address of the relay chain.
+    pub fn verify_dot_address(data: &[u8]) -> Result<DotAccountId, DispatchError> {
+        address::from_ss58check(data, T::NetworkPrefix::get())
+            .ok_or_else(|| Error::<T>::InvalidAddress.into())
+    }
+
+    /// Returns whether the account is the hot multisig account of the current or the last
+    /// trustee session, the transfers to the last trustees are accepted during the transition.
+    fn is_trustee_account(account: &DotAccountId) -> bool {
+        let current = T::TrusteeSessionProvider::current_trustee_session();
+        let last = T::TrusteeSessionProvider::last_trustee_session();
+        current
+            .into_iter()
+            .chain(last)
+            .any(|info| &info.hot_address.account == account)
+    }
+
+    fn apply_checkpoint(header: DotHeader, authorities: AuthorityList, set_id: SetId) {
+        let hash = header.hash();
+        let index = DotHeaderIndex {
+            hash,
+            number: header.number,
+        };
+        Headers::insert(&hash, Self::header_info(&header));
+        BestFinalized::put(index);
+        Authorities::put(authorities);
+        CurrentSetId::put(set_id);
+        NextScheduledChange::kill();
+        info!(
+            "[apply_checkpoint] Set checkpoint:{:?}, set_id:{}",
+            index, set_id
+        );
+        Self::deposit_event(Event::<T>::CheckpointSet(hash, header.number, set_id));
+    }
+
+    fn header_info(header: &DotHeader) -> DotHeaderInfo {
+        DotHeaderInfo {
+            number: header.number,
+            state_root: header.state_root,
+            extrinsics_root: header.extrinsics_root,
+        }
+    }
+
+    fn apply_finality_proof(
+        header: DotHeader,
+        justification: GrandpaJustification,
+    ) -> DispatchResult {
+        let authorities = Self::authorities();
+        ensure!(!authorities.is_empty(), Error::<T>::NotInitialized);
+
+        let hash = header.hash();
+        let best = Self::best_finalized();
+        if header.number <= best.number {
+            error!(
+                "[apply_finality_proof] Header number ({}) must be larger than the best finalized ({})",
+                header.number, best.number
+            );
+            return Err(Error::<T>::OldHeader.into());
+        }
+        let pending_change = Self::next_scheduled_change();
+        if let Some(change) = pending_change.as_ref() {
+            if header.number > change.enacted_at {
+                error!(
+                    "[apply_finality_proof] Header ({}) must not skip the header ({}) which enacts the authority set change",
+                    header.number, change.enacted_at
+                );
+                return Err(Error::<T>::ScheduledChangeSkipped.into());
+            }
+        }
+        ensure!(
+            !grandpa::has_forced_change(&header),
+            Error::<T>::UnsupportedForcedChange
+        );
+
+        let set_id = Self::current_set_id();
+        grandpa::verify_justification::<T>(
+            (hash, header.number),
+            set_id,
+            &authorities,
+            &justification,
+        )?;
+
+        with_transaction_result(|| {
+            Headers::insert(&hash, Self::header_info(&header));
+            BestFinalized::put(DotHeaderIndex {
+                hash,
+                number: header.number,
+            });
+            Self::deposit_event(Event::<T>::HeaderFinalized(hash, header.number));
+
+            if let Some(change) = pending_change {
+                if header.number == change.enacted_at {
+                    NextScheduledChange::kill();
+                    Self::enact_authority_set_change(change.next_authorities);
+                }
+            }
+
+            if let Some(change) = grandpa::find_scheduled_change(&header) {
+                ensure!(
+                    Self::next_scheduled_change().is_none(),
+                    Error::<T>::UnsupportedScheduledChange
+                );
+                if change.delay == 0 {
+                    Self::enact_authority_set_change(change.next_authorities);
+                } else {
+                    NextScheduledChange::put(DotScheduledChange {
+                        next_authorities: change.next_authorities,
+                        enacted_at: header.number.saturating_add(change.delay),
+                    });
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn enact_authority_set_change(next_authorities: AuthorityList) {
+        let set_id = Self::current_set_id() + 1;
+        Authorities::put(next_authorities);
+        CurrentSetId::put(set_id);
+        info!("[enact_authority_set_change] New authority set:{}", set_id);
+        Self::deposit_event(Event::<T>::AuthoritySetChanged(set_id));
+    }
+
+    fn apply_push_transaction(proof: DotTransferProof) -> DispatchResult {
+        let header = Self::headers(&proof.block_hash).ok_or_else(|| {
+            error!(
+                "[apply_push_transaction] Tx's block header ({:?}) must be finalized before",
+                proof.block_hash
+            );
+            Error::<T>::HeaderNotFound
+        })?;
+        let tx_hash = H256::from(blake2_256(&proof.extrinsic));
+        if xpallet_gateway_records::Module::<T>::is_tx_processed(
+            Chain::Polkadot,
+            tx_hash.as_bytes(),
+        ) {
+            error!(
+                "[apply_push_transaction] Reject processed tx (hash:{:?})",
+                tx_hash
+            );
+            return Err(Error::<T>::ReplayedTx.into());
+        }
+        ensure!(
+            extrinsic::verify_extrinsic_proof(
+                &header.extrinsics_root,
+                proof.index,
+                &proof.extrinsic,
+                proof.proof,
+            ),
+            Error::<T>::BadMerkleProof
+        );
+
+        let transfer = extrinsic::decode_transfer(&proof.extrinsic, &Self::transfer_calls())
+            .ok_or(Error::<T>::NotTransfer)?;
+        if !Self::is_trustee_account(&transfer.to) {
+            error!(
+                "[apply_push_transaction] Tx ({:?}) is not a transfer to the trustees, dest:{:?}",
+                tx_hash, transfer.to
+            );
+            return Err(Error::<T>::InvalidRecipient.into());
+        }
+        let events = events::read_events(&header.state_root, proof.events_proof)
+            .ok_or(Error::<T>::BadEventProof)?;
+        if !events::is_transfer_event(&events, proof.transfer_event_offset, proof.index, &transfer)
+            || !events::is_success_event(&events, proof.success_event_offset, proof.index)
+        {
+            error!(
+                "[apply_push_transaction] Tx ({:?}) is not proved to be successful by the events",
+                tx_hash
+            );
+            return Err(Error::<T>::FailedTransfer.into());
+        }
+        // the sender has the same public key on ChainX.
+        let sender: &[u8] = transfer.from.as_ref();
+        let who = T::AccountId::decode(&mut &sender[..]).map_err(|_| Error::<T>::InvalidSender)?;
+        let value: BalanceOf<T> = transfer.value.saturated_into();
+
+        xpallet_gateway_records::Module::<T>::deposit_by_tx(
+            Chain::Polkadot,
+            tx_hash.as_bytes().to_vec(),
+            &who,
+            Self::ASSET_ID,
+            value,
+        )?;
+        info!(
+            "[apply_push_transaction] Deposit tx ({:?}) success, who:{:?}, balance:{:?}",
+            tx_hash, who, value
+        );
+        Self::deposit_event(Event::<T>::Deposited(tx_hash, who, value));
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use frame_support::{impl_outer_origin, parameter_types, sp_io, traits::Contains, weights::Weight};
+use frame_system::EnsureSignedBy;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    AccountId32, DispatchError, Perbill,
+};
+
+use chainx_primitives::AssetId;
+pub use xp_protocol::X_DOT;
+use xpallet_assets::AssetRestrictions;
+use xpallet_assets_registrar::{AssetInfo, Chain};
+use xpallet_gateway_common::{traits::TrusteeSession, types::TrusteeSessionInfo};
+
+use crate::{DotTrusteeAddrInfo, Error, Module, Trait};
+
+/// The AccountId alias in this test module.
+pub(crate) type AccountId = AccountId32;
+pub(crate) type BlockNumber = u64;
+pub(crate) type Balance = u128;
+pub(crate) type Amount = i128;
+
+impl_outer_origin! {
+    pub enum Origin for Test where system = frame_system {}
+}
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+parameter_types! {
+    pub const BlockHashCount: u64 = 250;
+    pub const MaximumBlockWeight: Weight = 1024;
+    pub const MaximumBlockLength: u32 = 2 * 1024;
+    pub const AvailableBlockRatio: Perbill = Perbill::from_percent(75);
+}
+
+impl frame_system::Trait for Test {
+    type BaseCallFilter = ();
+    type Origin = Origin;
+    type Call = ();
+    type Index = u64;
+    type BlockNumber = BlockNumber;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = ();
+    type BlockHashCount = BlockHashCount;
+    type MaximumBlockWeight = MaximumBlockWeight;
+    type DbWeight = ();
+    type BlockExecutionWeight = ();
+    type ExtrinsicBaseWeight = ();
+    type MaximumExtrinsicWeight = MaximumBlockWeight;
+    type MaximumBlockLength = MaximumBlockLength;
+    type AvailableBlockRatio = AvailableBlockRatio;
+    type Version = ();
+    type PalletInfo = ();
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+}
+
+parameter_types! {
+    pub const ExistentialDeposit: u64 = 0;
+}
+impl pallet_balances::Trait for Test {
+    type MaxLocks = ();
+    type Balance = Balance;
+    type DustRemoval = ();
+    type Event = ();
+    type ExistentialDeposit = ExistentialDeposit;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+// assets
+parameter_types! {
+    pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
+}
+
+impl xpallet_assets_registrar::Trait for Test {
+    type Event = ();
+    type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
+    type RegistrarHandler = ();
+    type AssetIssuance = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_assets::Trait for Test {
+    type Event = ();
+    type Currency = Balances;
+    type Amount = Amount;
+    type TreasuryAccount = ();
+    type OnCreatedAccount = frame_system::CallOnCreatedAccount<Test>;
+    type OnAssetChanged = ();
+    type WeightInfo = ();
+}
+
+impl xpallet_gateway_records::Trait for Test {
+    type Event = ();
+    type WithdrawalWindow = ();
+    type MaxWithdrawalHistory = ();
+    type WithdrawalFeeProvider = ();
+    type WeightInfo = ();
+}
+
+pub struct Trustees;
+impl Contains<AccountId> for Trustees {
+    fn sorted_members() -> Vec<AccountId> {
+        vec![trustee()]
+    }
+}
+
+pub struct MockTrusteeSession;
+impl TrusteeSession<AccountId, DotTrusteeAddrInfo> for MockTrusteeSession {
+    fn trustee_session(
+        _: u32,
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Self::current_trustee_session()
+    }
+
+    fn current_trustee_session(
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Ok(TrusteeSessionInfo {
+            trustee_list: vec![trustee()],
+            threshold: 1,
+            hot_address: trustee_hot_address(),
+            cold_address: DotTrusteeAddrInfo::new(vec![AccountId32::new([0xbb; 32])], 1),
+        })
+    }
+
+    fn last_trustee_session(
+    ) -> Result<TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        Err("NoTrustee".into())
+    }
+
+    #[cfg(feature = "std")]
+    fn genesis_trustee(_: Chain, _: &[AccountId]) {}
+}
+
+parameter_types! {
+    pub const NetworkPrefix: u8 = 0;
+}
+
+impl Trait for Test {
+    type Event = ();
+    type NetworkPrefix = NetworkPrefix;
+    type TrusteeSessionProvider = MockTrusteeSession;
+    type TrusteeOrigin = EnsureSignedBy<Trustees, AccountId>;
+    type WeightInfo = ();
+}
+
+pub type System = frame_system::Module<Test>;
+pub type Balances = pallet_balances::Module<Test>;
+pub type XAssets = xpallet_assets::Module<Test>;
+pub type XGatewayPolkadot = Module<Test>;
+pub type XGatewayPolkadotErr = Error<Test>;
+
+pub(crate) fn dot() -> (AssetId, AssetInfo, AssetRestrictions) {
+    (
+        X_DOT,
+        AssetInfo::new::<Test>(
+            b"X-DOT".to_vec(),
+            b"X-DOT".to_vec(),
+            Chain::Polkadot,
+            10,
+            b"ChainX's cross-chain Polkadot".to_vec(),
+        )
+        .unwrap(),
+        AssetRestrictions::DESTROY_USABLE,
+    )
+}
+
+pub struct ExtBuilder;
+impl Default for ExtBuilder {
+    fn default() -> Self {
+        Self
+    }
+}
+impl ExtBuilder {
+    pub fn build(self) -> sp_io::TestExternalities {
+        let mut storage = frame_system::GenesisConfig::default()
+            .build_storage::<Test>()
+            .unwrap();
+
+        let (asset_id, info, restrictions) = dot();
+        let _ = xpallet_assets_registrar::GenesisConfig {
+            assets: vec![(asset_id, info, true, true)],
+        }
+        .assimilate_storage::<Test>(&mut storage);
+
+        let _ = xpallet_assets::GenesisConfig::<Test> {
+            assets_restrictions: vec![(asset_id, restrictions)],
+            endowed: Default::default(),
+        }
+        .assimilate_storage(&mut storage);
+
+        sp_io::TestExternalities::new(storage)
+    }
+    pub fn build_and_execute(self, test: impl FnOnce() -> ()) {
+        let mut ext = self.build();
+        ext.execute_with(|| System::set_block_number(1));
+        ext.execute_with(test);
+    }
+}
+
+pub fn trustee() -> AccountId32 {
+    AccountId32::new([0xaa; 32])
+}
+pub fn alice() -> AccountId32 {
+    AccountId32::new([1; 32])
+}
+pub fn trustee_hot_address() -> DotTrusteeAddrInfo {
+    DotTrusteeAddrInfo::new(vec![AccountId32::new([0xaa; 32])], 1)
+}
@@ -0,0 +1,572 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Compact, Encode};
+use frame_support::{assert_noop, assert_ok};
+use frame_system::RawOrigin;
+use sp_core::H256;
+use sp_finality_grandpa::{
+    AuthorityId, AuthorityList, ConsensusLog, ScheduledChange, SetId, GRANDPA_ENGINE_ID,
+};
+use sp_io::hashing::twox_128;
+use sp_keyring::Ed25519Keyring;
+use sp_runtime::{
+    traits::{BlakeTwo256, Header as HeaderT},
+    AccountId32, Digest, DigestItem,
+};
+use sp_trie::{
+    trie_types::{TrieDB, TrieDBMut},
+    MemoryDB, Recorder, Trie, TrieMut,
+};
+
+use xpallet_assets::ChainT;
+
+use crate::mock::*;
+use crate::types::{
+    DotBlockNumber, DotHeader, DotTransferProof, GrandpaCommit, GrandpaJustification,
+    GrandpaPrecommit, GrandpaSignedPrecommit,
+};
+
+const ROUND: u64 = 2;
+
+fn authorities(keys: &[Ed25519Keyring]) -> AuthorityList {
+    keys.iter()
+        .map(|key| (AuthorityId::from(key.public()), 1))
+        .collect()
+}
+
+fn genesis_authorities() -> AuthorityList {
+    authorities(&[
+        Ed25519Keyring::Alice,
+        Ed25519Keyring::Bob,
+        Ed25519Keyring::Charlie,
+        Ed25519Keyring::Dave,
+    ])
+}
+
+fn header(number: DotBlockNumber, parent_hash: H256, extrinsics_root: H256) -> DotHeader {
+    DotHeader::new(
+        number,
+        extrinsics_root,
+        H256::default(),
+        parent_hash,
+        Digest::default(),
+    )
+}
+
+fn header_with_change(
+    number: DotBlockNumber,
+    parent_hash: H256,
+    change: ScheduledChange<DotBlockNumber>,
+) -> DotHeader {
+    let mut header = header(number, parent_hash, H256::default());
+    header.digest_mut().push(DigestItem::Consensus(
+        GRANDPA_ENGINE_ID,
+        ConsensusLog::ScheduledChange(change).encode(),
+    ));
+    header
+}
+
+fn signed_precommit(
+    key: Ed25519Keyring,
+    target: &DotHeader,
+    set_id: SetId,
+) -> GrandpaSignedPrecommit {
+    let precommit = GrandpaPrecommit {
+        target_hash: target.hash(),
+        target_number: target.number,
+    };
+    let payload = (1u8, &precommit, ROUND, set_id).encode();
+    GrandpaSignedPrecommit {
+        precommit,
+        signature: key.sign(&payload).into(),
+        id: key.public().into(),
+    }
+}
+
+fn justification(
+    header: &DotHeader,
+    set_id: SetId,
+    signers: &[Ed25519Keyring],
+) -> GrandpaJustification {
+    GrandpaJustification {
+        round: ROUND,
+        commit: GrandpaCommit {
+            target_hash: header.hash(),
+            target_number: header.number,
+            precommits: signers
+                .iter()
+                .map(|key| signed_precommit(*key, header, set_id))
+                .collect(),
+        },
+        votes_ancestries: vec![],
+    }
+}
+
+fn signers() -> Vec<Ed25519Keyring> {
+    vec![
+        Ed25519Keyring::Alice,
+        Ed25519Keyring::Bob,
+        Ed25519Keyring::Charlie,
+    ]
+}
+
+fn setup(checkpoint: &DotHeader) {
+    assert_ok!(XGatewayPolkadot::set_checkpoint(
+        RawOrigin::Root.into(),
+        checkpoint.clone(),
+        genesis_authorities(),
+        0
+    ));
+}
+
+fn submit(header: &DotHeader, justification: GrandpaJustification) -> sp_runtime::DispatchResult {
+    XGatewayPolkadot::submit_finality_proof(
+        RawOrigin::Signed(alice()).into(),
+        header.clone(),
+        justification,
+    )
+    .map(|_| ())
+    .map_err(|e| e.error)
+}
+
+#[test]
+fn test_precommit_payload() {
+    let precommit = GrandpaPrecommit {
+        target_hash: H256::repeat_byte(1),
+        target_number: 10,
+    };
+    let message =
+        finality_grandpa::Message::Precommit(finality_grandpa::Precommit::<H256, DotBlockNumber> {
+            target_hash: precommit.target_hash,
+            target_number: precommit.target_number,
+        });
+    assert_eq!(
+        (1u8, &precommit, ROUND, 5u64).encode(),
+        sp_finality_grandpa::localized_payload(ROUND, 5, &message)
+    );
+}
+
+#[test]
+fn test_set_checkpoint() {
+    ExtBuilder::default().build_and_execute(|| {
+        let checkpoint = header(100, H256::default(), H256::default());
+        assert_noop!(
+            XGatewayPolkadot::set_checkpoint(
+                RawOrigin::Signed(trustee()).into(),
+                checkpoint.clone(),
+                genesis_authorities(),
+                0
+            ),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        setup(&checkpoint);
+        let best = XGatewayPolkadot::best_finalized();
+        assert_eq!(best.hash, checkpoint.hash());
+        assert_eq!(best.number, 100);
+        assert!(XGatewayPolkadot::headers(&checkpoint.hash()).is_some());
+        assert_eq!(XGatewayPolkadot::authorities(), genesis_authorities());
+    })
+}
+
+#[test]
+fn test_submit_finality_proof() {
+    ExtBuilder::default().build_and_execute(|| {
+        let checkpoint = header(100, H256::default(), H256::default());
+        let next = header(110, H256::repeat_byte(1), H256::default());
+        assert_noop!(
+            submit(&next, justification(&next, 0, &signers())),
+            XGatewayPolkadotErr::NotInitialized
+        );
+        setup(&checkpoint);
+
+        // the threshold of 4 authorities is 3
+        assert_noop!(
+            submit(&next, justification(&next, 0, &signers()[..2])),
+            XGatewayPolkadotErr::NotEnoughWeight
+        );
+        assert_noop!(
+            submit(
+                &next,
+                justification(
+                    &next,
+                    0,
+                    &[
+                        Ed25519Keyring::Alice,
+                        Ed25519Keyring::Bob,
+                        Ed25519Keyring::Eve
+                    ]
+                )
+            ),
+            XGatewayPolkadotErr::UnknownAuthority
+        );
+        assert_noop!(
+            submit(
+                &next,
+                justification(
+                    &next,
+                    0,
+                    &[
+                        Ed25519Keyring::Alice,
+                        Ed25519Keyring::Bob,
+                        Ed25519Keyring::Bob
+                    ]
+                )
+            ),
+            XGatewayPolkadotErr::DuplicateAuthorityVote
+        );
+        // signed for another authority set
+        assert_noop!(
+            submit(&next, justification(&next, 1, &signers())),
+            XGatewayPolkadotErr::InvalidAuthoritySignature
+        );
+        // the justification of another header
+        let other = header(111, H256::repeat_byte(1), H256::default());
+        assert_noop!(
+            submit(&next, justification(&other, 0, &signers())),
+            XGatewayPolkadotErr::InvalidJustification
+        );
+
+        assert_ok!(submit(&next, justification(&next, 0, &signers())));
+        let best = XGatewayPolkadot::best_finalized();
+        assert_eq!(best.hash, next.hash());
+        assert_eq!(best.number, 110);
+
+        assert_noop!(
+            submit(&next, justification(&next, 0, &signers())),
+            XGatewayPolkadotErr::OldHeader
+        );
+    })
+}
+
+#[test]
+fn test_precommit_descendants() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup(&header(100, H256::default(), H256::default()));
+        let target = header(110, H256::repeat_byte(1), H256::default());
+        let child = header(111, target.hash(), H256::default());
+
+        // precommit the child of the target
+        let mut proof = justification(&target, 0, &signers());
+        proof.commit.precommits[0] = signed_precommit(Ed25519Keyring::Alice, &child, 0);
+        assert_noop!(
+            submit(&target, proof.clone()),
+            XGatewayPolkadotErr::InvalidJustification
+        );
+
+        proof.votes_ancestries = vec![child];
+        assert_ok!(submit(&target, proof));
+        assert_eq!(XGatewayPolkadot::best_finalized().hash, target.hash());
+    })
+}
+
+#[test]
+fn test_authority_set_change() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup(&header(100, H256::default(), H256::default()));
+        let next_keys = [
+            Ed25519Keyring::Eve,
+            Ed25519Keyring::Ferdie,
+            Ed25519Keyring::One,
+        ];
+
+        // enacted immediately
+        let h110 = header_with_change(
+            110,
+            H256::repeat_byte(1),
+            ScheduledChange {
+                next_authorities: authorities(&next_keys),
+                delay: 0,
+            },
+        );
+        assert_ok!(submit(&h110, justification(&h110, 0, &signers())));
+        assert_eq!(XGatewayPolkadot::current_set_id(), 1);
+        assert_eq!(XGatewayPolkadot::authorities(), authorities(&next_keys));
+
+        let h120 = header_with_change(
+            120,
+            H256::repeat_byte(2),
+            ScheduledChange {
+                next_authorities: genesis_authorities(),
+                delay: 10,
+            },
+        );
+        assert_noop!(
+            submit(&h120, justification(&h120, 0, &signers())),
+            XGatewayPolkadotErr::UnknownAuthority
+        );
+        assert_ok!(submit(&h120, justification(&h120, 1, &next_keys)));
+        assert_eq!(XGatewayPolkadot::current_set_id(), 1);
+        assert_eq!(
+            XGatewayPolkadot::next_scheduled_change().map(|change| change.enacted_at),
+            Some(130)
+        );
+
+        // the header which enacts the change must be imported
+        let h131 = header(131, H256::repeat_byte(3), H256::default());
+        assert_noop!(
+            submit(&h131, justification(&h131, 1, &next_keys)),
+            XGatewayPolkadotErr::ScheduledChangeSkipped
+        );
+        let h130 = header(130, H256::repeat_byte(3), H256::default());
+        assert_ok!(submit(&h130, justification(&h130, 1, &next_keys)));
+        assert_eq!(XGatewayPolkadot::current_set_id(), 2);
+        assert_eq!(XGatewayPolkadot::authorities(), genesis_authorities());
+        assert!(XGatewayPolkadot::next_scheduled_change().is_none());
+
+        assert_ok!(submit(&h131, justification(&h131, 2, &signers())));
+    })
+}
+
+#[test]
+fn test_forced_change() {
+    ExtBuilder::default().build_and_execute(|| {
+        setup(&header(100, H256::default(), H256::default()));
+        let mut h110 = header(110, H256::repeat_byte(1), H256::default());
+        h110.digest_mut().push(DigestItem::Consensus(
+            GRANDPA_ENGINE_ID,
+            ConsensusLog::ForcedChange(
+                0,
+                ScheduledChange {
+                    next_authorities: genesis_authorities(),
+                    delay: 0,
+                },
+            )
+            .encode(),
+        ));
+        assert_noop!(
+            submit(&h110, justification(&h110, 0, &signers())),
+            XGatewayPolkadotErr::UnsupportedForcedChange
+        );
+    })
+}
+
+fn transfer_extrinsic(to: &AccountId32, value: u128) -> Vec<u8> {
+    // signed extrinsic v4
+    let mut body = vec![0x84, 0x00];
+    body.extend_from_slice(alice().as_ref());
+    // sr25519 signature
+    body.push(1);
+    body.extend_from_slice(&[0; 64]);
+    // immortal, nonce, tip
+    body.push(0);
+    body.extend(Compact(0u32).encode());
+    body.extend(Compact(0u128).encode());
+    // balances.transfer_keep_alive
+    body.extend_from_slice(&[5, 3]);
+    body.push(0x00);
+    body.extend_from_slice(to.as_ref());
+    body.extend(Compact(value).encode());
+    body.encode()
+}
+
+/// Build the extrinsics trie and returns the root and the proof of each extrinsic.
+fn extrinsics_trie(extrinsics: &[Vec<u8>]) -> (H256, Vec<Vec<Vec<u8>>>) {
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = H256::default();
+    {
+        let mut trie = TrieDBMut::<BlakeTwo256>::new(&mut db, &mut root);
+        for (index, extrinsic) in extrinsics.iter().enumerate() {
+            trie.insert(&Compact(index as u32).encode(), extrinsic)
+                .unwrap();
+        }
+    }
+    let trie = TrieDB::<BlakeTwo256>::new(&db, &root).unwrap();
+    let proofs = (0..extrinsics.len())
+        .map(|index| {
+            let mut recorder = Recorder::new();
+            trie.get_with(&Compact(index as u32).encode(), &mut recorder)
+                .unwrap();
+            recorder.drain().into_iter().map(|r| r.data).collect()
+        })
+        .collect();
+    (root, proofs)
+}
+
+/// Build the state trie with only `System::Events`, returns the root and the proof of it.
+fn events_trie(events: &[u8]) -> (H256, Vec<Vec<u8>>) {
+    let mut key = twox_128(b"System").to_vec();
+    key.extend_from_slice(&twox_128(b"Events"));
+    let mut db = MemoryDB::<BlakeTwo256>::default();
+    let mut root = H256::default();
+    {
+        let mut trie = TrieDBMut::<BlakeTwo256>::new(&mut db, &mut root);
+        trie.insert(&key, events).unwrap();
+    }
+    let trie = TrieDB::<BlakeTwo256>::new(&db, &root).unwrap();
+    let mut recorder = Recorder::new();
+    trie.get_with(&key, &mut recorder).unwrap();
+    let proof = recorder.drain().into_iter().map(|r| r.data).collect();
+    (root, proof)
+}
+
+/// Returns the encoded event record emitted in the phase of applying the extrinsic `index`.
+fn event_record(index: u32, event: [u8; 2], data: Vec<u8>) -> Vec<u8> {
+    let mut record = vec![0];
+    record.extend(index.encode());
+    record.extend_from_slice(&event);
+    record.extend(data);
+    // topics
+    record.extend(Vec::<H256>::new().encode());
+    record
+}
+
+#[test]
+fn test_push_transaction() {
+    ExtBuilder::default().build_and_execute(|| {
+        let trustee_account = trustee_hot_address().account;
+        let deposit = transfer_extrinsic(&trustee_account, 10_000_000_000);
+        let other = transfer_extrinsic(&AccountId32::new([2; 32]), 10_000_000_000);
+        // the same transfer as the deposit which failed at dispatch.
+        let failed = transfer_extrinsic(&trustee_account, 10_000_000_000);
+        let (root, proofs) = extrinsics_trie(&[
+            vec![0x10, 0, 0, 0, 0],
+            deposit.clone(),
+            other.clone(),
+            failed.clone(),
+        ]);
+
+        let transfer = (alice(), trustee_account, 10_000_000_000u128).encode();
+        // DispatchInfo
+        let dispatch_info = (1_000u64, 0u8, 0u8).encode();
+        // compact length of the records
+        let mut events = Compact(3u32).encode();
+        let transfer_event_offset = events.len() as u32;
+        events.extend(event_record(1, [5, 2], transfer));
+        let success_event_offset = events.len() as u32;
+        events.extend(event_record(1, [0, 0], dispatch_info));
+        let failed_event_offset = events.len() as u32;
+        // System::ExtrinsicFailed(DispatchError::Module { index: 5, error: 2 }, DispatchInfo)
+        let mut failed_data = vec![3, 5, 2];
+        failed_data.extend((1_000u64, 0u8, 0u8).encode());
+        events.extend(event_record(3, [0, 1], failed_data));
+        let (state_root, events_proof) = events_trie(&events);
+
+        let mut checkpoint = header(100, H256::default(), root);
+        checkpoint.state_root = state_root;
+        setup(&checkpoint);
+
+        let proof = DotTransferProof {
+            block_hash: checkpoint.hash(),
+            index: 1,
+            extrinsic: deposit,
+            proof: proofs[1].clone(),
+            events_proof,
+            transfer_event_offset,
+            success_event_offset,
+        };
+        assert_noop!(
+            XGatewayPolkadot::push_transaction(RawOrigin::Signed(alice()).into(), proof.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        let push = |proof: DotTransferProof| {
+            XGatewayPolkadot::push_transaction(RawOrigin::Signed(trustee()).into(), proof)
+                .map(|_| ())
+                .map_err(|e| e.error)
+        };
+        assert_noop!(
+            push(DotTransferProof {
+                block_hash: H256::repeat_byte(1),
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::HeaderNotFound
+        );
+        assert_noop!(
+            push(DotTransferProof {
+                index: 2,
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::BadMerkleProof
+        );
+        assert_noop!(
+            push(DotTransferProof {
+                index: 2,
+                extrinsic: other,
+                proof: proofs[2].clone(),
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::InvalidRecipient
+        );
+        assert_noop!(
+            push(DotTransferProof {
+                index: 0,
+                extrinsic: vec![0x10, 0, 0, 0, 0],
+                proof: proofs[0].clone(),
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::NotTransfer
+        );
+
+        assert_noop!(
+            push(DotTransferProof {
+                events_proof: vec![],
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::BadEventProof
+        );
+        // the failed transfer is included in the block but does not emit the events.
+        assert_noop!(
+            push(DotTransferProof {
+                index: 3,
+                extrinsic: failed.clone(),
+                proof: proofs[3].clone(),
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::FailedTransfer
+        );
+        assert_noop!(
+            push(DotTransferProof {
+                index: 3,
+                extrinsic: failed,
+                proof: proofs[3].clone(),
+                transfer_event_offset: failed_event_offset,
+                success_event_offset: failed_event_offset,
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::FailedTransfer
+        );
+        assert_noop!(
+            push(DotTransferProof {
+                success_event_offset: transfer_event_offset,
+                ..proof.clone()
+            }),
+            XGatewayPolkadotErr::FailedTransfer
+        );
+
+        assert_ok!(push(proof.clone()));
+        assert_eq!(XAssets::usable_balance(&alice(), &X_DOT), 10_000_000_000);
+        assert_noop!(push(proof), XGatewayPolkadotErr::ReplayedTx);
+    })
+}
+
+#[test]
+fn test_check_addr() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayPolkadot::check_addr(
+            b"15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5",
+            b""
+        ));
+        // substrate address
+        assert_noop!(
+            XGatewayPolkadot::check_addr(b"5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY", b""),
+            XGatewayPolkadotErr::InvalidAddress
+        );
+        assert_noop!(
+            XGatewayPolkadot::check_addr(b"0x1111", b""),
+            XGatewayPolkadotErr::InvalidAddress
+        );
+    })
+}
+
+#[test]
+fn test_withdrawal_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XGatewayPolkadot::set_withdrawal_fee(
+            RawOrigin::Signed(trustee()).into(),
+            1_000_000_000
+        ));
+        let limit = XGatewayPolkadot::withdrawal_limit(&X_DOT).unwrap();
+        assert_eq!(limit.fee, 1_000_000_000);
+        assert_eq!(limit.minimal_withdrawal, 1_500_000_000);
+        assert!(XGatewayPolkadot::withdrawal_limit(&xp_protocol::X_BTC).is_err());
+    })
+}
@@ -0,0 +1,207 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode, Error as CodecError};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use frame_support::dispatch::{DispatchError, DispatchResult};
+use sp_runtime::RuntimeDebug;
+use sp_std::{convert::TryFrom, prelude::*};
+
+use xp_logging::{error, info};
+use xpallet_assets::Chain;
+use xpallet_gateway_common::{
+    traits::{ChainProvider, TrusteeForChain},
+    trustees::{TrusteeMultisigProvider, TrusteeSessionManager},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+
+use crate::types::DotAccountId;
+use crate::{Error, Module, Trait};
+
+pub type DotTrusteeSessionInfo<AccountId> = TrusteeSessionInfo<AccountId, DotTrusteeAddrInfo>;
+pub type DotTrusteeIntentionProps = TrusteeIntentionProps<DotTrusteeType>;
+pub type DotTrusteeSessionManager<T> = TrusteeSessionManager<T, DotTrusteeAddrInfo>;
+pub type DotTrusteeMultisig<T> = TrusteeMultisigProvider<T, DotTrusteeType>;
+
+/// The multisig account of the trustees on Polkadot.
+///
+/// The `account` is derived from the `signers` and the `threshold` in the same way as
+/// `pallet_multisig::multi_account_id`, so that the trustees could sign with `as_multi`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotTrusteeAddrInfo {
+    pub account: DotAccountId,
+    pub signers: Vec<DotAccountId>,
+    pub threshold: u16,
+}
+
+impl DotTrusteeAddrInfo {
+    pub fn new(mut signers: Vec<DotAccountId>, threshold: u16) -> Self {
+        signers.sort();
+        Self {
+            account: multi_account_id(&signers, threshold),
+            signers,
+            threshold,
+        }
+    }
+}
+
+/// Same as `pallet_multisig::multi_account_id`, the `signers` must be sorted.
+pub fn multi_account_id(signers: &[DotAccountId], threshold: u16) -> DotAccountId {
+    let entropy =
+        (b"modlpy/utilisuba", signers, threshold).using_encoded(sp_io::hashing::blake2_256);
+    DotAccountId::new(entropy)
+}
+
+impl From<DotTrusteeAddrInfo> for Vec<u8> {
+    fn from(value: DotTrusteeAddrInfo) -> Self {
+        value.encode()
+    }
+}
+
+impl TryFrom<Vec<u8>> for DotTrusteeAddrInfo {
+    type Error = CodecError;
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        Decode::decode(&mut &value[..])
+    }
+}
+
+impl ChainProvider for DotTrusteeAddrInfo {
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+
+/// The sr25519 or ed25519 public key (32 bytes) of a trustee on Polkadot.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct DotTrusteeType(
+    #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_hex"))] pub Vec<u8>,
+);
+
+impl DotTrusteeType {
+    /// Returns the Polkadot account of this public key.
+    pub fn account(&self) -> DotAccountId {
+        let mut raw = [0u8; 32];
+        raw.copy_from_slice(&self.0);
+        DotAccountId::new(raw)
+    }
+}
+
+impl From<DotTrusteeType> for Vec<u8> {
+    fn from(value: DotTrusteeType) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<Vec<u8>> for DotTrusteeType {
+    type Error = ();
+
+    fn try_from(value: Vec<u8>) -> Result<Self, Self::Error> {
+        if value.len() == 32 {
+            Ok(DotTrusteeType(value))
+        } else {
+            Err(())
+        }
+    }
+}
+
+impl ChainProvider for DotTrusteeType {
+    fn chain() -> Chain {
+        Chain::Polkadot
+    }
+}
+
+fn check_keys<T: Trait>(keys: &[DotTrusteeType]) -> DispatchResult {
+    let has_duplicate = (1..keys.len()).any(|i| keys[i..].contains(&keys[i - 1]));
+    if has_duplicate {
+        error!("[generate_trustee_session_info] Keys contains duplicate pubkey");
+        return Err(Error::<T>::DuplicatedKeys.into());
+    }
+    Ok(())
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, DotTrusteeType, DotTrusteeAddrInfo> for Module<T> {
+    fn check_trustee_entity(raw_addr: &[u8]) -> Result<DotTrusteeType, DispatchError> {
+        let trustee_type = DotTrusteeType::try_from(raw_addr.to_vec())
+            .map_err(|_| Error::<T>::InvalidPublicKey)?;
+        if trustee_type.0.iter().all(|b| *b == 0) {
+            error!("[check_trustee_entity] Invalid public key (Zero32)");
+            return Err(Error::<T>::InvalidPublicKey.into());
+        }
+        Ok(trustee_type)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<DotTrusteeType>)>,
+        config: TrusteeInfoConfig,
+    ) -> Result<TrusteeSessionInfo<T::AccountId, DotTrusteeAddrInfo>, DispatchError> {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<DotTrusteeType>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<DotTrusteeType>, Vec<DotTrusteeType>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_keys::<T>(&hot_keys)?;
+        check_keys::<T>(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!(
+                "[generate_trustee_session_info] Trustees {:?} is less/more than {{min:{}, max:{}}} people",
+                trustees, config.min_trustee_count, config.max_trustee_count
+            );
+            return Err(Error::<T>::InvalidTrusteeCount.into());
+        }
+
+        let threshold = two_thirds_unsafe(trustees.len() as u32) as u16;
+        let hot_address = DotTrusteeAddrInfo::new(
+            hot_keys.iter().map(DotTrusteeType::account).collect(),
+            threshold,
+        );
+        let cold_address = DotTrusteeAddrInfo::new(
+            cold_keys.iter().map(DotTrusteeType::account).collect(),
+            threshold,
+        );
+        info!(
+            "[generate_trustee_session_info] hot_addr:{:?}, cold_addr:{:?}, trustee_list:{:?}",
+            hot_address, cold_address, trustees
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            threshold,
+            hot_address,
+            cold_address,
+        })
+    }
+}
+
+#[test]
+fn test_dot_trustee_addr_info() {
+    let alice = DotAccountId::new([1; 32]);
+    let bob = DotAccountId::new([2; 32]);
+    let info = DotTrusteeAddrInfo::new(vec![bob.clone(), alice.clone()], 2);
+    // the signers are sorted, thus the account does not depend on the order
+    assert_eq!(info.signers, vec![alice.clone(), bob.clone()]);
+    assert_eq!(info, DotTrusteeAddrInfo::new(vec![alice.clone(), bob], 2));
+    assert_ne!(info.account, multi_account_id(&info.signers, 1));
+
+    let raw: Vec<u8> = info.clone().into();
+    assert_eq!(DotTrusteeAddrInfo::try_from(raw).unwrap(), info);
+
+    assert_eq!(
+        DotTrusteeType::try_from(vec![1; 32]).unwrap().account(),
+        alice
+    );
+    assert!(DotTrusteeType::try_from(vec![1; 33]).is_err());
+}
@@ -0,0 +1,104 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_core::H256;
+use sp_finality_grandpa::{AuthorityId, AuthorityList, AuthoritySignature};
+use sp_runtime::{generic, traits::BlakeTwo256, AccountId32, RuntimeDebug};
+use sp_std::prelude::*;
+
+/// The account id type of the Polkadot relay chain.
+pub type DotAccountId = AccountId32;
+
+/// The block number type of the Polkadot relay chain.
+pub type DotBlockNumber = u32;
+/// The header type of the Polkadot relay chain.
+pub type DotHeader = generic::Header<DotBlockNumber, BlakeTwo256>;
+
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotHeaderIndex {
+    pub hash: H256,
+    pub number: DotBlockNumber,
+}
+
+/// The information of a finalized header that is needed to verify the proofs.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct DotHeaderInfo {
+    pub number: DotBlockNumber,
+    pub state_root: H256,
+    pub extrinsics_root: H256,
+}
+
+/// The authority set change which has been signaled but not yet enacted.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct DotScheduledChange {
+    pub next_authorities: AuthorityList,
+    /// The number of the header which enacts the change.
+    pub enacted_at: DotBlockNumber,
+}
+
+/// Same as `finality_grandpa::Precommit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaPrecommit {
+    pub target_hash: H256,
+    pub target_number: DotBlockNumber,
+}
+
+/// Same as `finality_grandpa::SignedPrecommit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaSignedPrecommit {
+    pub precommit: GrandpaPrecommit,
+    pub signature: AuthoritySignature,
+    pub id: AuthorityId,
+}
+
+/// Same as `finality_grandpa::Commit`.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaCommit {
+    pub target_hash: H256,
+    pub target_number: DotBlockNumber,
+    pub precommits: Vec<GrandpaSignedPrecommit>,
+}
+
+/// Same as `sc_finality_grandpa::GrandpaJustification`, which could be fetched by
+/// the `grandpa_proveFinality` rpc of the Polkadot node.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct GrandpaJustification {
+    pub round: u64,
+    pub commit: GrandpaCommit,
+    pub votes_ancestries: Vec<DotHeader>,
+}
+
+/// The proof of a transfer extrinsic included in a finalized block.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct DotTransferProof {
+    pub block_hash: H256,
+    /// The index of the extrinsic in the block.
+    pub index: u32,
+    /// The encoded extrinsic.
+    pub extrinsic: Vec<u8>,
+    /// The trie proof of the extrinsic against the `extrinsics_root`.
+    pub proof: Vec<Vec<u8>>,
+    /// The trie proof of `System::Events` against the `state_root`.
+    pub events_proof: Vec<Vec<u8>>,
+    /// The byte offset of the `Balances::Transfer` record of the extrinsic in `System::Events`.
+    pub transfer_event_offset: u32,
+    /// The byte offset of the `System::ExtrinsicSuccess` record of the extrinsic in `System::Events`.
+    pub success_event_offset: u32,
+}
+
+/// The transfer on Polkadot.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub struct DotTransfer {
+    pub from: DotAccountId,
+    pub to: DotAccountId,
+    pub value: u128,
+    /// The index of the balances pallet, which emits the transfer event as well.
+    pub pallet_index: u8,
+}
@@ -0,0 +1,103 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_gateway_polkadot
+//!
+//! NOTE: not benchmarked yet, the weights are estimated from the similar calls
+//! of xpallet_gateway_bitcoin and pallet_grandpa.
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_gateway_polkadot.
+pub trait WeightInfo {
+    fn submit_finality_proof(p: u32) -> Weight;
+    fn push_transaction() -> Weight;
+    fn set_checkpoint() -> Weight;
+    fn set_transfer_calls() -> Weight;
+    fn set_withdrawal_fee() -> Weight;
+    fn process_withdrawals(u: u32) -> Weight;
+    fn finish_withdrawals(u: u32) -> Weight;
+}
+
+/// Weights for xpallet_gateway_polkadot using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn submit_finality_proof(p: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((75_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn push_transaction() -> Weight {
+        (250_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(10 as Weight))
+            .saturating_add(T::DbWeight::get().writes(6 as Weight))
+    }
+    fn set_checkpoint() -> Weight {
+        (30_000_000 as Weight).saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn set_transfer_calls() -> Weight {
+        (4_500_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_fee() -> Weight {
+        (4_600_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn process_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((60_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+    fn finish_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((80_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn submit_finality_proof(p: u32) -> Weight {
+        (80_000_000 as Weight)
+            .saturating_add((75_000_000 as Weight).saturating_mul(p as Weight))
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn push_transaction() -> Weight {
+        (250_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(10 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(6 as Weight))
+    }
+    fn set_checkpoint() -> Weight {
+        (30_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn set_transfer_calls() -> Weight {
+        (4_500_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_withdrawal_fee() -> Weight {
+        (4_600_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn process_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((60_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+    }
+    fn finish_withdrawals(u: u32) -> Weight {
+        (20_000_000 as Weight)
+            .saturating_add((80_000_000 as Weight).saturating_mul(u as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((5 as Weight).saturating_mul(u as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(u as Weight)))
+    }
+}
@@ -3,15 +3,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::too_many_arguments, clippy::unnecessary_mut_passed)]
 
-use sp_std::collections::btree_map::BTreeMap;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 use codec::Codec;
 
 pub use chainx_primitives::{AssetId, Decimals};
 pub use xpallet_assets::Chain;
-pub use xpallet_gateway_records::{Withdrawal, WithdrawalRecordId, WithdrawalState};
+pub use xpallet_gateway_records::{
+    DepositRecord, TxRecord, Withdrawal, WithdrawalCaps, WithdrawalFeeMode, WithdrawalRecordId,
+    WithdrawalState,
+};
 
 sp_api::decl_runtime_apis! {
+    /// The version 2 changes the layout of `Withdrawal` (fee, caps and history fields),
+    /// and adds `withdrawal_caps`, `withdrawal_list_of`, `withdrawal_record` and `tx_record`.
+    #[api_version(2)]
     pub trait XGatewayRecordsApi<AccountId, Balance, BlockNumber>
     where
         AccountId: Codec,
@@ -21,5 +27,17 @@ sp_api::decl_runtime_apis! {
         fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
 
         fn withdrawal_list_by_chain(chain: Chain) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        /// Returns the withdrawal caps of the asset and its outflow in the current window.
+        fn withdrawal_caps(asset_id: AssetId) -> Option<(WithdrawalCaps<Balance>, Balance)>;
+
+        /// Returns the withdrawals of the account in the `page`, the newest ones are in the first page.
+        fn withdrawal_list_of(who: AccountId, page: u32) -> BTreeMap<WithdrawalRecordId, Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        /// Returns the pending or historical withdrawal with given id.
+        fn withdrawal_record(id: WithdrawalRecordId) -> Option<Withdrawal<AccountId, Balance, BlockNumber>>;
+
+        /// Returns the processed transaction `txid` on the external `chain` with its deposits.
+        fn tx_record(chain: Chain, txid: Vec<u8>) -> Option<TxRecord<AccountId, Balance, BlockNumber>>;
     }
 }
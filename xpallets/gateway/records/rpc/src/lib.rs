@@ -11,13 +11,14 @@ use serde::{Deserialize, Serialize};
 
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 use xp_rpc::{runtime_error_into_rpc_err, Result};
 
 use xpallet_gateway_records_rpc_runtime_api::{
-    AssetId, Chain, Withdrawal, WithdrawalRecordId, WithdrawalState,
-    XGatewayRecordsApi as GatewayRecordsRuntimeApi,
+    AssetId, Chain, DepositRecord, TxRecord, Withdrawal, WithdrawalCaps, WithdrawalFeeMode,
+    WithdrawalRecordId, WithdrawalState, XGatewayRecordsApi as GatewayRecordsRuntimeApi,
 };
 
 pub struct XGatewayRecords<C, B> {
@@ -62,6 +63,40 @@ where
         chain: Chain,
         at: Option<BlockHash>,
     ) -> Result<BTreeMap<WithdrawalRecordId, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the withdrawal caps of an asset and its outflow in the current window
+    #[rpc(name = "xgatewayrecords_withdrawalCaps")]
+    fn withdrawal_caps(
+        &self,
+        asset_id: AssetId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcWithdrawalCaps<Balance>>>;
+
+    /// Return the withdrawals of an account in the page (starting from 0), the newest ones are in the first page
+    #[rpc(name = "xgatewayrecords_withdrawalListOf")]
+    fn withdrawal_list_of(
+        &self,
+        who: AccountId,
+        page: u32,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<WithdrawalRecordId, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the pending or historical withdrawal with given id
+    #[rpc(name = "xgatewayrecords_withdrawalRecord")]
+    fn withdrawal_record(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>>;
+
+    /// Return the processed transaction `txid` on the external `chain` with its deposits
+    #[rpc(name = "xgatewayrecords_txRecord")]
+    fn tx_record(
+        &self,
+        chain: Chain,
+        txid: Bytes,
+        at: Option<BlockHash>,
+    ) -> Result<Option<RpcTxRecord<AccountId, Balance, BlockNumber>>>;
 }
 
 impl<C, Block, AccountId, Balance, BlockNumber>
@@ -117,7 +152,7 @@ where
             .map(|map| {
                 map.into_iter()
                     .filter_map(|(id, withdrawal)| {
-                        if withdrawal.state == WithdrawalState::Applying {
+                        if withdrawal.state == WithdrawalState::Applying && !withdrawal.delayed {
                             Some((id, withdrawal.into()))
                         } else {
                             None
@@ -127,6 +162,60 @@ where
             })
             .map_err(runtime_error_into_rpc_err)
     }
+
+    fn withdrawal_caps(
+        &self,
+        asset_id: AssetId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcWithdrawalCaps<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.withdrawal_caps(&at, asset_id)
+            .map(|caps| caps.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn withdrawal_list_of(
+        &self,
+        who: AccountId,
+        page: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<u32, RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.withdrawal_list_of(&at, who, page)
+            .map(|map| {
+                map.into_iter()
+                    .map(|(id, withdrawal)| (id, withdrawal.into()))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn withdrawal_record(
+        &self,
+        id: WithdrawalRecordId,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcWithdrawalRecord<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.withdrawal_record(&at, id)
+            .map(|withdrawal| withdrawal.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
+
+    fn tx_record(
+        &self,
+        chain: Chain,
+        txid: Bytes,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<RpcTxRecord<AccountId, Balance, BlockNumber>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        api.tx_record(&at, chain, txid.to_vec())
+            .map(|record| record.map(Into::into))
+            .map_err(runtime_error_into_rpc_err)
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
@@ -139,7 +228,13 @@ pub struct RpcWithdrawalRecord<AccountId, Balance: Display + FromStr, BlockNumbe
     pub addr: String,
     pub ext: String,
     pub height: BlockNumber,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub fee: Balance,
+    pub fee_mode: WithdrawalFeeMode,
     pub state: WithdrawalState,
+    pub delayed: bool,
+    pub finished_at: Option<BlockNumber>,
+    pub tx_hash: Option<Bytes>,
 }
 
 impl<AccountId, Balance: Display + FromStr, BlockNumber>
@@ -154,7 +249,76 @@ impl<AccountId, Balance: Display + FromStr, BlockNumber>
             addr: String::from_utf8_lossy(record.addr.as_ref()).into_owned(),
             ext: String::from_utf8_lossy(record.ext.as_ref()).into_owned(),
             height: record.height,
+            fee: record.fee.amount,
+            fee_mode: record.fee.mode,
             state: record.state,
+            delayed: record.delayed,
+            finished_at: record.finished_at,
+            tx_hash: record.tx_hash.map(Into::into),
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcWithdrawalCaps<Balance: Display + FromStr> {
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub total: Balance,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub per_account: Balance,
+    /// The withdrawal of the asset in the current window.
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub outflow: Balance,
+}
+
+impl<Balance: Display + FromStr> From<(WithdrawalCaps<Balance>, Balance)>
+    for RpcWithdrawalCaps<Balance>
+{
+    fn from((caps, outflow): (WithdrawalCaps<Balance>, Balance)) -> Self {
+        Self {
+            total: caps.total,
+            per_account: caps.per_account,
+            outflow,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcDepositRecord<AccountId, Balance: Display + FromStr> {
+    pub asset_id: AssetId,
+    pub beneficiary: AccountId,
+    #[serde(with = "xp_rpc::serde_num_str")]
+    pub balance: Balance,
+}
+
+impl<AccountId, Balance: Display + FromStr> From<DepositRecord<AccountId, Balance>>
+    for RpcDepositRecord<AccountId, Balance>
+{
+    fn from(record: DepositRecord<AccountId, Balance>) -> Self {
+        Self {
+            asset_id: record.asset_id,
+            beneficiary: record.beneficiary,
+            balance: record.balance,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTxRecord<AccountId, Balance: Display + FromStr, BlockNumber> {
+    pub height: BlockNumber,
+    pub deposits: Vec<RpcDepositRecord<AccountId, Balance>>,
+}
+
+impl<AccountId, Balance: Display + FromStr, BlockNumber>
+    From<TxRecord<AccountId, Balance, BlockNumber>>
+    for RpcTxRecord<AccountId, Balance, BlockNumber>
+{
+    fn from(record: TxRecord<AccountId, Balance, BlockNumber>) -> Self {
+        Self {
+            height: record.height,
+            deposits: record.deposits.into_iter().map(Into::into).collect(),
         }
     }
 }
@@ -1,6 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_support::storage::StorageMap;
 use frame_system::RawOrigin;
 
 use super::*;
@@ -89,6 +90,27 @@ benchmarks! {
     verify {
         assert_eq!(XGatewayRecords::<T>::state_of(0), None);
     }
+
+    set_withdrawal_caps {
+        let caps = WithdrawalCaps {
+            total: 100_000.into(),
+            per_account: 1000.into(),
+        };
+    }: _(RawOrigin::Root, ASSET_ID, Some(caps))
+    verify {
+        assert_eq!(XGatewayRecords::<T>::withdrawal_caps_of(ASSET_ID), Some(caps));
+    }
+
+    approve_withdrawal {
+        let receiver: T::AccountId = whitelisted_caller();
+        WithdrawalCapsOf::<T>::insert(ASSET_ID, WithdrawalCaps::default());
+        let amount: BalanceOf<T> = 1000.into();
+        deposit_and_withdraw::<T>(receiver, amount);
+        assert!(XGatewayRecords::<T>::is_delayed(0));
+    }: _(RawOrigin::Root, 0)
+    verify {
+        assert!(!XGatewayRecords::<T>::is_delayed(0));
+    }
 }
 
 #[cfg(test)]
@@ -103,6 +125,8 @@ mod tests {
             assert_ok!(test_benchmark_root_deposit::<Test>());
             assert_ok!(test_benchmark_root_withdraw::<Test>());
             assert_ok!(test_benchmark_set_withdrawal_state::<Test>());
+            assert_ok!(test_benchmark_set_withdrawal_caps::<Test>());
+            assert_ok!(test_benchmark_approve_withdrawal::<Test>());
         });
     }
 }
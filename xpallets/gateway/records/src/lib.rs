@@ -17,10 +17,13 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
     dispatch::{DispatchError, DispatchResult},
-    ensure, IterableStorageMap,
+    ensure,
+    traits::{BalanceStatus, Get, ReservableCurrency},
+    weights::Weight,
+    IterableStorageMap,
 };
 use frame_system::ensure_root;
-use sp_runtime::traits::StaticLookup;
+use sp_runtime::traits::{One, Saturating, StaticLookup, Zero};
 
 use orml_utilities::with_transaction_result;
 
@@ -28,9 +31,14 @@ use chainx_primitives::{AddrStr, AssetId};
 use xp_logging::{error, info};
 use xp_runtime::Memo;
 use xpallet_assets::{AssetType, BalanceOf, Chain};
-use xpallet_support::try_addr;
+use xpallet_support::{traits::TreasuryAccount, try_addr};
 
-pub use self::types::{Withdrawal, WithdrawalRecord, WithdrawalRecordId, WithdrawalState};
+use self::types::WithdrawalRecordV1;
+pub use self::types::{
+    DepositRecord, Releases, TxRecord, Withdrawal, WithdrawalCaps, WithdrawalFee,
+    WithdrawalFeeMode, WithdrawalHistory, WithdrawalOutflow, WithdrawalRecord, WithdrawalRecordId,
+    WithdrawalState,
+};
 pub use self::weights::WeightInfo;
 
 pub type WithdrawalRecordOf<T> = WithdrawalRecord<
@@ -39,6 +47,56 @@ pub type WithdrawalRecordOf<T> = WithdrawalRecord<
     <T as frame_system::Trait>::BlockNumber,
 >;
 
+pub type TxRecordOf<T> = TxRecord<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type WithdrawalHistoryOf<T> = WithdrawalHistory<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+
+pub type WithdrawalOf<T> = Withdrawal<
+    <T as frame_system::Trait>::AccountId,
+    BalanceOf<T>,
+    <T as frame_system::Trait>::BlockNumber,
+>;
+
+/// The number of withdrawals in a page of `withdrawal_list_of`.
+pub const WITHDRAWAL_PAGE_SIZE: u32 = 20;
+
+/// The number of slots the window of the withdrawal caps is divided into.
+pub const OUTFLOW_SLOTS: u32 = 24;
+
+pub type WithdrawalOutflowOf<T> =
+    WithdrawalOutflow<BalanceOf<T>, <T as frame_system::Trait>::BlockNumber>;
+
+/// Trait for getting the withdrawal fee of an asset.
+pub trait WithdrawalFeeProvider<Balance> {
+    /// Returns the fee of withdrawing `value` of the asset, paid in the way of `mode`.
+    fn withdrawal_fee(
+        asset_id: AssetId,
+        value: Balance,
+        mode: WithdrawalFeeMode,
+    ) -> Result<WithdrawalFee<Balance>, DispatchError>;
+}
+
+impl<Balance: Default> WithdrawalFeeProvider<Balance> for () {
+    fn withdrawal_fee(
+        _asset_id: AssetId,
+        _value: Balance,
+        mode: WithdrawalFeeMode,
+    ) -> Result<WithdrawalFee<Balance>, DispatchError> {
+        Ok(WithdrawalFee {
+            amount: Default::default(),
+            mode,
+        })
+    }
+}
+
 /// The module's config trait.
 ///
 /// `frame_system::Trait` should always be included in our implied traits.
@@ -46,6 +104,15 @@ pub trait Trait: xpallet_assets::Trait {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
+    /// The length of the window in which the withdrawal caps are applied, e.g. one day.
+    type WithdrawalWindow: Get<Self::BlockNumber>;
+
+    /// The maximum number of withdrawals kept in the history of an account.
+    type MaxWithdrawalHistory: Get<u32>;
+
+    /// The provider of the withdrawal fee schedule of each asset.
+    type WithdrawalFeeProvider: WithdrawalFeeProvider<BalanceOf<Self>>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -65,6 +132,12 @@ decl_error! {
         InvalidState,
         /// Meet unexpected chain
         UnexpectedChain,
+        /// The withdrawal exceeds the caps and waits for the approval
+        WithdrawalDelayed,
+        /// The withdrawal is not delayed
+        NotDelayed,
+        /// The delayed or approved withdrawal can not be amended
+        NotAmendable,
     }
 }
 
@@ -74,12 +147,15 @@ decl_event!(
     where
         <T as frame_system::Trait>::AccountId,
         Balance = BalanceOf<T>,
-        WithdrawalRecord = WithdrawalRecordOf<T>
+        WithdrawalRecord = WithdrawalRecordOf<T>,
+        WithdrawalCaps = WithdrawalCaps<BalanceOf<T>>
     {
         /// An account deposited some asset. [who, asset_id, amount]
         Deposited(AccountId, AssetId, Balance),
         /// A withdrawal application was created. [withdrawal_id, record_info]
         WithdrawalCreated(WithdrawalRecordId, WithdrawalRecord),
+        /// The address and memo of a withdrawal application were amended. [withdrawal_id, record_info]
+        WithdrawalAmended(WithdrawalRecordId, WithdrawalRecord),
         /// A withdrawal proposal was processed. [withdrawal_id]
         WithdrawalProcessed(WithdrawalRecordId),
         /// A withdrawal proposal was recovered. [withdrawal_id]
@@ -88,6 +164,12 @@ decl_event!(
         WithdrawalCanceled(WithdrawalRecordId, WithdrawalState),
         /// A withdrawal proposal was finished successfully. [withdrawal_id, withdrawal_state]
         WithdrawalFinished(WithdrawalRecordId, WithdrawalState),
+        /// A withdrawal exceeded the caps and was delayed. [withdrawal_id]
+        WithdrawalDelayed(WithdrawalRecordId),
+        /// A delayed withdrawal was approved. [withdrawal_id]
+        WithdrawalApproved(WithdrawalRecordId),
+        /// The withdrawal caps of an asset were set. [asset_id, caps]
+        WithdrawalCapsSet(AssetId, Option<WithdrawalCaps>),
     }
 );
 
@@ -103,6 +185,54 @@ decl_storage! {
 
         /// The id of next withdrawal record.
         pub NextWithdrawalRecordId get(fn id): WithdrawalRecordId = 0;
+
+        /// The withdrawal caps of the corresponding asset.
+        pub WithdrawalCapsOf get(fn withdrawal_caps_of):
+            map hasher(twox_64_concat) AssetId => Option<WithdrawalCaps<BalanceOf<T>>>;
+
+        /// The withdrawal of the corresponding asset in the rolling window.
+        pub AssetOutflowOf get(fn asset_outflow_of):
+            map hasher(twox_64_concat) AssetId => WithdrawalOutflowOf<T>;
+
+        /// The withdrawal of the corresponding account and asset in the rolling window.
+        pub AccountOutflowOf get(fn account_outflow_of):
+            double_map hasher(blake2_128_concat) T::AccountId, hasher(twox_64_concat) AssetId
+            => WithdrawalOutflowOf<T>;
+
+        /// The block at which the withdrawal was counted in the outflow, the outflow is
+        /// reverted if the withdrawal is canceled.
+        pub WithdrawalOutflowAt get(fn withdrawal_outflow_at):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<T::BlockNumber>;
+
+        /// The withdrawals which exceed the caps, they are kept `Applying` until approved.
+        pub DelayedWithdrawals get(fn is_delayed):
+            map hasher(twox_64_concat) WithdrawalRecordId => bool;
+
+        /// The delayed withdrawals which have been approved.
+        pub ApprovedWithdrawals get(fn is_approved):
+            map hasher(twox_64_concat) WithdrawalRecordId => bool;
+
+        /// The recent withdrawals of the corresponding account, from the oldest to the newest.
+        ///
+        /// The number of withdrawals is bounded by `MaxWithdrawalHistory`.
+        pub AccountWithdrawals get(fn account_withdrawals):
+            map hasher(blake2_128_concat) T::AccountId => Vec<WithdrawalRecordId>;
+
+        /// The withdrawals which have reached the final state, only the ones
+        /// indexed by `AccountWithdrawals` are kept.
+        pub WithdrawalHistoryOf get(fn withdrawal_history_of):
+            map hasher(twox_64_concat) WithdrawalRecordId => Option<WithdrawalHistoryOf<T>>;
+
+        /// The transactions on the external chains which have been processed successfully,
+        /// which is the replay guard shared by all the gateways.
+        pub TxRecords get(fn tx_record):
+            double_map hasher(twox_64_concat) Chain, hasher(blake2_128_concat) Vec<u8>
+            => Option<TxRecordOf<T>>;
+
+        /// Storage version of the pallet.
+        ///
+        /// This is set to v2.0.0 for new networks.
+        pub StorageVersion get(fn storage_version) build(|_: &GenesisConfig| Releases::V2_0_0): Releases;
     }
 }
 
@@ -112,6 +242,10 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_withdrawal_fee()
+        }
+
         /// Deposit asset token.
         ///
         /// This is a root-only operation.
@@ -129,6 +263,8 @@ decl_module! {
 
         /// Withdraw asset token (only lock token)
         ///
+        /// The withdrawal fee of the asset is deducted from `balance`.
+        ///
         /// This is a root-only operation.
         #[weight = <T as Trait>::WeightInfo::root_withdraw()]
         fn root_withdraw(
@@ -141,7 +277,12 @@ decl_module! {
         ) -> DispatchResult {
             ensure_root(origin)?;
             let who = T::Lookup::lookup(who)?;
-            Self::withdraw(&who, asset_id, balance, addr, memo)
+            let fee = T::WithdrawalFeeProvider::withdrawal_fee(
+                asset_id,
+                balance,
+                WithdrawalFeeMode::Deducted,
+            )?;
+            Self::withdraw(&who, asset_id, balance, addr, memo, fee)
         }
 
         /// Set the state of withdrawal record with given id and state.
@@ -171,6 +312,34 @@ decl_module! {
             }
             Ok(())
         }
+
+        /// Set the withdrawal caps of an asset, remove them if `caps` is `None`.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::set_withdrawal_caps()]
+        pub fn set_withdrawal_caps(
+            origin,
+            #[compact] asset_id: AssetId,
+            caps: Option<WithdrawalCaps<BalanceOf<T>>>
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            match caps {
+                Some(caps) => WithdrawalCapsOf::<T>::insert(asset_id, caps),
+                None => WithdrawalCapsOf::<T>::remove(asset_id),
+            }
+            Self::deposit_event(Event::<T>::WithdrawalCapsSet(asset_id, caps));
+            Ok(())
+        }
+
+        /// Approve the delayed withdrawal with given id.
+        ///
+        /// This is a root-only operation.
+        #[weight = <T as Trait>::WeightInfo::approve_withdrawal()]
+        pub fn approve_withdrawal(origin, #[compact] withdrawal_id: WithdrawalRecordId) -> DispatchResult {
+            ensure_root(origin)?;
+            let (record, _) = Self::ensure_withdrawal_records_exists(withdrawal_id)?;
+            Self::approve_withdrawal_impl(withdrawal_id, &record)
+        }
     }
 }
 
@@ -220,37 +389,91 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Deposit the asset token transferred by the transaction `txid` on the external `chain`.
+    ///
+    /// The deposit is recorded to the transaction, which is marked as processed as well.
+    /// The gateway must reject the processed transactions via `is_tx_processed` beforehand.
+    pub fn deposit_by_tx(
+        chain: Chain,
+        txid: Vec<u8>,
+        who: &T::AccountId,
+        asset_id: AssetId,
+        balance: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::deposit(who, asset_id, balance)?;
+
+        TxRecords::<T>::mutate(chain, txid, |record| {
+            Self::new_tx_record(record).deposits.push(DepositRecord {
+                asset_id,
+                beneficiary: who.clone(),
+                balance,
+            })
+        });
+        Ok(())
+    }
+
+    /// Marks the transaction `txid` on the external `chain` as processed, for the
+    /// transactions without any deposit.
+    pub fn record_processed_tx(chain: Chain, txid: Vec<u8>) {
+        TxRecords::<T>::mutate(chain, txid, |record| {
+            Self::new_tx_record(record);
+        });
+    }
+
+    /// Returns true if the transaction `txid` on the external `chain` has been processed.
+    pub fn is_tx_processed(chain: Chain, txid: &[u8]) -> bool {
+        TxRecords::<T>::contains_key(chain, txid)
+    }
+
+    fn new_tx_record(record: &mut Option<TxRecordOf<T>>) -> &mut TxRecordOf<T> {
+        record.get_or_insert_with(|| TxRecord {
+            height: frame_system::Module::<T>::block_number(),
+            deposits: Vec::new(),
+        })
+    }
+
     /// Withdrawal asset (lock asset token firstly, follow-up operations are required).
     ///
     /// WithdrawalRecord State: `Applying`
     ///
     /// NOTE: this function has included withdrawal_init and withdrawal_locking.
+    /// The `Native` fee is reserved from the PCX of the applicant until the withdrawal is finished.
     pub fn withdraw(
         who: &T::AccountId,
         asset_id: AssetId,
         balance: BalanceOf<T>,
         addr: AddrStr,
         ext: Memo,
+        fee: WithdrawalFee<BalanceOf<T>>,
     ) -> DispatchResult {
         xpallet_assets::Module::<T>::ensure_not_native_asset(&asset_id)?;
+        xpallet_assets::Module::<T>::ensure_not_frozen(&asset_id, who)?;
         Self::ensure_withdrawal_available_balance(who, asset_id, balance)?;
 
         let id = Self::id();
         info!(
-            "[apply_withdrawal] id:{}, who:{:?}, asset id:{}, balance:{:?}, addr:{:?}, memo:{}",
+            "[apply_withdrawal] id:{}, who:{:?}, asset id:{}, balance:{:?}, addr:{:?}, memo:{}, fee:{:?}",
             id,
             who,
             asset_id,
             balance,
             try_addr(&addr),
-            ext
+            ext,
+            fee
         );
         let height = frame_system::Module::<T>::block_number();
         let record =
-            WithdrawalRecordOf::<T>::new(who.clone(), asset_id, balance, addr, ext, height);
+            WithdrawalRecordOf::<T>::new(who.clone(), asset_id, balance, addr, ext, height, fee);
 
-        // Lock usable asset token
-        Self::lock(record.applicant(), record.asset_id(), record.balance())?;
+        with_transaction_result(|| {
+            // Lock usable asset token
+            Self::lock(record.applicant(), record.asset_id(), record.balance())?;
+            // Reserve the native fee
+            if fee.mode == WithdrawalFeeMode::Native {
+                <T as xpallet_assets::Trait>::Currency::reserve(record.applicant(), fee.amount)?;
+            }
+            Ok(())
+        })?;
 
         // Set storages
         PendingWithdrawals::<T>::insert(id, record.clone());
@@ -258,7 +481,19 @@ impl<T: Trait> Module<T> {
         let next_id = id.checked_add(1_u32).unwrap_or(0);
         NextWithdrawalRecordId::put(next_id);
 
+        Self::index_withdrawal(who, id);
         Self::deposit_event(Event::<T>::WithdrawalCreated(id, record));
+
+        if Self::within_withdrawal_caps(who, asset_id, balance) {
+            Self::record_outflow(id, who, asset_id, balance);
+        } else {
+            info!(
+                "[apply_withdrawal] id:{} exceeds the withdrawal caps of asset {}, delayed",
+                id, asset_id
+            );
+            DelayedWithdrawals::insert(id, true);
+            Self::deposit_event(Event::<T>::WithdrawalDelayed(id));
+        }
         Ok(())
     }
 
@@ -268,6 +503,13 @@ impl<T: Trait> Module<T> {
     pub fn process_withdrawal(id: WithdrawalRecordId, chain: Chain) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
+        if Self::is_delayed(id) {
+            error!(
+                "[process_withdrawal] id:{}, withdrawal is delayed and not approved",
+                id
+            );
+            return Err(Error::<T>::WithdrawalDelayed.into());
+        }
         Self::process_withdrawal_impl(id, curr_state)
     }
 
@@ -340,6 +582,55 @@ impl<T: Trait> Module<T> {
         Self::cancel_withdrawal_impl(id, record, curr_state, WithdrawalState::NormalCancel)
     }
 
+    /// Amend the address and memo of the withdrawal by the applicant.
+    ///
+    /// WithdrawalRecord State: `Applying`
+    ///
+    /// NOTE: the withdrawal keeps its id, so that its position in the queue is not changed.
+    pub fn amend_withdrawal(
+        id: WithdrawalRecordId,
+        who: &T::AccountId,
+        addr: AddrStr,
+        ext: Memo,
+    ) -> DispatchResult {
+        let (mut record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
+        if record.applicant() != who {
+            error!(
+                "[amend_withdrawal] id:{}, account {:?} is not the applicant {:?}",
+                id,
+                who,
+                record.applicant()
+            );
+            return Err(Error::<T>::InvalidAccount.into());
+        }
+        if curr_state != WithdrawalState::Applying {
+            error!(
+                "[amend_withdrawal] id:{}, current withdrawal state ({:?}) must be `Applying`",
+                id, curr_state
+            );
+            return Err(Error::<T>::NotApplyingState.into());
+        }
+        // The approval is given to the original address.
+        if Self::is_delayed(id) || Self::is_approved(id) {
+            error!(
+                "[amend_withdrawal] id:{}, the delayed or approved withdrawal can not be amended",
+                id
+            );
+            return Err(Error::<T>::NotAmendable.into());
+        }
+
+        info!(
+            "[amend_withdrawal] id:{}, addr:{:?}, memo:{}",
+            id,
+            try_addr(&addr),
+            ext
+        );
+        record.amend(addr, ext);
+        PendingWithdrawals::<T>::insert(id, &record);
+        Self::deposit_event(Event::<T>::WithdrawalAmended(id, record));
+        Ok(())
+    }
+
     fn cancel_withdrawal_impl(
         id: WithdrawalRecordId,
         record: WithdrawalRecordOf<T>,
@@ -356,10 +647,15 @@ impl<T: Trait> Module<T> {
 
         // Unlock reserved asset
         Self::unlock(record.applicant(), record.asset_id(), record.balance())?;
+        Self::unreserve_native_fee(&record);
 
         // Remove storage
+        Self::revert_outflow(id, &record);
         PendingWithdrawals::<T>::remove(id);
         WithdrawalStateOf::remove(id);
+        DelayedWithdrawals::remove(id);
+        ApprovedWithdrawals::remove(id);
+        Self::archive_withdrawal(id, record, new_state, None);
 
         Self::deposit_event(Event::<T>::WithdrawalCanceled(id, new_state));
         Ok(())
@@ -378,15 +674,25 @@ impl<T: Trait> Module<T> {
     /// valid when trustees submit withdrawal info, so that just release it directly.
     /// ethereum released reserved by trustees submit release request directly, so that we should check
     /// whether the withdrawal belongs to Ethereum Chain, in case release other chain withdraw.
+    ///
+    /// `tx_hash` is the hash of the transaction which settled the withdrawal on the target chain,
+    /// it's recorded in the withdrawal history.
     pub fn finish_withdrawal(
         id: WithdrawalRecordId,
         expected_chain: Option<Chain>,
+        tx_hash: Option<Vec<u8>>,
     ) -> DispatchResult {
         let (record, curr_state) = Self::ensure_withdrawal_records_exists(id)?;
         if let Some(chain) = expected_chain {
             Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
         }
-        Self::finish_withdrawal_impl(id, record, curr_state, WithdrawalState::NormalFinish)
+        Self::finish_withdrawal_impl(
+            id,
+            record,
+            curr_state,
+            WithdrawalState::NormalFinish,
+            tx_hash,
+        )
     }
 
     fn finish_withdrawal_impl(
@@ -394,6 +700,7 @@ impl<T: Trait> Module<T> {
         record: WithdrawalRecordOf<T>,
         curr_state: WithdrawalState,
         new_state: WithdrawalState,
+        tx_hash: Option<Vec<u8>>,
     ) -> DispatchResult {
         if curr_state != WithdrawalState::Processing {
             error!(
@@ -405,10 +712,15 @@ impl<T: Trait> Module<T> {
 
         // Destroy locked asset
         Self::destroy(record.applicant(), record.asset_id(), record.balance())?;
+        Self::charge_native_fee(&record)?;
 
         // Remove storage
         PendingWithdrawals::<T>::remove(id);
         WithdrawalStateOf::remove(id);
+        DelayedWithdrawals::remove(id);
+        ApprovedWithdrawals::remove(id);
+        WithdrawalOutflowAt::<T>::remove(id);
+        Self::archive_withdrawal(id, record, new_state, tx_hash);
 
         Self::deposit_event(Event::<T>::WithdrawalFinished(id, new_state));
         Ok(())
@@ -421,7 +733,7 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         with_transaction_result(|| {
             for id in ids {
-                Self::finish_withdrawal(*id, expected_chain)?;
+                Self::finish_withdrawal(*id, expected_chain, None)?;
             }
             Ok(())
         })
@@ -436,7 +748,10 @@ impl<T: Trait> Module<T> {
             (curr, new) if curr == new => Ok(()),
             (WithdrawalState::Applying, WithdrawalState::Processing) => {
                 // State: `Applying` ==> `Processing`
-                Self::process_withdrawal_impl(id, curr_state)
+                with_transaction_result(|| {
+                    Self::approve_delayed_withdrawal(id, &record)?;
+                    Self::process_withdrawal_impl(id, curr_state)
+                })
             }
             (WithdrawalState::Processing, WithdrawalState::Applying) => {
                 // State: `Processing` ==> `Applying`
@@ -450,14 +765,17 @@ impl<T: Trait> Module<T> {
             (WithdrawalState::Applying, WithdrawalState::NormalFinish)
             | (WithdrawalState::Applying, WithdrawalState::RootFinish) => {
                 // State: `Applying` ==> `Processing` ==> `NormalFinish`|`RootFinish`
-                Self::process_withdrawal_impl(id, curr_state)?;
-                let curr_state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
-                Self::finish_withdrawal_impl(id, record, curr_state, new_state)
+                with_transaction_result(|| {
+                    Self::approve_delayed_withdrawal(id, &record)?;
+                    Self::process_withdrawal_impl(id, curr_state)?;
+                    let curr_state = Self::state_of(id).ok_or(Error::<T>::NotExisted)?;
+                    Self::finish_withdrawal_impl(id, record, curr_state, new_state, None)
+                })
             }
             (WithdrawalState::Processing, WithdrawalState::NormalFinish)
             | (WithdrawalState::Processing, WithdrawalState::RootFinish) => {
                 // State: `Processing` ==> `NormalFinish`|`RootFinish`
-                Self::finish_withdrawal_impl(id, record, curr_state, new_state)
+                Self::finish_withdrawal_impl(id, record, curr_state, new_state, None)
             }
             _ => {
                 error!(
@@ -498,6 +816,143 @@ impl<T: Trait> Module<T> {
         Self::set_withdrawal_state(frame_system::RawOrigin::Root.into(), id, new_state)
     }
 
+    /// Approve the delayed withdrawal by the trustees of the chain.
+    pub fn approve_withdrawal_by_trustees(id: WithdrawalRecordId, chain: Chain) -> DispatchResult {
+        let (record, _) = Self::ensure_withdrawal_records_exists(id)?;
+        Self::ensure_asset_belongs_to_chain(record.asset_id(), chain)?;
+        Self::approve_withdrawal_impl(id, &record)
+    }
+
+    /// The approved withdrawal is counted in the outflow even if it exceeds the caps.
+    fn approve_withdrawal_impl(
+        id: WithdrawalRecordId,
+        record: &WithdrawalRecordOf<T>,
+    ) -> DispatchResult {
+        ensure!(Self::is_delayed(id), Error::<T>::NotDelayed);
+        DelayedWithdrawals::remove(id);
+        ApprovedWithdrawals::insert(id, true);
+        Self::record_outflow(id, record.applicant(), record.asset_id(), record.balance());
+        Self::deposit_event(Event::<T>::WithdrawalApproved(id));
+        Ok(())
+    }
+
+    /// The delayed withdrawal which is moved forward by root is approved first, so that
+    /// it's counted in the outflow of the withdrawal caps.
+    fn approve_delayed_withdrawal(
+        id: WithdrawalRecordId,
+        record: &WithdrawalRecordOf<T>,
+    ) -> DispatchResult {
+        if Self::is_delayed(id) {
+            Self::approve_withdrawal_impl(id, record)?;
+        }
+        Ok(())
+    }
+
+    /// Add the withdrawal to the history of the account, the oldest one is dropped
+    /// if the history is full.
+    fn index_withdrawal(who: &T::AccountId, id: WithdrawalRecordId) {
+        let max = T::MaxWithdrawalHistory::get() as usize;
+        AccountWithdrawals::<T>::mutate(who, |ids| {
+            ids.push(id);
+            if ids.len() > max {
+                for pruned in ids.drain(..ids.len() - max) {
+                    WithdrawalHistoryOf::<T>::remove(pruned);
+                }
+            }
+        });
+    }
+
+    /// Keep the withdrawal which has reached the final state if it's still indexed.
+    fn archive_withdrawal(
+        id: WithdrawalRecordId,
+        record: WithdrawalRecordOf<T>,
+        state: WithdrawalState,
+        tx_hash: Option<Vec<u8>>,
+    ) {
+        if !Self::account_withdrawals(record.applicant()).contains(&id) {
+            return;
+        }
+        let history = WithdrawalHistory {
+            record,
+            state,
+            finished_at: frame_system::Module::<T>::block_number(),
+            tx_hash,
+        };
+        WithdrawalHistoryOf::<T>::insert(id, history);
+    }
+
+    /// Returns the start of the slot of the withdrawal window which contains `height`.
+    ///
+    /// The window is divided into `OUTFLOW_SLOTS` slots, the outflow of a slot leaves the
+    /// window once the whole window has passed since the slot started.
+    fn outflow_slot_of(height: T::BlockNumber) -> T::BlockNumber {
+        let slot_length = (T::WithdrawalWindow::get() / OUTFLOW_SLOTS.into()).max(One::one());
+        height - height % slot_length
+    }
+
+    /// Returns the outflow of the rolling window, the slots which have left the window are dropped.
+    fn current_outflow(mut outflow: WithdrawalOutflowOf<T>) -> WithdrawalOutflowOf<T> {
+        let now = frame_system::Module::<T>::block_number();
+        let window = T::WithdrawalWindow::get();
+        outflow
+            .slots
+            .retain(|(start, _)| now < start.saturating_add(window));
+        outflow
+    }
+
+    /// Returns `false` if the withdrawal exceeds the caps of the asset.
+    fn within_withdrawal_caps(who: &T::AccountId, asset_id: AssetId, value: BalanceOf<T>) -> bool {
+        let caps = match Self::withdrawal_caps_of(asset_id) {
+            Some(caps) => caps,
+            None => return true,
+        };
+        let asset_outflow = Self::current_outflow(Self::asset_outflow_of(asset_id)).amount();
+        let account_outflow =
+            Self::current_outflow(Self::account_outflow_of(who, asset_id)).amount();
+        asset_outflow.saturating_add(value) <= caps.total
+            && account_outflow.saturating_add(value) <= caps.per_account
+    }
+
+    /// Count the withdrawal in the outflow of the asset and the account.
+    ///
+    /// The outflow is only tracked for the assets with withdrawal caps, the delayed
+    /// withdrawals are not counted until approved.
+    fn record_outflow(
+        id: WithdrawalRecordId,
+        who: &T::AccountId,
+        asset_id: AssetId,
+        value: BalanceOf<T>,
+    ) {
+        if Self::withdrawal_caps_of(asset_id).is_none() {
+            return;
+        }
+        let now = frame_system::Module::<T>::block_number();
+        let slot = Self::outflow_slot_of(now);
+        let mut asset_outflow = Self::current_outflow(Self::asset_outflow_of(asset_id));
+        let mut account_outflow = Self::current_outflow(Self::account_outflow_of(who, asset_id));
+        asset_outflow.add(slot, value);
+        account_outflow.add(slot, value);
+        AssetOutflowOf::<T>::insert(asset_id, asset_outflow);
+        AccountOutflowOf::<T>::insert(who, asset_id, account_outflow);
+        WithdrawalOutflowAt::<T>::insert(id, now);
+    }
+
+    /// Revert the outflow of the canceled withdrawal if it was counted.
+    fn revert_outflow(id: WithdrawalRecordId, record: &WithdrawalRecordOf<T>) {
+        let at = match WithdrawalOutflowAt::<T>::take(id) {
+            Some(at) => at,
+            None => return,
+        };
+        let slot = Self::outflow_slot_of(at);
+        let (who, asset_id) = (record.applicant(), record.asset_id());
+        let mut asset_outflow = Self::current_outflow(Self::asset_outflow_of(asset_id));
+        let mut account_outflow = Self::current_outflow(Self::account_outflow_of(who, asset_id));
+        asset_outflow.sub(slot, record.balance());
+        account_outflow.sub(slot, record.balance());
+        AssetOutflowOf::<T>::insert(asset_id, asset_outflow);
+        AccountOutflowOf::<T>::insert(who, asset_id, account_outflow);
+    }
+
     fn lock(who: &T::AccountId, asset_id: AssetId, value: BalanceOf<T>) -> DispatchResult {
         xpallet_assets::Module::<T>::move_balance(
             &asset_id,
@@ -528,16 +983,77 @@ impl<T: Trait> Module<T> {
         xpallet_assets::Module::<T>::destroy_reserved_withdrawal(&asset_id, &who, value)?;
         Ok(())
     }
+
+    fn unreserve_native_fee(record: &WithdrawalRecordOf<T>) {
+        let fee = record.fee();
+        if fee.mode == WithdrawalFeeMode::Native {
+            <T as xpallet_assets::Trait>::Currency::unreserve(record.applicant(), fee.amount);
+        }
+    }
+
+    /// Move the reserved native fee to the treasury.
+    fn charge_native_fee(record: &WithdrawalRecordOf<T>) -> DispatchResult {
+        let fee = record.fee();
+        if fee.mode == WithdrawalFeeMode::Native && !fee.amount.is_zero() {
+            let treasury_account =
+                <T as xpallet_assets::Trait>::TreasuryAccount::treasury_account();
+            <T as xpallet_assets::Trait>::Currency::repatriate_reserved(
+                record.applicant(),
+                &treasury_account,
+                fee.amount,
+                BalanceStatus::Free,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Migrate the pending withdrawal records to `Releases::V2_0_0`.
+    ///
+    /// The fee of the records created before is deducted from the withdrawal,
+    /// which is given by `WithdrawalFeeProvider`.
+    pub fn migrate_withdrawal_fee() -> Weight {
+        if Self::storage_version() != Releases::V1_0_0 {
+            return 0;
+        }
+        PendingWithdrawals::<T>::translate::<
+            WithdrawalRecordV1<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+            _,
+        >(|_, record| {
+            let fee = T::WithdrawalFeeProvider::withdrawal_fee(
+                record.asset_id(),
+                record.balance(),
+                WithdrawalFeeMode::Deducted,
+            )
+            .unwrap_or_else(|_| WithdrawalFee {
+                amount: Zero::zero(),
+                mode: WithdrawalFeeMode::Deducted,
+            });
+            Some(record.upgrade(fee))
+        });
+        StorageVersion::put(Releases::V2_0_0);
+
+        let count = PendingWithdrawals::<T>::iter().count() as Weight;
+        info!(
+            "[migrate_withdrawal_fee] {} pending withdrawal records migrated",
+            count
+        );
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
 }
 
 impl<T: Trait> Module<T> {
-    pub fn withdrawal_list(
-    ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+    pub fn withdrawal_list() -> BTreeMap<WithdrawalRecordId, WithdrawalOf<T>> {
         PendingWithdrawals::<T>::iter()
             .map(|(id, record)| {
                 (
                     id,
-                    Withdrawal::new(record, Self::state_of(id).unwrap_or_default()),
+                    Withdrawal::new(
+                        record,
+                        Self::state_of(id).unwrap_or_default(),
+                        Self::is_delayed(id),
+                    ),
                 )
             })
             .collect()
@@ -545,7 +1061,7 @@ impl<T: Trait> Module<T> {
 
     pub fn withdrawals_list_by_chain(
         chain: Chain,
-    ) -> BTreeMap<WithdrawalRecordId, Withdrawal<T::AccountId, BalanceOf<T>, T::BlockNumber>> {
+    ) -> BTreeMap<WithdrawalRecordId, WithdrawalOf<T>> {
         Self::withdrawal_list()
             .into_iter()
             .filter(|(_, withdrawal)| {
@@ -553,4 +1069,41 @@ impl<T: Trait> Module<T> {
             })
             .collect()
     }
+
+    /// Returns the pending or historical withdrawal with given id.
+    pub fn withdrawal_record(id: WithdrawalRecordId) -> Option<WithdrawalOf<T>> {
+        match Self::pending_withdrawals(id) {
+            Some(record) => Some(Withdrawal::new(
+                record,
+                Self::state_of(id).unwrap_or_default(),
+                Self::is_delayed(id),
+            )),
+            None => Self::withdrawal_history_of(id).map(Into::into),
+        }
+    }
+
+    /// Returns the withdrawals of the account in the `page` (starting from 0),
+    /// the newest withdrawals are in the first page.
+    pub fn withdrawal_list_of(
+        who: &T::AccountId,
+        page: u32,
+    ) -> BTreeMap<WithdrawalRecordId, WithdrawalOf<T>> {
+        Self::account_withdrawals(who)
+            .into_iter()
+            .rev()
+            .skip(page.saturating_mul(WITHDRAWAL_PAGE_SIZE) as usize)
+            .take(WITHDRAWAL_PAGE_SIZE as usize)
+            .filter_map(|id| Self::withdrawal_record(id).map(|withdrawal| (id, withdrawal)))
+            .collect()
+    }
+
+    /// Returns the withdrawal caps of the asset and its outflow in the rolling window.
+    pub fn withdrawal_caps(
+        asset_id: AssetId,
+    ) -> Option<(WithdrawalCaps<BalanceOf<T>>, BalanceOf<T>)> {
+        Self::withdrawal_caps_of(asset_id).map(|caps| {
+            let outflow = Self::current_outflow(Self::asset_outflow_of(asset_id));
+            (caps, outflow.amount())
+        })
+    }
 }
@@ -1,5 +1,7 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use std::cell::RefCell;
+
 use frame_support::{impl_outer_origin, parameter_types, sp_io, weights::Weight};
 use sp_core::H256;
 use sp_runtime::{
@@ -79,12 +81,16 @@ impl pallet_balances::Trait for Test {
 // assets
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 
 impl xpallet_assets_registrar::Trait for Test {
     type Event = ();
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = ();
+    type AssetIssuance = ();
     type WeightInfo = ();
 }
 
@@ -98,8 +104,40 @@ impl xpallet_assets::Trait for Test {
     type WeightInfo = ();
 }
 
+parameter_types! {
+    pub const WithdrawalWindow: BlockNumber = 10;
+    pub const MaxWithdrawalHistory: u32 = 3;
+}
+
+thread_local! {
+    static WITHDRAWAL_FEE: RefCell<Balance> = RefCell::new(0);
+}
+
+/// The flat withdrawal fee of all assets.
+pub struct MockWithdrawalFee;
+impl MockWithdrawalFee {
+    pub fn set(fee: Balance) {
+        WITHDRAWAL_FEE.with(|v| *v.borrow_mut() = fee);
+    }
+}
+impl WithdrawalFeeProvider<Balance> for MockWithdrawalFee {
+    fn withdrawal_fee(
+        _asset_id: AssetId,
+        _value: Balance,
+        mode: WithdrawalFeeMode,
+    ) -> Result<WithdrawalFee<Balance>, DispatchError> {
+        Ok(WithdrawalFee {
+            amount: WITHDRAWAL_FEE.with(|v| *v.borrow()),
+            mode,
+        })
+    }
+}
+
 impl Trait for Test {
     type Event = ();
+    type WithdrawalWindow = WithdrawalWindow;
+    type MaxWithdrawalHistory = MaxWithdrawalHistory;
+    type WithdrawalFeeProvider = MockWithdrawalFee;
     type WeightInfo = ();
 }
 
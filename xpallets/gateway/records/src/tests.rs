@@ -19,7 +19,8 @@ fn test_normal() {
             X_BTC,
             50,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
 
         let numbers = XRecords::withdrawals_list_by_chain(Chain::Bitcoin)
@@ -30,7 +31,7 @@ fn test_normal() {
 
         assert_ok!(XRecords::process_withdrawals(&numbers, Chain::Bitcoin));
         for i in numbers {
-            assert_ok!(XRecords::finish_withdrawal(i, None));
+            assert_ok!(XRecords::finish_withdrawal(i, None, None));
         }
         assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 50 + 100);
     })
@@ -51,7 +52,8 @@ fn test_normal2() {
             X_BTC,
             50,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
         // withdrawal twice at once
         assert_ok!(XRecords::withdraw(
@@ -59,14 +61,16 @@ fn test_normal2() {
             X_ETH,
             100,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
         assert_ok!(XRecords::withdraw(
             &ALICE,
             X_ETH,
             50,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
 
         let numbers1 = XRecords::withdrawals_list_by_chain(Chain::Bitcoin)
@@ -119,7 +123,8 @@ fn test_withdrawal_more_then_usable() {
                 X_BTC,
                 100 + 50,
                 b"addr".to_vec(),
-                b"ext".to_vec().into()
+                b"ext".to_vec().into(),
+                WithdrawalFee::default()
             ),
             xpallet_assets::Error::<Test>::InsufficientBalance
         );
@@ -136,7 +141,8 @@ fn test_withdrawal_force_set_state() {
             X_BTC,
             10,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
         assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100);
         // ignore processing state, force release locked balance
@@ -152,7 +158,8 @@ fn test_withdrawal_force_set_state() {
             X_BTC,
             10,
             b"addr".to_vec(),
-            b"ext".to_vec().into()
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
         ));
         assert_ok!(XRecords::set_withdrawal_state(
             RawOrigin::Root.into(),
@@ -188,9 +195,501 @@ fn test_withdrawal_chainx() {
                 ChainXAssetId::get(),
                 50,
                 b"addr".to_vec(),
-                b"ext".to_vec().into()
+                b"ext".to_vec().into(),
+                WithdrawalFee::default()
             ),
             xpallet_assets::Error::<Test>::DenyNativeAsset
         );
     })
 }
+
+#[test]
+fn test_withdrawal_native_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        let _ = Balances::deposit_creating(&ALICE, 100);
+        let fee = WithdrawalFee {
+            amount: 30,
+            mode: WithdrawalFeeMode::Native,
+        };
+        assert_noop!(
+            XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                10,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee {
+                    amount: 101,
+                    mode: WithdrawalFeeMode::Native,
+                }
+            ),
+            pallet_balances::Error::<Test, _>::InsufficientBalance
+        );
+
+        // the native fee is reserved and unreserved after the cancellation
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr".to_vec(),
+            b"ext".to_vec().into(),
+            fee
+        ));
+        assert_eq!(Balances::reserved_balance(ALICE), 30);
+        assert_eq!(XRecords::pending_withdrawals(0).unwrap().fee(), fee);
+        assert_eq!(
+            XRecords::pending_withdrawals(0).unwrap().transfer_amount(),
+            10
+        );
+        assert_ok!(XRecords::cancel_withdrawal(0, &ALICE));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 100);
+
+        // the native fee is moved to the treasury after the withdrawal is finished
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr".to_vec(),
+            b"ext".to_vec().into(),
+            fee
+        ));
+        assert_ok!(XRecords::process_withdrawal(1, Chain::Bitcoin));
+        assert_ok!(XRecords::finish_withdrawal(1, None, None));
+        assert_eq!(Balances::reserved_balance(ALICE), 0);
+        assert_eq!(Balances::free_balance(ALICE), 70);
+        // the treasury account of the mock is the default account
+        assert_eq!(Balances::free_balance(0), 30);
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 - 10);
+    })
+}
+
+#[test]
+fn test_withdrawal_deducted_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec().into(),
+            WithdrawalFee {
+                amount: 5,
+                mode: WithdrawalFeeMode::Deducted,
+            }
+        ));
+        let record = XRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(record.balance(), 50);
+        assert_eq!(record.transfer_amount(), 45);
+        assert_eq!(XRecords::withdrawal_list()[&0].fee, record.fee());
+
+        // the fee of the root withdrawal is given by the fee provider
+        MockWithdrawalFee::set(8);
+        assert_ok!(XRecords::root_withdraw(
+            RawOrigin::Root.into(),
+            ALICE,
+            X_BTC,
+            30,
+            b"addr".to_vec(),
+            b"ext".to_vec().into(),
+        ));
+        let record = XRecords::pending_withdrawals(1).unwrap();
+        assert_eq!(
+            record.fee(),
+            WithdrawalFee {
+                amount: 8,
+                mode: WithdrawalFeeMode::Deducted,
+            }
+        );
+        assert_eq!(record.transfer_amount(), 22);
+    })
+}
+
+#[test]
+fn test_migrate_withdrawal_fee() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the same layout as `WithdrawalRecordV1`
+        let old_record = (
+            X_BTC,
+            ALICE,
+            50 as Balance,
+            b"addr".to_vec(),
+            Memo::from(b"ext".to_vec()),
+            1 as BlockNumber,
+        );
+        frame_support::storage::unhashed::put(
+            &PendingWithdrawals::<Test>::hashed_key_for(0),
+            &old_record,
+        );
+        assert_eq!(XRecords::storage_version(), Releases::V1_0_0);
+
+        MockWithdrawalFee::set(5);
+        XRecords::migrate_withdrawal_fee();
+        assert_eq!(XRecords::storage_version(), Releases::V2_0_0);
+        let record = XRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(record.applicant(), &ALICE);
+        assert_eq!(record.balance(), 50);
+        assert_eq!(record.transfer_amount(), 45);
+
+        // only migrate once
+        MockWithdrawalFee::set(10);
+        XRecords::migrate_withdrawal_fee();
+        assert_eq!(XRecords::pending_withdrawals(0).unwrap().fee().amount, 5);
+    })
+}
+
+#[test]
+fn test_withdrawal_caps() {
+    ExtBuilder::default().build_and_execute(|| {
+        let withdraw = |who, value| {
+            XRecords::withdraw(
+                &who,
+                X_BTC,
+                value,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee::default(),
+            )
+        };
+        let caps = WithdrawalCaps {
+            total: 100,
+            per_account: 60,
+        };
+        assert_ok!(XRecords::set_withdrawal_caps(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(caps)
+        ));
+
+        assert_ok!(withdraw(ALICE, 50));
+        assert!(!XRecords::is_delayed(0));
+        // exceed the cap of the account
+        assert_ok!(withdraw(ALICE, 20));
+        assert!(XRecords::is_delayed(1));
+        assert!(XRecords::withdrawal_list()[&1].delayed);
+        assert_noop!(
+            XRecords::process_withdrawal(1, Chain::Bitcoin),
+            XRecordsErr::WithdrawalDelayed
+        );
+        assert_ok!(withdraw(BOB, 40));
+        assert!(!XRecords::is_delayed(2));
+        // exceed the cap of the asset
+        assert_ok!(withdraw(CHARLIE, 20));
+        assert!(XRecords::is_delayed(3));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 90)));
+
+        assert_noop!(
+            XRecords::approve_withdrawal(RawOrigin::Root.into(), 0),
+            XRecordsErr::NotDelayed
+        );
+        assert_ok!(XRecords::approve_withdrawal(RawOrigin::Root.into(), 1));
+        assert_ok!(XRecords::process_withdrawal(1, Chain::Bitcoin));
+        assert_ok!(XRecords::approve_withdrawal_by_trustees(3, Chain::Bitcoin));
+        assert_noop!(
+            XRecords::approve_withdrawal_by_trustees(3, Chain::Ethereum),
+            XRecordsErr::UnexpectedChain
+        );
+        // the approved withdrawals are counted in the outflow
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 130)));
+        assert_eq!(XRecords::account_outflow_of(ALICE, X_BTC).amount(), 70);
+
+        // the outflow leaves the rolling window once the whole window has passed
+        System::set_block_number(6);
+        assert_ok!(withdraw(BOB, 10));
+        assert!(XRecords::is_delayed(4));
+        assert_ok!(XRecords::cancel_withdrawal(4, &BOB));
+        System::set_block_number(11);
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 0)));
+        assert_ok!(withdraw(CHARLIE, 60));
+        assert!(!XRecords::is_delayed(5));
+        System::set_block_number(15);
+        assert_ok!(withdraw(DAVE, 50));
+        assert!(XRecords::is_delayed(6));
+        assert_ok!(XRecords::cancel_withdrawal(6, &DAVE));
+        assert!(!XRecords::is_delayed(6));
+
+        // the canceled withdrawal is removed from the outflow
+        assert_ok!(withdraw(DAVE, 30));
+        assert!(!XRecords::is_delayed(7));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 90)));
+        assert_ok!(XRecords::cancel_withdrawal(5, &CHARLIE));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 30)));
+        assert_eq!(XRecords::account_outflow_of(CHARLIE, X_BTC).amount(), 0);
+        assert_ok!(withdraw(BOB, 60));
+        assert!(!XRecords::is_delayed(8));
+
+        // no caps
+        assert_ok!(XRecords::set_withdrawal_caps(
+            RawOrigin::Root.into(),
+            X_BTC,
+            None
+        ));
+        assert_ok!(withdraw(DAVE, 300));
+        assert!(!XRecords::is_delayed(9));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), None);
+    })
+}
+
+#[test]
+fn test_withdrawal_caps_force_set_state() {
+    ExtBuilder::default().build_and_execute(|| {
+        let withdraw = |who, value| {
+            XRecords::withdraw(
+                &who,
+                X_BTC,
+                value,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee::default(),
+            )
+        };
+        let caps = WithdrawalCaps {
+            total: 100,
+            per_account: 60,
+        };
+        assert_ok!(XRecords::set_withdrawal_caps(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(caps)
+        ));
+
+        assert_ok!(withdraw(ALICE, 50));
+        assert_ok!(withdraw(ALICE, 20));
+        assert_ok!(withdraw(BOB, 60));
+        assert!(XRecords::is_delayed(1));
+        assert!(XRecords::is_delayed(2));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 50)));
+
+        // the delayed withdrawal forced to be processed by root is approved
+        assert_ok!(XRecords::set_withdrawal_state(
+            RawOrigin::Root.into(),
+            1,
+            WithdrawalState::Processing
+        ));
+        assert_eq!(XRecords::state_of(1), Some(WithdrawalState::Processing));
+        assert!(!XRecords::is_delayed(1));
+        assert!(XRecords::is_approved(1));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 70)));
+        assert_eq!(XRecords::account_outflow_of(ALICE, X_BTC).amount(), 70);
+
+        // the delayed withdrawal forced to be finished by root is counted in the outflow
+        assert_ok!(XRecords::set_withdrawal_state(
+            RawOrigin::Root.into(),
+            2,
+            WithdrawalState::RootFinish
+        ));
+        assert_eq!(XRecords::state_of(2), None);
+        assert!(!XRecords::is_delayed(2));
+        assert_eq!(XRecords::withdrawal_caps(X_BTC), Some((caps, 130)));
+        assert_eq!(XRecords::account_outflow_of(BOB, X_BTC).amount(), 60);
+
+        // the caps window is full
+        assert_ok!(withdraw(CHARLIE, 10));
+        assert!(XRecords::is_delayed(3));
+    })
+}
+
+#[test]
+fn test_withdrawal_history() {
+    ExtBuilder::default().build_and_execute(|| {
+        let withdraw = || {
+            assert_ok!(XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                10,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee::default()
+            ));
+        };
+
+        withdraw();
+        withdraw();
+        System::set_block_number(5);
+        assert_ok!(XRecords::process_withdrawal(0, Chain::Bitcoin));
+        assert_ok!(XRecords::finish_withdrawal(0, None, Some(b"txid".to_vec())));
+        assert_ok!(XRecords::cancel_withdrawal(1, &ALICE));
+
+        // the finished and cancelled withdrawals are kept in the history
+        let finished = XRecords::withdrawal_record(0).unwrap();
+        assert_eq!(finished.state, WithdrawalState::NormalFinish);
+        assert_eq!(finished.finished_at, Some(5));
+        assert_eq!(finished.tx_hash, Some(b"txid".to_vec()));
+        let cancelled = XRecords::withdrawal_record(1).unwrap();
+        assert_eq!(cancelled.state, WithdrawalState::NormalCancel);
+        assert_eq!(cancelled.tx_hash, None);
+
+        withdraw();
+        let pending = XRecords::withdrawal_record(2).unwrap();
+        assert_eq!(pending.state, WithdrawalState::Applying);
+        assert_eq!(pending.finished_at, None);
+        assert_eq!(
+            XRecords::withdrawal_list_of(&ALICE, 0)
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+
+        // the oldest withdrawal is pruned when the history is full
+        withdraw();
+        assert_eq!(XRecords::account_withdrawals(&ALICE), vec![1, 2, 3]);
+        assert_eq!(XRecords::withdrawal_history_of(0), None);
+        assert!(XRecords::withdrawal_record(0).is_none());
+        assert!(XRecords::withdrawal_list_of(&ALICE, 1).is_empty());
+        assert!(XRecords::withdrawal_list_of(&BOB, 0).is_empty());
+    })
+}
+
+#[test]
+fn test_deposit_by_tx() {
+    ExtBuilder::default().build_and_execute(|| {
+        System::set_block_number(5);
+        assert!(!XRecords::is_tx_processed(Chain::Bitcoin, b"txid"));
+        assert_ok!(XRecords::deposit_by_tx(
+            Chain::Bitcoin,
+            b"txid".to_vec(),
+            &ALICE,
+            X_BTC,
+            100
+        ));
+        // a transaction could contain several deposits
+        assert_ok!(XRecords::deposit_by_tx(
+            Chain::Bitcoin,
+            b"txid".to_vec(),
+            &BOB,
+            X_BTC,
+            50
+        ));
+        assert_eq!(XAssets::usable_balance(&ALICE, &X_BTC), 100 + 100);
+        assert_eq!(
+            XRecords::tx_record(Chain::Bitcoin, b"txid".to_vec()),
+            Some(TxRecord {
+                height: 5,
+                deposits: vec![
+                    DepositRecord {
+                        asset_id: X_BTC,
+                        beneficiary: ALICE,
+                        balance: 100,
+                    },
+                    DepositRecord {
+                        asset_id: X_BTC,
+                        beneficiary: BOB,
+                        balance: 50,
+                    }
+                ],
+            })
+        );
+        assert!(XRecords::is_tx_processed(Chain::Bitcoin, b"txid"));
+        // the same txid on another chain is a different transaction
+        assert!(!XRecords::is_tx_processed(Chain::Ethereum, b"txid"));
+
+        // the transactions without any deposit are recorded as well
+        XRecords::record_processed_tx(Chain::Ethereum, b"withdrawal".to_vec());
+        assert_eq!(
+            XRecords::tx_record(Chain::Ethereum, b"withdrawal".to_vec()),
+            Some(TxRecord {
+                height: 5,
+                deposits: vec![],
+            })
+        );
+    })
+}
+
+#[test]
+fn test_amend_withdrawal() {
+    ExtBuilder::default().build_and_execute(|| {
+        for _ in 0..2 {
+            assert_ok!(XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                10,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee::default()
+            ));
+        }
+
+        assert_noop!(
+            XRecords::amend_withdrawal(0, &BOB, b"new".to_vec(), b"memo".to_vec().into()),
+            Error::<Test>::InvalidAccount
+        );
+        assert_ok!(XRecords::amend_withdrawal(
+            0,
+            &ALICE,
+            b"new".to_vec(),
+            b"memo".to_vec().into()
+        ));
+        let record = XRecords::pending_withdrawals(0).unwrap();
+        assert_eq!(record.addr(), &b"new".to_vec());
+        assert_eq!(record.ext(), &Memo::from(b"memo".to_vec()));
+        // the amended withdrawal keeps its position in the queue
+        assert_eq!(
+            XRecords::withdrawals_list_by_chain(Chain::Bitcoin)
+                .into_iter()
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+
+        assert_ok!(XRecords::process_withdrawal(1, Chain::Bitcoin));
+        assert_noop!(
+            XRecords::amend_withdrawal(1, &ALICE, b"new".to_vec(), b"memo".to_vec().into()),
+            Error::<Test>::NotApplyingState
+        );
+        assert_noop!(
+            XRecords::amend_withdrawal(2, &ALICE, b"new".to_vec(), b"memo".to_vec().into()),
+            Error::<Test>::NotExisted
+        );
+
+        // the delayed and the approved withdrawals can not be amended
+        assert_ok!(XRecords::set_withdrawal_caps(
+            RawOrigin::Root.into(),
+            X_BTC,
+            Some(WithdrawalCaps {
+                total: 5,
+                per_account: 5,
+            })
+        ));
+        assert_ok!(XRecords::withdraw(
+            &ALICE,
+            X_BTC,
+            10,
+            b"addr".to_vec(),
+            b"ext".to_vec().into(),
+            WithdrawalFee::default()
+        ));
+        assert!(XRecords::is_delayed(2));
+        assert_noop!(
+            XRecords::amend_withdrawal(2, &ALICE, b"new".to_vec(), b"memo".to_vec().into()),
+            Error::<Test>::NotAmendable
+        );
+        assert_ok!(XRecords::approve_withdrawal(RawOrigin::Root.into(), 2));
+        assert_noop!(
+            XRecords::amend_withdrawal(2, &ALICE, b"new".to_vec(), b"memo".to_vec().into()),
+            Error::<Test>::NotAmendable
+        );
+        assert_ok!(XRecords::cancel_withdrawal(2, &ALICE));
+        assert!(!XRecords::is_approved(2));
+    })
+}
+
+#[test]
+fn test_withdrawal_frozen() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(XAssets::freeze(RawOrigin::Root.into(), ALICE, X_BTC));
+        assert_noop!(
+            XRecords::withdraw(
+                &ALICE,
+                X_BTC,
+                10,
+                b"addr".to_vec(),
+                b"ext".to_vec().into(),
+                WithdrawalFee::default()
+            ),
+            xpallet_assets::Error::<Test>::AccountFrozen
+        );
+        // the deposit still works
+        assert_ok!(XRecords::deposit(&ALICE, X_BTC, 10));
+    })
+}
@@ -4,7 +4,8 @@ use codec::{Codec, Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{traits::Saturating, RuntimeDebug};
+use sp_std::prelude::Vec;
 
 use chainx_primitives::{AddrStr, AssetId};
 use xp_runtime::Memo;
@@ -39,6 +40,30 @@ impl Default for WithdrawalState {
     }
 }
 
+/// The way to pay the withdrawal fee.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum WithdrawalFeeMode {
+    /// The fee is deducted from the withdrawn asset.
+    Deducted,
+    /// The fee is paid in PCX, which is reserved until the withdrawal is finished.
+    Native,
+}
+
+impl Default for WithdrawalFeeMode {
+    fn default() -> Self {
+        WithdrawalFeeMode::Deducted
+    }
+}
+
+/// The fee applied to a withdrawal.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalFee<Balance> {
+    /// The amount of the fee, in the withdrawn asset if the fee is `Deducted`, otherwise in PCX.
+    pub amount: Balance,
+    pub mode: WithdrawalFeeMode,
+}
+
 /// WithdrawalRecord for withdrawal
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
 pub struct WithdrawalRecord<AccountId, Balance, BlockNumber> {
@@ -48,6 +73,7 @@ pub struct WithdrawalRecord<AccountId, Balance, BlockNumber> {
     addr: AddrStr,
     ext: Memo,
     height: BlockNumber,
+    fee: WithdrawalFee<Balance>,
 }
 
 impl<AccountId, Balance, BlockNumber> WithdrawalRecord<AccountId, Balance, BlockNumber>
@@ -63,6 +89,7 @@ where
         addr: AddrStr,
         ext: Memo,
         height: BlockNumber,
+        fee: WithdrawalFee<Balance>,
     ) -> Self {
         Self {
             asset_id,
@@ -71,6 +98,7 @@ where
             addr,
             ext,
             height,
+            fee,
         }
     }
     pub fn applicant(&self) -> &AccountId {
@@ -91,6 +119,140 @@ where
     pub fn height(&self) -> BlockNumber {
         self.height
     }
+    pub fn fee(&self) -> WithdrawalFee<Balance> {
+        self.fee
+    }
+    pub fn amend(&mut self, addr: AddrStr, ext: Memo) {
+        self.addr = addr;
+        self.ext = ext;
+    }
+}
+
+impl<AccountId, Balance, BlockNumber> WithdrawalRecord<AccountId, Balance, BlockNumber>
+where
+    Balance: Saturating + Copy,
+{
+    /// The amount which should be transferred to `addr` on the target chain.
+    pub fn transfer_amount(&self) -> Balance {
+        match self.fee.mode {
+            WithdrawalFeeMode::Deducted => self.balance.saturating_sub(self.fee.amount),
+            WithdrawalFeeMode::Native => self.balance,
+        }
+    }
+}
+
+/// The layout of `WithdrawalRecord` before the withdrawal fee was recorded.
+#[derive(Encode, Decode)]
+pub(crate) struct WithdrawalRecordV1<AccountId, Balance, BlockNumber> {
+    asset_id: AssetId,
+    applicant: AccountId,
+    balance: Balance,
+    addr: AddrStr,
+    ext: Memo,
+    height: BlockNumber,
+}
+
+impl<AccountId, Balance: Copy, BlockNumber> WithdrawalRecordV1<AccountId, Balance, BlockNumber> {
+    pub(crate) fn asset_id(&self) -> AssetId {
+        self.asset_id
+    }
+
+    pub(crate) fn balance(&self) -> Balance {
+        self.balance
+    }
+
+    /// Upgrade to the current layout, with the fee which has been deducted from the record.
+    pub(crate) fn upgrade(
+        self,
+        fee: WithdrawalFee<Balance>,
+    ) -> WithdrawalRecord<AccountId, Balance, BlockNumber> {
+        WithdrawalRecord {
+            asset_id: self.asset_id,
+            applicant: self.applicant,
+            balance: self.balance,
+            addr: self.addr,
+            ext: self.ext,
+            height: self.height,
+            fee,
+        }
+    }
+}
+
+/// The storage version of the XGatewayRecords module.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    V1_0_0,
+    /// `WithdrawalRecord` has the `fee` field.
+    V2_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
+/// The withdrawal caps of an asset in every withdrawal window.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct WithdrawalCaps<Balance> {
+    /// The maximum total withdrawal of the asset.
+    pub total: Balance,
+    /// The maximum withdrawal of the asset for an account.
+    pub per_account: Balance,
+}
+
+/// The amount withdrawn in the rolling window of the withdrawal caps.
+///
+/// The window is divided into slots, `slots` is the amount withdrawn in each slot
+/// as `(slot start, amount)`, from the oldest slot to the newest.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalOutflow<Balance, BlockNumber> {
+    pub slots: Vec<(BlockNumber, Balance)>,
+}
+
+impl<Balance, BlockNumber> WithdrawalOutflow<Balance, BlockNumber>
+where
+    Balance: Copy + Default + Saturating,
+    BlockNumber: Copy + PartialEq,
+{
+    /// Returns the total amount withdrawn in the window.
+    pub fn amount(&self) -> Balance {
+        self.slots
+            .iter()
+            .fold(Balance::default(), |acc, (_, amount)| {
+                acc.saturating_add(*amount)
+            })
+    }
+
+    /// Add `value` to the slot starting from `slot`, which must be the newest one.
+    pub fn add(&mut self, slot: BlockNumber, value: Balance) {
+        match self.slots.last_mut() {
+            Some((start, amount)) if *start == slot => *amount = amount.saturating_add(value),
+            _ => self.slots.push((slot, value)),
+        }
+    }
+
+    /// Subtract `value` from the slot starting from `slot`, nothing happens if the slot
+    /// has already left the window.
+    pub fn sub(&mut self, slot: BlockNumber, value: Balance) {
+        if let Some((_, amount)) = self.slots.iter_mut().find(|(start, _)| *start == slot) {
+            *amount = amount.saturating_sub(value);
+        }
+    }
+}
+
+/// The withdrawal which has reached the final state.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct WithdrawalHistory<AccountId, Balance, BlockNumber> {
+    pub record: WithdrawalRecord<AccountId, Balance, BlockNumber>,
+    /// One of `NormalFinish`, `RootFinish`, `NormalCancel` and `RootCancel`.
+    pub state: WithdrawalState,
+    /// The block at which the withdrawal reached the final state.
+    pub finished_at: BlockNumber,
+    /// The hash of the transaction which settled the withdrawal on the target chain.
+    pub tx_hash: Option<Vec<u8>>,
 }
 
 #[derive(PartialEq, Eq, Clone, Encode, Decode)]
@@ -101,13 +263,21 @@ pub struct Withdrawal<AccountId, Balance, BlockNumber> {
     pub addr: AddrStr,
     pub ext: Memo,
     pub height: BlockNumber,
+    pub fee: WithdrawalFee<Balance>,
     pub state: WithdrawalState,
+    /// Whether the withdrawal exceeds the caps and waits for the approval.
+    pub delayed: bool,
+    /// The block at which the withdrawal reached the final state.
+    pub finished_at: Option<BlockNumber>,
+    /// The hash of the transaction which settled the withdrawal on the target chain.
+    pub tx_hash: Option<Vec<u8>>,
 }
 
 impl<AccountId, Balance, BlockNumber> Withdrawal<AccountId, Balance, BlockNumber> {
     pub fn new(
         record: WithdrawalRecord<AccountId, Balance, BlockNumber>,
         state: WithdrawalState,
+        delayed: bool,
     ) -> Self {
         Self {
             asset_id: record.asset_id,
@@ -116,7 +286,41 @@ impl<AccountId, Balance, BlockNumber> Withdrawal<AccountId, Balance, BlockNumber
             addr: record.addr,
             ext: record.ext,
             height: record.height,
+            fee: record.fee,
             state,
+            delayed,
+            finished_at: None,
+            tx_hash: None,
+        }
+    }
+}
+
+impl<AccountId, Balance, BlockNumber> From<WithdrawalHistory<AccountId, Balance, BlockNumber>>
+    for Withdrawal<AccountId, Balance, BlockNumber>
+{
+    fn from(history: WithdrawalHistory<AccountId, Balance, BlockNumber>) -> Self {
+        Self {
+            finished_at: Some(history.finished_at),
+            tx_hash: history.tx_hash,
+            ..Self::new(history.record, history.state, false)
         }
     }
 }
+
+/// A deposit made by the transaction on the external chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct DepositRecord<AccountId, Balance> {
+    pub asset_id: AssetId,
+    pub beneficiary: AccountId,
+    pub balance: Balance,
+}
+
+/// The processed transaction on the external chain.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+pub struct TxRecord<AccountId, Balance, BlockNumber> {
+    /// The block at which the transaction was processed.
+    pub height: BlockNumber,
+    /// All the deposits made by the transaction, a transaction on Ethereum could
+    /// contain several deposit events.
+    pub deposits: Vec<DepositRecord<AccountId, Balance>>,
+}
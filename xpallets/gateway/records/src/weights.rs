@@ -34,6 +34,8 @@ pub trait WeightInfo {
     fn root_withdraw() -> Weight;
     fn set_withdrawal_state() -> Weight;
     fn set_withdrawal_state_list(u: u32) -> Weight;
+    fn set_withdrawal_caps() -> Weight;
+    fn approve_withdrawal() -> Weight;
 }
 
 /// Weights for xpallet_gateway_records using the Substrate node and recommended hardware.
@@ -45,9 +47,9 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().writes(4 as Weight))
     }
     fn root_withdraw() -> Weight {
-        (159_911_000 as Weight)
-            .saturating_add(T::DbWeight::get().reads(5 as Weight))
-            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+        (176_823_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(7 as Weight))
     }
     fn set_withdrawal_state() -> Weight {
         (209_709_000 as Weight)
@@ -59,6 +61,14 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
             .saturating_add(T::DbWeight::get().reads(8 as Weight))
             .saturating_add(T::DbWeight::get().writes(6 as Weight))
     }
+    fn set_withdrawal_caps() -> Weight {
+        (17_302_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn approve_withdrawal() -> Weight {
+        (31_548_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -69,9 +79,9 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().writes(4 as Weight))
     }
     fn root_withdraw() -> Weight {
-        (159_911_000 as Weight)
-            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
-            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+        (176_823_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(7 as Weight))
     }
     fn set_withdrawal_state() -> Weight {
         (209_709_000 as Weight)
@@ -83,4 +93,12 @@ impl WeightInfo for () {
             .saturating_add(RocksDbWeight::get().reads(8 as Weight))
             .saturating_add(RocksDbWeight::get().writes(6 as Weight))
     }
+    fn set_withdrawal_caps() -> Weight {
+        (17_302_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn approve_withdrawal() -> Weight {
+        (31_548_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
 }
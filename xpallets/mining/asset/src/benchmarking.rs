@@ -49,6 +49,25 @@ benchmarks! {
         assert!(Module::<T>::free_balance(&miner) == 90.into());
     }
 
+    claim_all {
+        let miner = account("miner", u, SEED);
+        xpallet_assets::Module::<T>::issue(&X_BTC, &miner, 1000.into())?;
+
+        let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&X_BTC);
+        <T as xpallet_assets::Trait>::Currency::make_free_balance_be(&reward_pot, 100.into());
+        <T as xpallet_assets::Trait>::Currency::issue(100.into());
+
+        Module::<T>::set_claim_staking_requirement(RawOrigin::Root.into(), X_BTC, 0)?;
+        Module::<T>::set_claim_frequency_limit(RawOrigin::Root.into(), X_BTC, 0.into())?;
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 100.into());
+
+    }: _(RawOrigin::Signed(miner.clone()))
+    verify {
+        assert!(Module::<T>::free_balance(&miner) > Zero::zero());
+    }
+
     set_claim_staking_requirement {
         let c = 1000;
     }: _(RawOrigin::Root, X_BTC, c)
@@ -85,6 +104,7 @@ mod tests {
             assert_ok!(test_benchmark_set_claim_staking_requirement::<Test>());
             assert_ok!(test_benchmark_set_claim_frequency_limit::<Test>());
             assert_ok!(test_benchmark_set_asset_power::<Test>());
+            assert_ok!(test_benchmark_claim_all::<Test>());
         });
     }
 }
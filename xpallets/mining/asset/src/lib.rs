@@ -21,7 +21,7 @@ use sp_std::prelude::*;
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::{DispatchError, DispatchResult},
+    dispatch::{DispatchError, DispatchResult, DispatchResultWithPostInfo},
     ensure,
     storage::IterableStorageMap,
     traits::{Currency, ExistenceRequirement},
@@ -29,6 +29,8 @@ use frame_support::{
 use frame_system::{ensure_root, ensure_signed};
 use sp_runtime::traits::{SaturatedConversion, Zero};
 
+use orml_utilities::with_transaction_result;
+
 use chainx_primitives::AssetId;
 use xp_logging::warn;
 use xp_mining_common::{
@@ -145,6 +147,8 @@ decl_event!(
         Claimed(AccountId, AssetId, Balance),
         /// Issue new balance to the reward pot. [reward_pot_account, amount]
         Minted(AccountId, Balance),
+        /// An asset miner failed to claim the mining reward in `claim_all`. [claimer, asset_id, error]
+        ClaimFailed(AccountId, AssetId, DispatchError),
     }
 );
 
@@ -195,6 +199,32 @@ decl_module! {
             <Self as Claim<T::AccountId>>::claim(&sender, &target)?;
         }
 
+        /// Claims the mining reward of all the mining assets the origin account holds.
+        ///
+        /// Each claim is still subject to the `ClaimRestriction` of the asset, the
+        /// outcome of each claim is reported via `Claimed` or `ClaimFailed`.
+        #[weight = <T as Trait>::WeightInfo::claim_all(Self::mining_previleged_assets().len() as u32)]
+        fn claim_all(origin) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let targets = Self::mining_previleged_assets()
+                .into_iter()
+                .filter(|asset_id| MinerLedgers::<T>::contains_key(&sender, asset_id))
+                .collect::<Vec<_>>();
+            let count = targets.len() as u32;
+
+            for target in targets {
+                let result = with_transaction_result(|| {
+                    <Self as Claim<T::AccountId>>::claim(&sender, &target).map_err(Into::into)
+                });
+                if let Err(e) = result {
+                    Self::deposit_event(Event::<T>::ClaimFailed(sender.clone(), target, e));
+                }
+            }
+
+            Ok(Some(<T as Trait>::WeightInfo::claim_all(count)).into())
+        }
+
         #[weight = <T as Trait>::WeightInfo::set_claim_staking_requirement()]
         fn set_claim_staking_requirement(origin, #[compact] asset_id: AssetId, #[compact] new: StakingRequirement) {
             ensure_root(origin)?;
@@ -12,6 +12,7 @@ use sp_core::H256;
 use sp_runtime::{
     testing::{Header, UintAuthorityId},
     traits::{BlakeTwo256, IdentityLookup},
+    transaction_validity::TransactionPriority,
     Perbill,
 };
 
@@ -51,7 +52,7 @@ impl_outer_event! {
         system<T>,
         balances<T>,
         session,
-        assets_registrar,
+        assets_registrar<T>,
         assets<T>,
         staking<T>,
         mining_asset<T>,
@@ -129,11 +130,15 @@ impl pallet_balances::Trait for Test {
 
 parameter_types! {
     pub const ChainXAssetId: AssetId = 0;
+    pub const AssetDeposit: Balance = 100;
 }
 impl xpallet_assets_registrar::Trait for Test {
     type Event = MetaEvent;
     type NativeAssetId = ChainXAssetId;
+    type DepositCurrency = Balances;
+    type AssetDeposit = AssetDeposit;
     type RegistrarHandler = XMiningAsset;
+    type AssetIssuance = ();
     type WeightInfo = ();
 }
 
@@ -243,6 +248,8 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const OffchainElection: bool = false;
+    pub const StakingUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
 
 pub struct DummyStakingRewardPotAccountDeterminer;
@@ -255,6 +262,11 @@ impl xp_mining_common::RewardPotAccountFor<AccountId, AccountId>
     }
 }
 
+impl frame_system::offchain::SendTransactionTypes<xpallet_mining_staking::Call<Test>> for Test {
+    type OverarchingCall = xpallet_mining_staking::Call<Test>;
+    type Extrinsic = sp_runtime::testing::TestXt<xpallet_mining_staking::Call<Test>, ()>;
+}
+
 impl xpallet_mining_staking::Trait for Test {
     type Currency = Balances;
     type Event = MetaEvent;
@@ -265,6 +277,10 @@ impl xpallet_mining_staking::Trait for Test {
     type SessionInterface = Self;
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type ElectionStrategy = xpallet_mining_staking::TopVotes;
+    type OffchainElection = OffchainElection;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = ();
 }
 
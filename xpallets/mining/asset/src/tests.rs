@@ -454,3 +454,61 @@ fn asset_mining_reward_should_work() {
         assert_eq!(Balances::free_balance(&TREASURY_ACCOUNT), treasury_balance);
     });
 }
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_register_xbtc());
+        let t_1 = 777;
+        assert_ok!(t_issue_xbtc(t_1, 100));
+        t_xbtc_set_claim_frequency_limit(2);
+        t_xbtc_set_claim_staking_requirement(0);
+
+        let reward_pot = DummyAssetRewardPotAccountDeterminer::reward_pot_account_for(&X_BTC);
+        t_issue_pcx(reward_pot, 1000);
+        t_system_block_number_inc(1);
+
+        let claim_failures = || {
+            System::events()
+                .into_iter()
+                .filter_map(|e| match e.event {
+                    MetaEvent::mining_asset(RawEvent::ClaimFailed(who, asset_id, err))
+                        if who == t_1 =>
+                    {
+                        Some((asset_id, err))
+                    }
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // 10% of the dividend goes to the referral.
+        let dividend =
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number()).unwrap();
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1)));
+        let claimed = dividend - dividend / 10;
+        assert_eq!(Balances::free_balance(t_1), claimed);
+        assert!(claim_failures().is_empty());
+
+        // The frequency limit is honoured.
+        t_system_block_number_inc(1);
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1)));
+        assert_eq!(Balances::free_balance(t_1), claimed);
+        assert_eq!(
+            claim_failures(),
+            vec![(
+                X_BTC,
+                DispatchError::from(Error::<Test>::UnexpiredFrequencyLimit)
+            )]
+        );
+
+        t_system_block_number_inc(2);
+        let dividend =
+            XMiningAsset::compute_dividend_at(&t_1, &X_BTC, System::block_number()).unwrap();
+        assert_ok!(XMiningAsset::claim_all(Origin::signed(t_1)));
+        assert_eq!(
+            Balances::free_balance(t_1),
+            claimed + dividend - dividend / 10
+        );
+    });
+}
@@ -34,6 +34,7 @@ pub trait WeightInfo {
     fn set_claim_staking_requirement() -> Weight;
     fn set_claim_frequency_limit() -> Weight;
     fn set_asset_power() -> Weight;
+    fn claim_all(n: u32) -> Weight;
 }
 
 /// Weights for xpallet_mining_asset using the Substrate node and recommended hardware.
@@ -57,6 +58,13 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_asset_power() -> Weight {
         (5_538_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn claim_all(n: u32) -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add((232_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
 }
 
 // For backwards compatibility and tests
@@ -79,4 +87,11 @@ impl WeightInfo for () {
     fn set_asset_power() -> Weight {
         (5_538_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn claim_all(n: u32) -> Weight {
+        (14_000_000 as Weight)
+            .saturating_add((232_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((13 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+    }
 }
@@ -0,0 +1,640 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! # Nomination Pools Module
+//!
+//! Small PCX holders can join a pool instead of nominating the validators themselves.
+//! The pool account bonds the joined balances to the validators chosen by the pool
+//! operator, claims the staking dividends of the pool automatically whenever a member
+//! operates on the pool and distributes them to the members pro rata to their shares.
+//!
+//! Leaving a pool goes through the standard bonding duration of Staking.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::type_complexity)]
+
+mod types;
+pub mod weights;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+use sp_std::prelude::*;
+
+use frame_support::{
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResult},
+    ensure,
+    traits::{Currency, ExistenceRequirement, Get},
+};
+use frame_system::{ensure_signed, RawOrigin};
+use sp_runtime::{
+    helpers_128bit::multiply_by_rational,
+    traits::{AccountIdConversion, SaturatedConversion, Saturating, StaticLookup, Zero},
+    ModuleId,
+};
+
+use orml_utilities::with_transaction_result;
+
+use xp_logging::debug;
+use xp_mining_common::Claim;
+use xpallet_mining_staking::{BalanceOf, LockedType, Unbonded};
+
+pub use self::types::*;
+pub use self::weights::WeightInfo;
+
+/// The precision of `PoolInfo::reward_per_share`.
+pub const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+type Staking<T> = xpallet_mining_staking::Module<T>;
+
+pub trait Trait: xpallet_mining_staking::Trait {
+    /// The overarching event type.
+    type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
+
+    /// The module id, used for deriving the pool accounts.
+    type ModuleId: Get<ModuleId>;
+
+    /// Maximum number of validators a pool can bond to.
+    type MaxTargets: Get<u32>;
+
+    type WeightInfo: WeightInfo;
+}
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XMiningPools {
+        /// Id of the next created pool.
+        pub NextPoolId get(fn next_pool_id): PoolId;
+
+        /// Information of the existing pools.
+        pub Pools get(fn pools):
+            map hasher(twox_64_concat) PoolId => Option<PoolInfo<T::AccountId, BalanceOf<T>>>;
+
+        /// The map from pool to the ledgers of its members.
+        pub PoolMembers get(fn pool_members):
+            double_map hasher(twox_64_concat) PoolId, hasher(blake2_128_concat) T::AccountId
+            => PoolMember<BalanceOf<T>, T::BlockNumber>;
+    }
+}
+
+decl_event!(
+    pub enum Event<T>
+    where
+        Balance = BalanceOf<T>,
+        <T as frame_system::Trait>::AccountId,
+    {
+        /// A new pool was created. [pool_id, operator, pool_account]
+        PoolCreated(PoolId, AccountId, AccountId),
+        /// The operator changed the validators of the pool. [pool_id, targets]
+        TargetsUpdated(PoolId, Vec<AccountId>),
+        /// An account joined the pool. [pool_id, member, amount, shares]
+        Joined(PoolId, AccountId, Balance, Balance),
+        /// The pool claimed the staking dividends. [pool_id, amount]
+        RewardClaimed(PoolId, Balance),
+        /// A member was paid the share of the staking dividends. [pool_id, member, amount]
+        PaidOut(PoolId, AccountId, Balance),
+        /// A member unbonded the shares from the pool. [pool_id, member, shares, amount]
+        Unbonded(PoolId, AccountId, Balance, Balance),
+        /// A member withdrew the unbonded balances that are due. [pool_id, member, amount]
+        Withdrawn(PoolId, AccountId, Balance),
+    }
+);
+
+decl_error! {
+    /// Error for the nomination pools module.
+    pub enum Error for Module<T: Trait> {
+        /// The pool does not exist.
+        PoolNotFound,
+        /// The origin is not the operator of the pool.
+        NotOperator,
+        /// A pool must bond to at least one validator.
+        EmptyTargets,
+        /// The targets exceed `MaxTargets`.
+        TooManyTargets,
+        /// The targets contain duplicate validators.
+        DuplicateTarget,
+        /// The target is not a validator.
+        NotValidator,
+        /// The validator is not one of the targets of the pool.
+        NotTarget,
+        /// The pool still has balances bonded or unbonding to the removed target.
+        TargetStillBonded,
+        /// The amount is zero.
+        ZeroBalance,
+        /// The shares are zero.
+        ZeroShares,
+        /// The account is not a member of the pool.
+        NotMember,
+        /// The member does not have enough shares.
+        InsufficientShares,
+        /// The member can have only `MaximumUnbondedChunkSize` unbonding entries in parallel.
+        NoMoreUnbondChunks,
+        /// No unbonded balance is due.
+        NothingToWithdraw,
+        /// The unbonded balances of the pool have not been unlocked from Staking yet.
+        NotYetUnlocked,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        /// Create a new pool operated by the origin account, bonding to `targets`.
+        #[weight = <T as Trait>::WeightInfo::create_pool(T::MaxTargets::get())]
+        fn create_pool(origin, targets: Vec<T::AccountId>) {
+            let sender = ensure_signed(origin)?;
+
+            Self::check_targets(&targets)?;
+
+            let pool_id = Self::next_pool_id();
+            NextPoolId::put(pool_id + 1);
+            Pools::<T>::insert(pool_id, PoolInfo {
+                operator: sender.clone(),
+                targets,
+                total_shares: Zero::zero(),
+                reward_per_share: 0,
+                unpaid_reward: Zero::zero(),
+                unbonding: Zero::zero(),
+                unlocked: Zero::zero(),
+            });
+
+            let pool_account = Self::pool_account_of(pool_id);
+            Self::deposit_event(Event::<T>::PoolCreated(pool_id, sender, pool_account));
+        }
+
+        /// Change the validators of the pool, the newly joined balances are bonded to `targets`.
+        ///
+        /// A validator the pool still bonds to can not be removed, use `rebond` to move
+        /// the balances to the other targets first. The unbonding balances of the pool must
+        /// be withdrawn before removing the validator as well.
+        #[weight = <T as Trait>::WeightInfo::set_targets(T::MaxTargets::get())]
+        fn set_targets(origin, #[compact] pool_id: PoolId, targets: Vec<T::AccountId>) {
+            let sender = ensure_signed(origin)?;
+            let mut pool = Self::operated_pool(&sender, pool_id)?;
+
+            Self::check_targets(&targets)?;
+            let pool_account = Self::pool_account_of(pool_id);
+            ensure!(
+                pool.targets
+                    .iter()
+                    .filter(|target| !targets.contains(target))
+                    .all(|target| Staking::<T>::bonded_to(&pool_account, target).is_zero()
+                        && Staking::<T>::unbonded_chunks_of(&pool_account, target).is_empty()),
+                Error::<T>::TargetStillBonded
+            );
+
+            pool.targets = targets.clone();
+            Pools::<T>::insert(pool_id, pool);
+
+            Self::deposit_event(Event::<T>::TargetsUpdated(pool_id, targets));
+        }
+
+        /// Move the `value` of the pool's nomination from one target to another.
+        #[weight = <T as Trait>::WeightInfo::rebond()]
+        fn rebond(
+            origin,
+            #[compact] pool_id: PoolId,
+            from: <T::Lookup as StaticLookup>::Source,
+            to: <T::Lookup as StaticLookup>::Source,
+            #[compact] value: BalanceOf<T>
+        ) {
+            let sender = ensure_signed(origin)?;
+            let pool = Self::operated_pool(&sender, pool_id)?;
+            let from = T::Lookup::lookup(from)?;
+            let to = T::Lookup::lookup(to)?;
+
+            ensure!(pool.targets.contains(&to), Error::<T>::NotTarget);
+
+            Staking::<T>::rebond(
+                Self::pool_origin(pool_id),
+                T::Lookup::unlookup(from),
+                T::Lookup::unlookup(to),
+                value,
+            )?;
+        }
+
+        /// Join the pool with `value` of the origin account's balance.
+        ///
+        /// The balance is bonded to the target the pool bonds the least to.
+        #[weight = <T as Trait>::WeightInfo::join(T::MaxTargets::get())]
+        fn join(origin, #[compact] pool_id: PoolId, #[compact] value: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+
+            let shares = with_transaction_result(|| Self::apply_join(&sender, pool_id, value))?;
+
+            Self::deposit_event(Event::<T>::Joined(pool_id, sender, value, shares));
+        }
+
+        /// Claim the staking dividends of the pool and pay the share of the origin account.
+        #[weight = <T as Trait>::WeightInfo::claim_payout(T::MaxTargets::get())]
+        fn claim_payout(origin, #[compact] pool_id: PoolId) {
+            let sender = ensure_signed(origin)?;
+            let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+            let mut member = Self::pool_members(pool_id, &sender);
+            ensure!(!member.shares.is_zero(), Error::<T>::NotMember);
+
+            Self::claim_pool_rewards(pool_id, &mut pool);
+            Self::pay_member(pool_id, &mut pool, &sender, &mut member)?;
+            member.reward_debt = Self::accumulated_reward_of(member.shares, pool.reward_per_share);
+
+            Pools::<T>::insert(pool_id, pool);
+            PoolMembers::<T>::insert(pool_id, &sender, member);
+        }
+
+        /// Unbond the `shares` of the origin account from the pool.
+        ///
+        /// The balances the shares are worth can be withdrawn after the bonding duration.
+        #[weight = <T as Trait>::WeightInfo::unbond(T::MaxTargets::get())]
+        fn unbond(origin, #[compact] pool_id: PoolId, #[compact] shares: BalanceOf<T>) {
+            let sender = ensure_signed(origin)?;
+            ensure!(!shares.is_zero(), Error::<T>::ZeroShares);
+
+            let value = with_transaction_result(|| Self::apply_unbond(&sender, pool_id, shares))?;
+
+            Self::deposit_event(Event::<T>::Unbonded(pool_id, sender, shares, value));
+        }
+
+        /// Withdraw the unbonded balances of the origin account that are due.
+        ///
+        /// Only the balances the pool has unlocked from Staking can be withdrawn.
+        #[weight = <T as Trait>::WeightInfo::withdraw_unbonded(T::MaxTargets::get())]
+        fn withdraw_unbonded(origin, #[compact] pool_id: PoolId) {
+            let sender = ensure_signed(origin)?;
+
+            let value = with_transaction_result(|| Self::apply_withdraw_unbonded(&sender, pool_id))?;
+
+            Self::deposit_event(Event::<T>::Withdrawn(pool_id, sender, value));
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// Returns the account which bonds on behalf of the pool `pool_id`.
+    pub fn pool_account_of(pool_id: PoolId) -> T::AccountId {
+        T::ModuleId::get().into_sub_account(pool_id)
+    }
+
+    /// Returns the total balances the pool `pool_id` has bonded.
+    pub fn pool_bonded(pool_id: PoolId) -> BalanceOf<T> {
+        let pool_account = Self::pool_account_of(pool_id);
+        Self::pools(pool_id)
+            .map(|pool| Self::bonded_of(&pool_account, &pool.targets))
+            .unwrap_or_default()
+    }
+
+    /// Returns the dividends `who` can be paid from the pool `pool_id` right now,
+    /// the dividends the pool has not claimed from Staking yet are not included.
+    pub fn pending_payout_of(pool_id: PoolId, who: &T::AccountId) -> BalanceOf<T> {
+        Self::pools(pool_id)
+            .map(|pool| Self::pending_reward_of(&Self::pool_members(pool_id, who), &pool))
+            .unwrap_or_default()
+    }
+
+    fn pool_origin(pool_id: PoolId) -> T::Origin {
+        RawOrigin::Signed(Self::pool_account_of(pool_id)).into()
+    }
+
+    /// Returns the balances of the pool account locked by Staking.
+    fn staking_locked_of(pool_account: &T::AccountId) -> BalanceOf<T> {
+        Staking::<T>::locks(pool_account)
+            .values()
+            .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc + *x)
+    }
+
+    /// Returns the balances of the pool account being unbonded from Staking.
+    fn staking_unbonding_of(pool_account: &T::AccountId) -> BalanceOf<T> {
+        Staking::<T>::locks(pool_account)
+            .get(&LockedType::BondedWithdrawal)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    fn bonded_of(pool_account: &T::AccountId, targets: &[T::AccountId]) -> BalanceOf<T> {
+        targets.iter().fold(Zero::zero(), |acc, target| {
+            acc + Staking::<T>::bonded_to(pool_account, target)
+        })
+    }
+
+    fn operated_pool(
+        who: &T::AccountId,
+        pool_id: PoolId,
+    ) -> Result<PoolInfo<T::AccountId, BalanceOf<T>>, Error<T>> {
+        let pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        ensure!(pool.operator == *who, Error::<T>::NotOperator);
+        Ok(pool)
+    }
+
+    fn check_targets(targets: &[T::AccountId]) -> Result<(), Error<T>> {
+        ensure!(!targets.is_empty(), Error::<T>::EmptyTargets);
+        ensure!(
+            targets.len() <= T::MaxTargets::get() as usize,
+            Error::<T>::TooManyTargets
+        );
+        for (idx, target) in targets.iter().enumerate() {
+            ensure!(
+                !targets[..idx].contains(target),
+                Error::<T>::DuplicateTarget
+            );
+            ensure!(Staking::<T>::is_validator(target), Error::<T>::NotValidator);
+        }
+        Ok(())
+    }
+
+    /// Converts `value` of balances to the shares of a pool, or vice versa.
+    fn convert(
+        value: BalanceOf<T>,
+        numerator: BalanceOf<T>,
+        denominator: BalanceOf<T>,
+    ) -> BalanceOf<T> {
+        if numerator.is_zero() || denominator.is_zero() {
+            return value;
+        }
+        multiply_by_rational(
+            value.saturated_into(),
+            numerator.saturated_into(),
+            denominator.saturated_into(),
+        )
+        .map(|x| x.saturated_into())
+        .unwrap_or(value)
+    }
+
+    fn accumulated_reward_of(shares: BalanceOf<T>, reward_per_share: u128) -> u128 {
+        multiply_by_rational(
+            shares.saturated_into(),
+            reward_per_share,
+            REWARD_PER_SHARE_PRECISION,
+        )
+        .unwrap_or(u128::max_value())
+    }
+
+    fn pending_reward_of(
+        member: &PoolMember<BalanceOf<T>, T::BlockNumber>,
+        pool: &PoolInfo<T::AccountId, BalanceOf<T>>,
+    ) -> BalanceOf<T> {
+        Self::accumulated_reward_of(member.shares, pool.reward_per_share)
+            .saturating_sub(member.reward_debt)
+            .saturated_into()
+    }
+
+    /// Claims the staking dividends of the pool from all its targets.
+    ///
+    /// The pool balances beyond the ones locked by Staking, the unlocked principal and
+    /// the unpaid dividends are taken as the new dividends, which includes the ones paid
+    /// to the pool account by others via `payout_for`. The new dividends are accumulated
+    /// into `PoolInfo::reward_per_share`.
+    fn claim_pool_rewards(pool_id: PoolId, pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>) {
+        let pool_account = Self::pool_account_of(pool_id);
+
+        for target in pool.targets.iter() {
+            let result = with_transaction_result(|| {
+                <Staking<T> as Claim<T::AccountId>>::claim(&pool_account, target)
+                    .map_err(Into::into)
+            });
+            if let Err(e) = result {
+                debug!(
+                    "[claim_pool_rewards] pool:{:?} failed to claim from {:?}: {:?}",
+                    pool_id, target, e
+                );
+            }
+        }
+
+        let accounted = Self::staking_locked_of(&pool_account) + pool.unlocked + pool.unpaid_reward;
+        let reward = T::Currency::free_balance(&pool_account).saturating_sub(accounted);
+        if reward.is_zero() || pool.total_shares.is_zero() {
+            return;
+        }
+
+        let reward_per_share = multiply_by_rational(
+            reward.saturated_into(),
+            REWARD_PER_SHARE_PRECISION,
+            pool.total_shares.saturated_into(),
+        )
+        .unwrap_or_default();
+        pool.reward_per_share = pool.reward_per_share.saturating_add(reward_per_share);
+        pool.unpaid_reward += reward;
+
+        Self::deposit_event(Event::<T>::RewardClaimed(pool_id, reward));
+    }
+
+    /// Pays the pending dividends to the member `who`.
+    ///
+    /// `reward_debt` of the member has to be reset by the caller afterwards.
+    fn pay_member(
+        pool_id: PoolId,
+        pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>,
+        who: &T::AccountId,
+        member: &mut PoolMember<BalanceOf<T>, T::BlockNumber>,
+    ) -> DispatchResult {
+        let pending = Self::pending_reward_of(member, pool);
+        if pending.is_zero() {
+            return Ok(());
+        }
+        T::Currency::transfer(
+            &Self::pool_account_of(pool_id),
+            who,
+            pending,
+            ExistenceRequirement::AllowDeath,
+        )?;
+        pool.unpaid_reward = pool.unpaid_reward.saturating_sub(pending);
+        Self::deposit_event(Event::<T>::PaidOut(pool_id, who.clone(), pending));
+        Ok(())
+    }
+
+    fn apply_join(
+        who: &T::AccountId,
+        pool_id: PoolId,
+        value: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let mut member = Self::pool_members(pool_id, who);
+        let pool_account = Self::pool_account_of(pool_id);
+
+        Self::claim_pool_rewards(pool_id, &mut pool);
+        Self::pay_member(pool_id, &mut pool, who, &mut member)?;
+
+        let bonded = Self::bonded_of(&pool_account, &pool.targets);
+        let shares = Self::convert(value, pool.total_shares, bonded);
+
+        T::Currency::transfer(who, &pool_account, value, ExistenceRequirement::KeepAlive)?;
+        let target = pool
+            .targets
+            .iter()
+            .min_by_key(|target| Staking::<T>::bonded_to(&pool_account, target))
+            .cloned()
+            .ok_or(Error::<T>::EmptyTargets)?;
+        Staking::<T>::bond(
+            Self::pool_origin(pool_id),
+            T::Lookup::unlookup(target),
+            value,
+        )?;
+
+        pool.total_shares += shares;
+        member.shares += shares;
+        member.reward_debt = Self::accumulated_reward_of(member.shares, pool.reward_per_share);
+
+        Pools::<T>::insert(pool_id, pool);
+        PoolMembers::<T>::insert(pool_id, who, member);
+
+        Ok(shares)
+    }
+
+    fn apply_unbond(
+        who: &T::AccountId,
+        pool_id: PoolId,
+        shares: BalanceOf<T>,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let mut member = Self::pool_members(pool_id, who);
+        ensure!(member.shares >= shares, Error::<T>::InsufficientShares);
+
+        let current_block = <frame_system::Module<T>>::block_number();
+        let locked_until = current_block + Staking::<T>::bonding_duration();
+        let merged = member
+            .unbonding
+            .iter()
+            .any(|chunk| chunk.locked_until == locked_until);
+        ensure!(
+            merged || member.unbonding.len() < Staking::<T>::maximum_unbonded_chunk_size() as usize,
+            Error::<T>::NoMoreUnbondChunks
+        );
+
+        let pool_account = Self::pool_account_of(pool_id);
+
+        Self::claim_pool_rewards(pool_id, &mut pool);
+        Self::pay_member(pool_id, &mut pool, who, &mut member)?;
+
+        let bonded = Self::bonded_of(&pool_account, &pool.targets);
+        let value = Self::convert(shares, bonded, pool.total_shares);
+        ensure!(!value.is_zero(), Error::<T>::ZeroBalance);
+
+        // Free the unbonded chunks of the pool that are due to make room for the new one.
+        Self::withdraw_pool_unbonded(pool_id, &mut pool);
+
+        // Unbond from the targets the pool bonds the most to first.
+        let mut targets = pool
+            .targets
+            .iter()
+            .map(|target| {
+                (
+                    target.clone(),
+                    Staking::<T>::bonded_to(&pool_account, target),
+                )
+            })
+            .collect::<Vec<_>>();
+        targets.sort_by(|a, b| b.1.cmp(&a.1));
+        let mut remaining = value;
+        for (target, target_bonded) in targets {
+            if remaining.is_zero() {
+                break;
+            }
+            let to_unbond = remaining.min(target_bonded);
+            if to_unbond.is_zero() {
+                continue;
+            }
+            // All the members share the unbonded chunks of the pool account, which must not
+            // be used up by some of them, the members are capped by their own chunks instead.
+            Staking::<T>::unbond_uncapped(&pool_account, &target, to_unbond)?;
+            remaining = remaining.saturating_sub(to_unbond);
+        }
+
+        pool.total_shares = pool.total_shares.saturating_sub(shares);
+        pool.unbonding += value;
+        member.shares -= shares;
+        member.reward_debt = Self::accumulated_reward_of(member.shares, pool.reward_per_share);
+        if let Some(chunk) = member
+            .unbonding
+            .iter_mut()
+            .find(|chunk| chunk.locked_until == locked_until)
+        {
+            chunk.value += value;
+        } else {
+            member.unbonding.push(Unbonded {
+                value,
+                locked_until,
+            });
+        }
+
+        Pools::<T>::insert(pool_id, pool);
+        PoolMembers::<T>::insert(pool_id, who, member);
+
+        Ok(value)
+    }
+
+    fn apply_withdraw_unbonded(
+        who: &T::AccountId,
+        pool_id: PoolId,
+    ) -> Result<BalanceOf<T>, DispatchError> {
+        let mut pool = Self::pools(pool_id).ok_or(Error::<T>::PoolNotFound)?;
+        let mut member = Self::pool_members(pool_id, who);
+
+        let current_block = <frame_system::Module<T>>::block_number();
+        let (due, pending): (Vec<_>, Vec<_>) = member
+            .unbonding
+            .into_iter()
+            .partition(|chunk| current_block > chunk.locked_until);
+        ensure!(!due.is_empty(), Error::<T>::NothingToWithdraw);
+        let owed = due
+            .iter()
+            .fold(Zero::zero(), |acc: BalanceOf<T>, chunk| acc + chunk.value);
+
+        // The due balances might have been unlocked by the other members already.
+        Self::withdraw_pool_unbonded(pool_id, &mut pool);
+
+        let pool_account = Self::pool_account_of(pool_id);
+        let value = Self::unbonding_value_of(owed, &pool_account, &pool);
+        ensure!(value <= pool.unlocked, Error::<T>::NotYetUnlocked);
+
+        T::Currency::transfer(&pool_account, who, value, ExistenceRequirement::AllowDeath)?;
+        pool.unbonding = pool.unbonding.saturating_sub(owed);
+        pool.unlocked -= value;
+
+        member.unbonding = pending;
+        if member.shares.is_zero() && member.unbonding.is_empty() {
+            PoolMembers::<T>::remove(pool_id, who);
+        } else {
+            PoolMembers::<T>::insert(pool_id, who, member);
+        }
+        Pools::<T>::insert(pool_id, pool);
+
+        Ok(value)
+    }
+
+    /// Withdraws the unbonded balances of the pool that are due from Staking, which are
+    /// recorded as unlocked for the members to withdraw.
+    ///
+    /// The pool only has unbonded balances on its targets, see `set_targets`.
+    fn withdraw_pool_unbonded(pool_id: PoolId, pool: &mut PoolInfo<T::AccountId, BalanceOf<T>>) {
+        let pool_account = Self::pool_account_of(pool_id);
+        if let Ok(unlocked) = Staking::<T>::withdraw_unbonded_from(&pool_account, &pool.targets) {
+            pool.unlocked += unlocked;
+        }
+    }
+
+    /// Returns the balances the `owed` unbonding balances of a member are worth.
+    ///
+    /// The slashes on the unbonding balances of the pool are shared by all the unbonding
+    /// members pro rata.
+    fn unbonding_value_of(
+        owed: BalanceOf<T>,
+        pool_account: &T::AccountId,
+        pool: &PoolInfo<T::AccountId, BalanceOf<T>>,
+    ) -> BalanceOf<T> {
+        let actual = Self::staking_unbonding_of(pool_account) + pool.unlocked;
+        if actual >= pool.unbonding {
+            owed
+        } else {
+            multiply_by_rational(
+                owed.saturated_into(),
+                actual.saturated_into(),
+                pool.unbonding.saturated_into(),
+            )
+            .map(|x| x.saturated_into())
+            .unwrap_or_default()
+        }
+    }
+}
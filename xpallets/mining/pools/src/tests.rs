@@ -0,0 +1,370 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use super::*;
+use crate::mock::*;
+
+use frame_support::{assert_err, assert_ok, traits::OnInitialize};
+use sp_runtime::Perbill;
+use sp_staking::offence::{OffenceDetails, OnOffenceHandler};
+
+use xp_mining_staking::SessionIndex;
+
+fn t_create_pool(operator: AccountId, targets: Vec<AccountId>) -> DispatchResult {
+    XMiningPools::create_pool(Origin::signed(operator), targets)
+}
+
+fn t_join(who: AccountId, pool_id: PoolId, value: Balance) -> DispatchResult {
+    XMiningPools::join(Origin::signed(who), pool_id, value)
+}
+
+fn t_unbond(who: AccountId, pool_id: PoolId, shares: Balance) -> DispatchResult {
+    XMiningPools::unbond(Origin::signed(who), pool_id, shares)
+}
+
+fn t_start_session(session_index: SessionIndex) {
+    for i in Session::current_index()..session_index {
+        System::set_block_number((i + 1).into());
+        Timestamp::set_timestamp(System::block_number() * 1000 + INIT_TIMESTAMP);
+        Session::on_initialize(System::block_number());
+    }
+    assert_eq!(Session::current_index(), session_index);
+}
+
+fn t_paid_out(pool_id: PoolId, who: AccountId) -> Balance {
+    System::events()
+        .iter()
+        .filter_map(|e| match e.event {
+            MetaEvent::pools(RawEvent::PaidOut(id, member, amount))
+                if id == pool_id && member == who =>
+            {
+                Some(amount)
+            }
+            _ => None,
+        })
+        .sum()
+}
+
+#[test]
+fn create_pool_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_err!(t_create_pool(10, vec![]), Error::<Test>::EmptyTargets);
+        assert_err!(
+            t_create_pool(10, vec![1, 2, 3, 4]),
+            Error::<Test>::TooManyTargets
+        );
+        assert_err!(
+            t_create_pool(10, vec![2, 2]),
+            Error::<Test>::DuplicateTarget
+        );
+        assert_err!(t_create_pool(10, vec![2, 5]), Error::<Test>::NotValidator);
+
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_eq!(XMiningPools::next_pool_id(), 1);
+        assert_eq!(
+            XMiningPools::pools(0),
+            Some(PoolInfo {
+                operator: 10,
+                targets: vec![2, 3],
+                total_shares: 0,
+                reward_per_share: 0,
+                unpaid_reward: 0,
+                unbonding: 0,
+                unlocked: 0,
+            })
+        );
+        let pool_account = XMiningPools::pool_account_of(0);
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::pools(RawEvent::PoolCreated(0, 10, pool_account))));
+
+        assert_err!(
+            XMiningPools::set_targets(Origin::signed(20), 0, vec![4]),
+            Error::<Test>::NotOperator
+        );
+        assert_err!(
+            XMiningPools::set_targets(Origin::signed(10), 1, vec![4]),
+            Error::<Test>::PoolNotFound
+        );
+        assert_ok!(XMiningPools::set_targets(Origin::signed(10), 0, vec![4]));
+        assert_eq!(XMiningPools::pools(0).unwrap().targets, vec![4]);
+    });
+}
+
+#[test]
+fn join_and_claim_payout_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_err!(t_join(10, 0, 0), Error::<Test>::ZeroBalance);
+        assert_err!(t_join(10, 1, 100), Error::<Test>::PoolNotFound);
+
+        // The joined balances are bonded to the target the pool bonds the least to.
+        assert_ok!(t_join(10, 0, 100));
+        assert_ok!(t_join(20, 0, 200));
+        let pool_account = XMiningPools::pool_account_of(0);
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 100);
+        assert_eq!(XStaking::bonded_to(&pool_account, &3), 200);
+        assert_eq!(XMiningPools::pool_bonded(0), 300);
+        assert_eq!(XMiningPools::pools(0).unwrap().total_shares, 300);
+        assert_eq!(XMiningPools::pool_members(0, 10).shares, 100);
+        assert_eq!(XMiningPools::pool_members(0, 20).shares, 200);
+        assert_eq!(Balances::usable_balance(&10), 900);
+        assert_eq!(Balances::usable_balance(&20), 800);
+
+        assert_err!(
+            XMiningPools::claim_payout(Origin::signed(30), 0),
+            Error::<Test>::NotMember
+        );
+
+        t_start_session(6);
+
+        // The pool claims the dividends from both targets automatically.
+        assert_ok!(XMiningPools::claim_payout(Origin::signed(10), 0));
+        let reward_per_share = XMiningPools::pools(0).unwrap().reward_per_share;
+        assert!(reward_per_share > 0);
+        let paid_10 = t_paid_out(0, 10);
+        assert_eq!(paid_10, 100 * reward_per_share / REWARD_PER_SHARE_PRECISION);
+        assert_eq!(Balances::free_balance(&10), 900 + paid_10);
+
+        // The pool has nothing more to claim in the same block.
+        assert_eq!(
+            XMiningPools::pending_payout_of(0, &20),
+            200 * reward_per_share / REWARD_PER_SHARE_PRECISION
+        );
+        assert_ok!(XMiningPools::claim_payout(Origin::signed(20), 0));
+        assert_eq!(
+            XMiningPools::pools(0).unwrap().reward_per_share,
+            reward_per_share
+        );
+        let paid_20 = t_paid_out(0, 20);
+        assert_eq!(paid_20, 200 * reward_per_share / REWARD_PER_SHARE_PRECISION);
+        assert_eq!(XMiningPools::pending_payout_of(0, &10), 0);
+        assert_eq!(XMiningPools::pending_payout_of(0, &20), 0);
+
+        // A newcomer does not share the dividends claimed before joining.
+        assert_ok!(t_join(1, 0, 30));
+        assert_eq!(XMiningPools::pending_payout_of(0, &1), 0);
+        assert_eq!(XMiningPools::pool_members(0, 1).shares, 30);
+    });
+}
+
+#[test]
+fn dividends_paid_by_others_should_be_accounted() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_ok!(t_join(10, 0, 100));
+        assert_ok!(t_join(20, 0, 200));
+        let pool_account = XMiningPools::pool_account_of(0);
+
+        t_start_session(6);
+
+        // Anyone can move the dividends of the pool to the pool account.
+        let free_before = Balances::free_balance(&pool_account);
+        assert_ok!(XStaking::payout_for(Origin::signed(30), pool_account, 2));
+        let paid_for = Balances::free_balance(&pool_account) - free_before;
+        assert!(paid_for > 0);
+
+        assert_ok!(XMiningPools::claim_payout(Origin::signed(10), 0));
+        assert_ok!(XMiningPools::claim_payout(Origin::signed(20), 0));
+        let claimed = System::events()
+            .iter()
+            .filter_map(|e| match e.event {
+                MetaEvent::pools(RawEvent::RewardClaimed(0, amount)) => Some(amount),
+                _ => None,
+            })
+            .sum::<Balance>();
+        assert!(claimed > paid_for);
+
+        // Only the rounding dust of the dividends is left in the pool.
+        let pool = XMiningPools::pools(0).unwrap();
+        assert_eq!(
+            t_paid_out(0, 10) + t_paid_out(0, 20) + pool.unpaid_reward,
+            claimed
+        );
+        assert_eq!(
+            Balances::free_balance(&pool_account),
+            XMiningPools::pool_bonded(0) + pool.unpaid_reward
+        );
+    });
+}
+
+#[test]
+fn unbond_and_withdraw_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_ok!(t_join(10, 0, 100));
+        assert_ok!(t_join(20, 0, 200));
+
+        assert_err!(t_unbond(10, 0, 0), Error::<Test>::ZeroShares);
+        assert_err!(t_unbond(10, 0, 101), Error::<Test>::InsufficientShares);
+        assert_err!(t_unbond(30, 0, 1), Error::<Test>::InsufficientShares);
+
+        // Unbond from the target the pool bonds the most to first.
+        assert_ok!(t_unbond(10, 0, 50));
+        let pool_account = XMiningPools::pool_account_of(0);
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 100);
+        assert_eq!(XStaking::bonded_to(&pool_account, &3), 150);
+        assert_eq!(XMiningPools::pools(0).unwrap().total_shares, 250);
+
+        let locked_until = System::block_number() + XStaking::bonding_duration();
+        assert_eq!(
+            XMiningPools::pool_members(0, 10),
+            PoolMember {
+                shares: 50,
+                reward_debt: 0,
+                unbonding: vec![Unbonded {
+                    value: 50,
+                    locked_until
+                }],
+            }
+        );
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::pools(RawEvent::Unbonded(0, 10, 50, 50))));
+
+        assert_err!(
+            XMiningPools::withdraw_unbonded(Origin::signed(10), 0),
+            Error::<Test>::NothingToWithdraw
+        );
+
+        System::set_block_number(locked_until + 1);
+        assert_ok!(XMiningPools::withdraw_unbonded(Origin::signed(10), 0));
+        assert_eq!(Balances::free_balance(&10), 950);
+        assert_eq!(Balances::usable_balance(&10), 950);
+        assert!(XMiningPools::pool_members(0, 10).unbonding.is_empty());
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::pools(RawEvent::Withdrawn(0, 10, 50))));
+
+        // The member is removed once everything is withdrawn.
+        assert_ok!(t_unbond(10, 0, 50));
+        System::set_block_number(System::block_number() + XStaking::bonding_duration() + 1);
+        assert_ok!(XMiningPools::withdraw_unbonded(Origin::signed(10), 0));
+        assert_eq!(Balances::free_balance(&10), 1000);
+        assert!(!PoolMembers::<Test>::contains_key(0, 10));
+        assert_eq!(XMiningPools::pool_bonded(0), 200);
+    });
+}
+
+#[test]
+fn unbond_should_not_be_capped_by_other_members() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2]));
+        let members = (100..112).collect::<Vec<AccountId>>();
+        for member in members.iter() {
+            Balances::make_free_balance_be(member, 100);
+            assert_ok!(t_join(*member, 0, 10));
+        }
+
+        // Each member unbonds in a different era, which takes more unbonded chunks
+        // of the pool account than `MaximumUnbondedChunkSize`.
+        let era_length = SessionDuration::get() * XStaking::sessions_per_era() as BlockNumber;
+        for member in members.iter() {
+            System::set_block_number(System::block_number() + era_length);
+            assert_ok!(t_unbond(*member, 0, 1));
+        }
+        let pool_account = XMiningPools::pool_account_of(0);
+        assert!(
+            XStaking::unbonded_chunks_of(&pool_account, &2).len()
+                > XStaking::maximum_unbonded_chunk_size() as usize
+        );
+        assert_eq!(XMiningPools::pools(0).unwrap().unbonding, 12);
+
+        // All of them exit through the standard bonding duration.
+        System::set_block_number(System::block_number() + XStaking::bonding_duration() + 1);
+        for member in members.iter() {
+            assert_ok!(XMiningPools::withdraw_unbonded(Origin::signed(*member), 0));
+            assert_eq!(Balances::usable_balance(member), 91);
+        }
+    });
+}
+
+#[test]
+fn operator_rebond_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_ok!(t_join(20, 0, 100));
+        let pool_account = XMiningPools::pool_account_of(0);
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 100);
+
+        // The validator the pool still bonds to can not be removed.
+        assert_err!(
+            XMiningPools::set_targets(Origin::signed(10), 0, vec![3, 4]),
+            Error::<Test>::TargetStillBonded
+        );
+
+        assert_err!(
+            XMiningPools::rebond(Origin::signed(20), 0, 2, 3, 100),
+            Error::<Test>::NotOperator
+        );
+        assert_err!(
+            XMiningPools::rebond(Origin::signed(10), 0, 2, 4, 100),
+            Error::<Test>::NotTarget
+        );
+        assert_ok!(XMiningPools::set_targets(
+            Origin::signed(10),
+            0,
+            vec![2, 3, 4]
+        ));
+        assert_ok!(XMiningPools::rebond(Origin::signed(10), 0, 2, 4, 100));
+        assert_eq!(XStaking::bonded_to(&pool_account, &2), 0);
+        assert_eq!(XStaking::bonded_to(&pool_account, &4), 100);
+
+        assert_ok!(XMiningPools::set_targets(Origin::signed(10), 0, vec![3, 4]));
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::pools(RawEvent::TargetsUpdated(0, vec![3, 4]))));
+        assert_eq!(XMiningPools::pool_bonded(0), 100);
+
+        // The validator the pool still has unbonding balances on can not be removed either.
+        assert_ok!(t_unbond(20, 0, 100));
+        assert_eq!(XStaking::bonded_to(&pool_account, &4), 0);
+        assert_err!(
+            XMiningPools::set_targets(Origin::signed(10), 0, vec![3]),
+            Error::<Test>::TargetStillBonded
+        );
+    });
+}
+
+#[test]
+fn slashed_unbonding_should_be_shared_pro_rata() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_ok!(t_create_pool(10, vec![2, 3]));
+        assert_ok!(t_join(10, 0, 100));
+        assert_ok!(t_join(20, 0, 200));
+        let pool_account = XMiningPools::pool_account_of(0);
+
+        // The unbonded 100 comes from target 3.
+        assert_ok!(t_unbond(10, 0, 100));
+        let locked_until = XMiningPools::pool_members(0, 10).unbonding[0].locked_until;
+        assert_eq!(XStaking::bonded_to(&pool_account, &3), 100);
+        assert_eq!(XMiningPools::pools(0).unwrap().unbonding, 100);
+
+        assert_ok!(XStaking::set_nominator_slashing(
+            Origin::root(),
+            true,
+            Perbill::from_percent(10)
+        ));
+        let reward_pot = XStaking::reward_pot_for(&3);
+        assert_ok!(XStaking::on_offence(
+            &[OffenceDetails {
+                offender: (3, reward_pot),
+                reporters: vec![],
+            }],
+            &[Perbill::zero()],
+            Session::current_index(),
+        ));
+        t_start_session(1);
+        assert_eq!(XStaking::bonded_to(&pool_account, &3), 90);
+
+        System::set_block_number(locked_until + 1);
+        assert_ok!(XMiningPools::withdraw_unbonded(Origin::signed(10), 0));
+        assert_eq!(Balances::free_balance(&10), 990);
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::pools(RawEvent::Withdrawn(0, 10, 90))));
+
+        let pool = XMiningPools::pools(0).unwrap();
+        assert_eq!(pool.unbonding, 0);
+        assert_eq!(pool.unlocked, 0);
+    });
+}
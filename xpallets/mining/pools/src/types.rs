@@ -0,0 +1,49 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+use sp_std::vec::Vec;
+
+use codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+use sp_runtime::RuntimeDebug;
+
+use xpallet_mining_staking::Unbonded;
+
+/// Type for the index of nomination pools.
+pub type PoolId = u32;
+
+/// Information of a nomination pool.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolInfo<AccountId, Balance> {
+    /// Account which chooses the validators the pool bonds to.
+    pub operator: AccountId,
+    /// Validators the new joined balances are bonded to.
+    pub targets: Vec<AccountId>,
+    /// Total shares issued to the pool members.
+    pub total_shares: Balance,
+    /// Accumulated staking reward per share, scaled by `REWARD_PER_SHARE_PRECISION`.
+    pub reward_per_share: u128,
+    /// Staking dividends accumulated into `reward_per_share` but not paid to the members yet.
+    pub unpaid_reward: Balance,
+    /// Total balances the members are unbonding from the pool, before any slash.
+    pub unbonding: Balance,
+    /// Unbonded balances already unlocked from Staking but not withdrawn by the members yet.
+    pub unlocked: Balance,
+}
+
+/// Ledger of a member in a nomination pool.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PoolMember<Balance, BlockNumber> {
+    /// Shares of the pool the member holds.
+    pub shares: Balance,
+    /// Reward per share the member has been paid up to, multiplied by `shares`.
+    pub reward_debt: u128,
+    /// Balances being unbonded from the pool, the slashes on the unbonding balances of
+    /// the pool are shared by the members pro rata when withdrawing.
+    pub unbonding: Vec<Unbonded<Balance, BlockNumber>>,
+}
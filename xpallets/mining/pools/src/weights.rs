@@ -0,0 +1,151 @@
+// Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
+
+//! Weights for xpallet_mining_pools
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 2.0.0
+//! DATE: 2020-11-20, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("benchmarks"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/chainx
+// benchmark
+// --chain=benchmarks
+// --steps=50
+// --repeat=20
+// --pallet=xpallet_mining_pools
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --output=./xpallets/mining/pools/src/weights.rs
+// --template=./scripts/xpallet-weight-template.hbs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{
+    traits::Get,
+    weights::{constants::RocksDbWeight, Weight},
+};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for xpallet_mining_pools.
+pub trait WeightInfo {
+    fn create_pool(t: u32) -> Weight;
+    fn set_targets(t: u32) -> Weight;
+    fn rebond() -> Weight;
+    fn join(t: u32) -> Weight;
+    fn claim_payout(t: u32) -> Weight;
+    fn unbond(t: u32) -> Weight;
+    fn withdraw_unbonded(t: u32) -> Weight;
+}
+
+/// Weights for xpallet_mining_pools using the Substrate node and recommended hardware.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
+    fn create_pool(t: u32) -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add((6_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn set_targets(t: u32) -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn rebond() -> Weight {
+        (205_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+    }
+    fn join(t: u32) -> Weight {
+        (321_000_000 as Weight)
+            .saturating_add((392_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(12 as Weight))
+            .saturating_add(T::DbWeight::get().reads((16 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(8 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(t as Weight)))
+    }
+    fn claim_payout(t: u32) -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add((392_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(4 as Weight))
+            .saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(4 as Weight))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(t as Weight)))
+    }
+    fn unbond(t: u32) -> Weight {
+        (233_000_000 as Weight)
+            .saturating_add((547_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().reads((22 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes((12 as Weight).saturating_mul(t as Weight)))
+    }
+    fn withdraw_unbonded(t: u32) -> Weight {
+        (141_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(T::DbWeight::get().reads(5 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(T::DbWeight::get().writes(5 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+    }
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+    fn create_pool(t: u32) -> Weight {
+        (32_000_000 as Weight)
+            .saturating_add((6_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn set_targets(t: u32) -> Weight {
+        (36_000_000 as Weight)
+            .saturating_add((9_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn rebond() -> Weight {
+        (205_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+    }
+    fn join(t: u32) -> Weight {
+        (321_000_000 as Weight)
+            .saturating_add((392_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(12 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((16 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(8 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(t as Weight)))
+    }
+    fn claim_payout(t: u32) -> Weight {
+        (104_000_000 as Weight)
+            .saturating_add((392_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(4 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(4 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(t as Weight)))
+    }
+    fn unbond(t: u32) -> Weight {
+        (233_000_000 as Weight)
+            .saturating_add((547_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((22 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((12 as Weight).saturating_mul(t as Weight)))
+    }
+    fn withdraw_unbonded(t: u32) -> Weight {
+        (141_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(t as Weight))
+            .saturating_add(RocksDbWeight::get().reads(5 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(t as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(5 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(t as Weight)))
+    }
+}
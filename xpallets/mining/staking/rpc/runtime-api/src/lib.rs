@@ -10,11 +10,16 @@ use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 use codec::Codec;
 
 pub use xpallet_mining_staking::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger, VoteWeight,
+    EraIndex, EraRewards, NominatorInfo, NominatorLedger, Unbonded, ValidatorEraRecord,
+    ValidatorInfo, ValidatorLedger, VoteWeight,
 };
 
 sp_api::decl_runtime_apis! {
     /// The API to query Staking info.
+    ///
+    /// The version 2 changes the layout of `ValidatorInfo` (`commission` and `pending_commission`)
+    /// and `NominatorInfo` (`restake`), and adds `era_rewards` and `validator_history`.
+    #[api_version(2)]
     pub trait XStakingApi<AccountId, Balance, VoteWeight, BlockNumber>
     where
         AccountId: Codec + Ord,
@@ -36,5 +41,11 @@ sp_api::decl_runtime_apis! {
 
         /// Get individual nominator information given the nominator AccountId.
         fn nominator_info_of(who: AccountId) -> NominatorInfo<BlockNumber>;
+
+        /// Get the rewards and slashes of all the validators in the given era.
+        fn era_rewards(era: EraIndex) -> EraRewards<AccountId, Balance>;
+
+        /// Get the rewards and slashes of the validator in the latest `eras` eras.
+        fn validator_history(who: AccountId, eras: u32) -> BTreeMap<EraIndex, ValidatorEraRecord<Balance>>;
     }
 }
@@ -17,8 +17,8 @@ use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use xp_rpc::{runtime_error_into_rpc_err, Result, RpcBalance, RpcVoteWeight};
 
 use xpallet_mining_staking_rpc_runtime_api::{
-    NominatorInfo, NominatorLedger, Unbonded, ValidatorInfo, ValidatorLedger,
-    XStakingApi as XStakingRuntimeApi,
+    EraIndex, EraRewards, NominatorInfo, NominatorLedger, Unbonded, ValidatorEraRecord,
+    ValidatorInfo, ValidatorLedger, XStakingApi as XStakingRuntimeApi,
 };
 
 /// XStaking RPC methods.
@@ -74,6 +74,23 @@ where
         who: AccountId,
         at: Option<BlockHash>,
     ) -> Result<NominatorInfo<BlockNumber>>;
+
+    /// Get the rewards and slashes of all the validators in the given era.
+    #[rpc(name = "xstaking_getEraRewards")]
+    fn era_rewards(
+        &self,
+        era: EraIndex,
+        at: Option<BlockHash>,
+    ) -> Result<EraRewards<AccountId, RpcBalance<Balance>>>;
+
+    /// Get the rewards and slashes of the validator in the latest `eras` eras.
+    #[rpc(name = "xstaking_getValidatorHistory")]
+    fn validator_history(
+        &self,
+        who: AccountId,
+        eras: u32,
+        at: Option<BlockHash>,
+    ) -> Result<BTreeMap<EraIndex, ValidatorEraRecord<RpcBalance<Balance>>>>;
 }
 
 /// A struct that implements the [`XStakingApi`].
@@ -232,4 +249,53 @@ where
             .nominator_info_of(&at, who)
             .map_err(runtime_error_into_rpc_err)?)
     }
+
+    fn era_rewards(
+        &self,
+        era: EraIndex,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<EraRewards<AccountId, RpcBalance<Balance>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .era_rewards(&at, era)
+            .map(|era_rewards| EraRewards {
+                total_reward: era_rewards.total_reward.into(),
+                validators: era_rewards
+                    .validators
+                    .into_iter()
+                    .map(|(validator, record)| (validator, into_rpc_record(record)))
+                    .collect(),
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+
+    fn validator_history(
+        &self,
+        who: AccountId,
+        eras: u32,
+        at: Option<<Block as BlockT>::Hash>,
+    ) -> Result<BTreeMap<EraIndex, ValidatorEraRecord<RpcBalance<Balance>>>> {
+        let api = self.client.runtime_api();
+        let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+        Ok(api
+            .validator_history(&at, who, eras)
+            .map(|history| {
+                history
+                    .into_iter()
+                    .map(|(era, record)| (era, into_rpc_record(record)))
+                    .collect()
+            })
+            .map_err(runtime_error_into_rpc_err)?)
+    }
+}
+
+fn into_rpc_record<Balance: Display + FromStr>(
+    record: ValidatorEraRecord<Balance>,
+) -> ValidatorEraRecord<RpcBalance<Balance>> {
+    ValidatorEraRecord {
+        reward: record.reward.into(),
+        slash: record.slash.into(),
+        reward_pot_balance: record.reward_pot_balance.into(),
+    }
 }
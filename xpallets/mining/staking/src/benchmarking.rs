@@ -90,6 +90,52 @@ benchmarks! {
         assert!(Module::<T>::staked_of(&validator)  == 80.into());
     }
 
+    unbond_all {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 100);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+    }: _(RawOrigin::Signed(validator.clone()), validator_lookup)
+    verify {
+        assert!(Module::<T>::bonded_to(&validator, &validator) == 0.into());
+    }
+
+    withdraw_unbonded_all {
+        let n in 1 .. 50;
+
+        let nominator = create_funded_user::<T>("nominator", u, 100 * n);
+        for i in 0..n {
+            let validator: T::AccountId = create_validator::<T>("withdraw_all_validator", 100 + i, 1000);
+            b_bond::<T>(nominator.clone(), validator.clone(), 10);
+            Module::<T>::unbond(
+                RawOrigin::Signed(nominator.clone()).into(),
+                T::Lookup::unlookup(validator),
+                10.into(),
+            )?;
+        }
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + Module::<T>::bonding_duration() + 1.into());
+    }: _(RawOrigin::Signed(nominator.clone()), n)
+    verify {
+        assert!(Module::<T>::total_locked_of(&nominator).is_zero());
+    }
+
+    cancel_unbond {
+        let nominator = create_funded_user::<T>("nominator", u, 100);
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 100);
+        b_bond::<T>(nominator.clone(), validator.clone(), 30);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+
+        Module::<T>::unbond(
+            RawOrigin::Signed(nominator.clone()).into(),
+            validator_lookup.clone(),
+            10.into(),
+        )?;
+    }: _(RawOrigin::Signed(nominator.clone()), validator_lookup, 0)
+    verify {
+        assert!(Module::<T>::bonded_to(&nominator, &validator) == 30.into());
+        assert!(Module::<T>::unbonded_chunks_of(&nominator, &validator).is_empty());
+    }
+
     rebond {
         let nominator = create_funded_user::<T>("nominator", u, 100);
         let validator1: T::AccountId = create_validator::<T>("validator1", 2, 100);
@@ -122,6 +168,51 @@ benchmarks! {
         assert!(T::Currency::total_balance(&validator) == balance_before + pot_balance.into());
     }
 
+    claim_all {
+        let n in 1 .. 50;
+
+        let nominator = create_funded_user::<T>("nominator", u, 100 * n);
+        for i in 0..n {
+            let validator: T::AccountId = create_validator::<T>("claim_all_validator", 100 + i, 1000);
+            b_bond::<T>(nominator.clone(), validator.clone(), 10);
+
+            let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+            T::Currency::make_free_balance_be(&validator_pot, 1000.into());
+            T::Currency::issue(1000.into());
+        }
+
+        let balance_before = T::Currency::free_balance(&nominator);
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 1.into());
+    }: _(RawOrigin::Signed(nominator.clone()), n)
+    verify {
+        assert!(T::Currency::free_balance(&nominator) > balance_before);
+    }
+
+    submit_election_solution {
+        let w in 4 .. 16;
+        let a in 1 .. 50;
+
+        let validators = (0..w)
+            .map(|i| create_validator::<T>("election_validator", 200 + i, 1000))
+            .collect::<Vec<_>>();
+        for i in 0..a {
+            let nominator = create_funded_user::<T>("election_nominator", i, 100);
+            b_bond::<T>(nominator.clone(), validators[(i % w) as usize].clone(), 10);
+            b_bond::<T>(nominator, validators[((i + 1) % w) as usize].clone(), 10);
+        }
+        ValidatorCount::put(w);
+        IsCurrentSessionFinal::put(true);
+        Module::<T>::create_election_snapshot();
+
+        let solution = Module::<T>::compute_election_solution().unwrap();
+        let era = solution.era;
+    }: _(RawOrigin::None, solution)
+    verify {
+        assert!(Module::<T>::queued_elected().map(|q| q.era) == Some(era));
+    }
+
     chill {
         let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
         if !Module::<T>::is_validator(&validator) {
@@ -178,12 +269,92 @@ benchmarks! {
         assert_eq!(MinimumPenalty::<T>::get(), c.into());
     }
 
+    set_slash_defer_duration {
+        let c = 24u32;
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(SlashDeferDuration::get(), c);
+    }
+
+    set_nominator_slashing {
+        let fraction = Perbill::from_percent(10);
+    }: _(RawOrigin::Root, true, fraction)
+    verify {
+        assert!(NominatorSlashingEnabled::get());
+        assert_eq!(NominatorSlashFraction::get(), fraction);
+    }
+
+    cancel_deferred_slash {
+        let s in 1 .. 1000;
+
+        let validator: T::AccountId = account("validator", 0, SEED);
+        let unapplied = (0..1000)
+            .map(|_| UnappliedSlash {
+                validator: validator.clone(),
+                penalty: 10.into(),
+                slash_session: 0,
+                others: Vec::new(),
+            })
+            .collect::<Vec<_>>();
+        UnappliedSlashes::<T>::insert(1, unapplied);
+        let slash_indices = (0..s).collect::<Vec<_>>();
+    }: _(RawOrigin::Root, 1, slash_indices)
+    verify {
+        assert_eq!(UnappliedSlashes::<T>::get(1).len(), (1000 - s) as usize);
+    }
+
     set_sessions_per_era {
         let c = 1000u32;
     }: _(RawOrigin::Root, c)
     verify {
         assert_eq!(SessionsPerEra::get(), c);
     }
+
+    set_commission {
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let commission = Perbill::from_percent(20);
+    }: _(RawOrigin::Signed(validator.clone()), commission)
+    verify {
+        assert_eq!(
+            Validators::<T>::get(&validator).pending_commission.map(|p| p.commission),
+            Some(commission)
+        );
+    }
+
+    set_maximum_validator_commission {
+        let c = Perbill::from_percent(50);
+    }: _(RawOrigin::Root, c)
+    verify {
+        assert_eq!(MaximumValidatorCommission::get(), c);
+    }
+
+    set_restake {
+        let nominator = create_funded_user::<T>("nominator", u, 100);
+    }: _(RawOrigin::Signed(nominator.clone()), true)
+    verify {
+        assert!(Module::<T>::is_restake(&nominator));
+    }
+
+    payout_for {
+        let caller = create_funded_user::<T>("caller", u, 100);
+        let validator: T::AccountId = create_validator::<T>("validator", 2, 1000);
+        let validator_lookup: <T::Lookup as StaticLookup>::Source = T::Lookup::unlookup(validator.clone());
+        Module::<T>::set_restake(RawOrigin::Signed(validator.clone()).into(), true)?;
+
+        let validator_pot = T::DetermineRewardPotAccount::reward_pot_account_for(&validator);
+
+        let pot_balance = 50;
+        T::Currency::make_free_balance_be(&validator_pot, pot_balance.into());
+        T::Currency::issue(pot_balance.into());
+
+        let bonded_before = Module::<T>::bonded_to(&validator, &validator);
+
+        let block_number: T::BlockNumber = frame_system::Module::<T>::block_number();
+        frame_system::Module::<T>::set_block_number(block_number + 1.into());
+    }: _(RawOrigin::Signed(caller), validator_lookup.clone(), validator_lookup)
+    verify {
+        assert!(Module::<T>::bonded_to(&validator, &validator) == bonded_before + pot_balance.into());
+    }
 }
 
 #[cfg(test)]
@@ -207,6 +378,18 @@ mod tests {
             assert_ok!(test_benchmark_set_minimum_validator_count::<Test>());
             assert_ok!(test_benchmark_set_bonding_duration::<Test>());
             assert_ok!(test_benchmark_set_validator_bonding_duration::<Test>());
+            assert_ok!(test_benchmark_set_commission::<Test>());
+            assert_ok!(test_benchmark_set_maximum_validator_commission::<Test>());
+            assert_ok!(test_benchmark_set_restake::<Test>());
+            assert_ok!(test_benchmark_payout_for::<Test>());
+            assert_ok!(test_benchmark_claim_all::<Test>());
+            assert_ok!(test_benchmark_submit_election_solution::<Test>());
+            assert_ok!(test_benchmark_set_slash_defer_duration::<Test>());
+            assert_ok!(test_benchmark_cancel_deferred_slash::<Test>());
+            assert_ok!(test_benchmark_set_nominator_slashing::<Test>());
+            assert_ok!(test_benchmark_unbond_all::<Test>());
+            assert_ok!(test_benchmark_withdraw_unbonded_all::<Test>());
+            assert_ok!(test_benchmark_cancel_unbond::<Test>());
         });
     }
 }
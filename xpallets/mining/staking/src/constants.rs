@@ -1,6 +1,9 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
 use frame_support::traits::LockIdentifier;
+use sp_runtime::Perbill;
+
+use crate::EraIndex;
 
 pub const STAKING_ID: LockIdentifier = *b"staking ";
 
@@ -36,3 +39,16 @@ pub const DEFAULT_BONDING_DURATION: u64 = DEFAULT_BLOCKS_PER_SESSION * 12 * 24 *
 
 /// The default bonding duration for validator is 3 * 10 days.
 pub const DEFAULT_VALIDATOR_BONDING_DURATION: u64 = DEFAULT_BONDING_DURATION * 10;
+
+/// The commission of a newly registered validator, 10% of the session reward
+/// goes to the validator directly, the rest 90% goes to its reward pot.
+pub const DEFAULT_VALIDATOR_COMMISSION: Perbill = Perbill::from_parts(100_000_000);
+
+/// The number of eras a commission change has to wait before taking effect,
+/// counted from the next era, so that the nominators have at least one whole
+/// era to react.
+pub const COMMISSION_CHANGE_DELAY: EraIndex = 1;
+
+/// The number of eras of which the reward and slash records are kept,
+/// the records of older eras are pruned at the start of each era.
+pub const HISTORY_DEPTH: EraIndex = 84;
@@ -1,7 +1,63 @@
 // Copyright 2019-2020 ChainX Project Authors. Licensed under GPL-3.0.
 
+use sp_npos_elections::{is_score_better, seq_phragmen, ElectionScore, ExtendedBalance};
+use sp_std::collections::btree_set::BTreeSet;
+
+use frame_system::offchain::SubmitTransaction;
+
 use super::*;
 
+/// A voter in the election, (voter, total nomination, nominated candidates).
+pub type ElectionVoter<AccountId> = (AccountId, sp_npos_elections::VoteWeight, Vec<AccountId>);
+
+/// Strategy for electing the validators of a new era.
+pub trait ElectionStrategy<T: Trait> {
+    /// Returns the elected validators given the number of desired validators and
+    /// the qualified candidates sorted by their total nominations.
+    ///
+    /// Returns `None` if the election failed.
+    fn elect(
+        desired: usize,
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+    ) -> Option<Vec<T::AccountId>>;
+}
+
+/// Elect the candidates with the most total nominations.
+pub struct TopVotes;
+
+impl<T: Trait> ElectionStrategy<T> for TopVotes {
+    fn elect(
+        desired: usize,
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+    ) -> Option<Vec<T::AccountId>> {
+        Some(
+            candidates
+                .into_iter()
+                .take(desired)
+                .map(|(_, v)| v)
+                .collect(),
+        )
+    }
+}
+
+/// Elect the validators using the sequential Phragmén method.
+///
+/// Each nominator is treated as a voter approving all the candidates it has nominated,
+/// with the sum of its nominations as the voting power, which helps spread the stake
+/// across more validators than taking the ones with the most nominations.
+pub struct SequentialPhragmen;
+
+impl<T: Trait> ElectionStrategy<T> for SequentialPhragmen {
+    fn elect(
+        desired: usize,
+        candidates: Vec<(BalanceOf<T>, T::AccountId)>,
+    ) -> Option<Vec<T::AccountId>> {
+        let candidates = candidates.into_iter().map(|(_, v)| v).collect::<Vec<_>>();
+        let voters = Module::<T>::election_voters(&candidates.iter().cloned().collect());
+        Module::<T>::do_phragmen(desired, candidates, voters).map(|(winners, _)| winners)
+    }
+}
+
 impl<T: Trait> Module<T> {
     /// Returns a new validator set for the new era.
     pub(crate) fn new_era(start_session_index: SessionIndex) -> Option<Vec<T::AccountId>> {
@@ -11,6 +67,8 @@ impl<T: Trait> Module<T> {
             s.unwrap()
         });
         ErasStartSessionIndex::insert(&current_era, &start_session_index);
+        IsCurrentSessionFinal::put(false);
+        Self::kill_election_snapshot();
 
         // Set staking information for new era.
         let maybe_new_validators = Self::select_and_update_validators(current_era);
@@ -31,13 +89,17 @@ impl<T: Trait> Module<T> {
         Self::is_active(who) && Self::meet_candidate_threshold(who)
     }
 
+    /// Returns true if the candidate has enough self-bonded and total nominations.
+    fn is_threshold_satisfied(who: &T::AccountId) -> bool {
+        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
+        Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total
+    }
+
     /// Returns true if the candidate meets the minimum candidate threshold.
     ///
     /// Otherwise the candidate will be **forced to be chilled**.
     fn meet_candidate_threshold(who: &T::AccountId) -> bool {
-        let BondRequirement { self_bonded, total } = Self::validator_candidate_requirement();
-        let threshold_satisfied =
-            Self::validator_self_bonded(who) >= self_bonded && Self::total_votes_of(who) >= total;
+        let threshold_satisfied = Self::is_threshold_satisfied(who);
 
         if !threshold_satisfied && Self::try_force_chilled(who).is_ok() {
             xp_logging::info!("[meet_candidate_threshold] Force {:?} to be inactive since it doesn't meet the minimum bond requirement", who);
@@ -46,43 +108,309 @@ impl<T: Trait> Module<T> {
         threshold_satisfied
     }
 
-    /// Filters out all the qualified validator candidates, sorted by the total nominations.
-    fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
-        let mut candidates = Self::validator_set()
-            .filter(Self::is_qualified_candidate)
+    /// Sorts the candidates by the total nominations.
+    fn sort_candidates(
+        candidates: impl Iterator<Item = T::AccountId>,
+    ) -> Vec<(BalanceOf<T>, T::AccountId)> {
+        let mut candidates = candidates
             .map(|v| (Self::total_votes_of(&v), v))
             .collect::<Vec<_>>();
         candidates.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(&b1));
         candidates
     }
 
+    /// Filters out all the qualified validator candidates, sorted by the total nominations.
+    fn filter_out_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
+        Self::sort_candidates(Self::validator_set().filter(Self::is_qualified_candidate))
+    }
+
+    /// Returns all the qualified validator candidates, sorted by the total nominations.
+    ///
+    /// Unlike `filter_out_candidates`, the unqualified ones are not forced to be chilled.
+    pub(crate) fn election_candidates() -> Vec<(BalanceOf<T>, T::AccountId)> {
+        Self::sort_candidates(
+            Self::validator_set().filter(|v| Self::is_active(v) && Self::is_threshold_satisfied(v)),
+        )
+    }
+
+    /// Returns the voters of the election given the candidates.
+    ///
+    /// The nominations to the non-candidates are ignored.
+    pub(crate) fn election_voters(
+        candidates: &BTreeSet<T::AccountId>,
+    ) -> Vec<ElectionVoter<T::AccountId>> {
+        use frame_support::IterableStorageDoubleMap;
+        let mut voters = BTreeMap::<T::AccountId, (sp_npos_elections::VoteWeight, Vec<_>)>::new();
+        for (nominator, validator, ledger) in Nominations::<T>::iter() {
+            if ledger.nomination.is_zero() || !candidates.contains(&validator) {
+                continue;
+            }
+            let (stake, targets) = voters.entry(nominator).or_default();
+            *stake = stake.saturating_add(ledger.nomination.saturated_into());
+            targets.push(validator);
+        }
+        voters
+            .into_iter()
+            .map(|(voter, (stake, targets))| (voter, stake, targets))
+            .collect()
+    }
+
+    /// Runs the sequential Phragmén election, returns the winners and the assignments.
+    pub(crate) fn do_phragmen(
+        desired: usize,
+        candidates: Vec<T::AccountId>,
+        voters: Vec<ElectionVoter<T::AccountId>>,
+    ) -> Option<(Vec<T::AccountId>, Vec<ElectionAssignment<T::AccountId>>)> {
+        match seq_phragmen::<T::AccountId, Perbill>(desired, candidates, voters, None) {
+            Ok(result) => {
+                let winners = result.winners.into_iter().map(|(v, _)| v).collect();
+                let assignments = result
+                    .assignments
+                    .into_iter()
+                    .map(|assignment| (assignment.who, assignment.distribution))
+                    .collect();
+                Some((winners, assignments))
+            }
+            Err(e) => {
+                warn!("[do_phragmen] Phragmén election failed: {:?}", e);
+                None
+            }
+        }
+    }
+
+    /// Returns the score of the election solution, i.e., [min support, sum of supports,
+    /// sum of supports squared].
+    ///
+    /// Each assignment must distribute the stake of an unique voter to the winners it
+    /// has nominated, no more than the whole stake.
+    pub(crate) fn evaluate_election_solution(
+        winners: &[T::AccountId],
+        assignments: &[ElectionAssignment<T::AccountId>],
+        voters: &[ElectionVoter<T::AccountId>],
+    ) -> Result<ElectionScore, Error<T>> {
+        let voters = voters
+            .iter()
+            .map(|(voter, stake, targets)| (voter, (*stake, targets)))
+            .collect::<BTreeMap<_, _>>();
+        let mut supports = winners
+            .iter()
+            .map(|w| (w, ExtendedBalance::zero()))
+            .collect::<BTreeMap<_, _>>();
+
+        let mut assigned = BTreeSet::new();
+        for (voter, distribution) in assignments {
+            ensure!(
+                assigned.insert(voter),
+                Error::<T>::OffchainElectionBogusAssignment
+            );
+            let (stake, targets) = voters
+                .get(voter)
+                .ok_or(Error::<T>::OffchainElectionBogusAssignment)?;
+
+            let mut total_parts = 0u64;
+            for (target, ratio) in distribution {
+                ensure!(
+                    targets.contains(target),
+                    Error::<T>::OffchainElectionBogusAssignment
+                );
+                let support = supports
+                    .get_mut(target)
+                    .ok_or(Error::<T>::OffchainElectionBogusAssignment)?;
+                *support = support.saturating_add(ratio.mul_floor(ExtendedBalance::from(*stake)));
+                total_parts += u64::from(ratio.deconstruct());
+            }
+            ensure!(
+                total_parts <= u64::from(Perbill::one().deconstruct()),
+                Error::<T>::OffchainElectionBogusAssignment
+            );
+        }
+
+        let min_support = supports.values().min().copied().unwrap_or_default();
+        let (sum, sum_squared) = supports.values().fold(
+            (ExtendedBalance::zero(), ExtendedBalance::zero()),
+            |(sum, sum_squared), s| {
+                (
+                    sum.saturating_add(*s),
+                    sum_squared.saturating_add(s.saturating_mul(*s)),
+                )
+            },
+        );
+        Ok([min_support, sum, sum_squared])
+    }
+
+    /// Returns the number of validators expected to be elected given the number of candidates.
+    fn desired_validator_count(candidates: usize) -> usize {
+        (ValidatorCount::get() as usize).min(candidates)
+    }
+
+    /// Takes the snapshot of the election candidates and voters at the start of the final
+    /// session of the era, against which the offchain election solutions are computed
+    /// and checked.
+    pub(crate) fn create_election_snapshot() {
+        if !T::OffchainElection::get() {
+            return;
+        }
+        let candidates = Self::election_candidates()
+            .into_iter()
+            .map(|(_, v)| v)
+            .collect::<Vec<_>>();
+        let voters = Self::election_voters(&candidates.iter().cloned().collect());
+        SnapshotCandidates::<T>::put(candidates);
+        SnapshotVoters::<T>::put(voters);
+    }
+
+    /// Removes the election snapshot once the era is over.
+    fn kill_election_snapshot() {
+        SnapshotCandidates::<T>::kill();
+        SnapshotVoters::<T>::kill();
+    }
+
+    /// Computes the Phragmén solution for the next era from the election snapshot,
+    /// used by the offchain worker.
+    pub fn compute_election_solution() -> Option<ElectionSolution<T::AccountId>> {
+        let era = Self::current_era().map(|e| e + 1).unwrap_or(0);
+        let candidates = Self::snapshot_candidates()?;
+        let voters = Self::snapshot_voters()?;
+        if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+            return None;
+        }
+        let desired = Self::desired_validator_count(candidates.len());
+        let (winners, assignments) = Self::do_phragmen(desired, candidates, voters.clone())?;
+        let score = Self::evaluate_election_solution(&winners, &assignments, &voters).ok()?;
+        Some(ElectionSolution {
+            era,
+            winners,
+            assignments,
+            score,
+        })
+    }
+
+    /// Computes and submits the election solution for the next era if it is better
+    /// than the queued one.
+    pub(crate) fn offchain_election() {
+        let solution = match Self::compute_election_solution() {
+            Some(solution) => solution,
+            None => return,
+        };
+        if let Some(queued) = Self::queued_elected() {
+            if queued.era == solution.era
+                && !is_score_better(solution.score, queued.score, Perbill::zero())
+            {
+                return;
+            }
+        }
+        debug!(
+            "[offchain_election] era:{}, winners:{:?}, score:{:?}",
+            solution.era, solution.winners, solution.score
+        );
+        let call = Call::submit_election_solution(solution);
+        if let Err(e) = SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()) {
+            error!("[offchain_election] Failed to submit the solution: {:?}", e);
+        }
+    }
+
+    /// Returns Ok if the election solution is valid and better than the queued one.
+    pub(crate) fn check_election_solution(
+        solution: &ElectionSolution<T::AccountId>,
+    ) -> Result<(), Error<T>> {
+        let ElectionSolution {
+            era,
+            winners,
+            assignments,
+            score,
+        } = solution;
+
+        ensure!(
+            T::OffchainElection::get(),
+            Error::<T>::OffchainElectionDisabled
+        );
+        let next_era = Self::current_era().map(|e| e + 1).unwrap_or(0);
+        ensure!(
+            *era == next_era && Self::is_current_session_final(),
+            Error::<T>::OffchainElectionEarlySubmission
+        );
+        if let Some(queued) = Self::queued_elected() {
+            if queued.era == *era {
+                ensure!(
+                    is_score_better(*score, queued.score, Perbill::zero()),
+                    Error::<T>::OffchainElectionWeakSubmission
+                );
+            }
+        }
+
+        let candidates = Self::snapshot_candidates()
+            .ok_or(Error::<T>::OffchainElectionEarlySubmission)?
+            .into_iter()
+            .collect::<BTreeSet<_>>();
+        let voters = Self::snapshot_voters().ok_or(Error::<T>::OffchainElectionEarlySubmission)?;
+        let desired = Self::desired_validator_count(candidates.len());
+        ensure!(
+            winners.len() == desired
+                && desired >= Self::reasonable_minimum_validator_count() as usize,
+            Error::<T>::OffchainElectionBogusWinner
+        );
+        let mut unique_winners = BTreeSet::new();
+        ensure!(
+            winners
+                .iter()
+                .all(|w| candidates.contains(w) && unique_winners.insert(w)),
+            Error::<T>::OffchainElectionBogusWinner
+        );
+
+        let computed_score = Self::evaluate_election_solution(winners, assignments, &voters)?;
+        ensure!(
+            computed_score == *score,
+            Error::<T>::OffchainElectionBogusScore
+        );
+
+        Ok(())
+    }
+
+    /// Returns the queued offchain election result of era `era` if all the winners
+    /// are still the qualified candidates.
+    fn take_queued_elected(
+        era: EraIndex,
+        candidates: &[(BalanceOf<T>, T::AccountId)],
+    ) -> Option<Vec<T::AccountId>> {
+        let queued = QueuedElected::<T>::take()?;
+        if queued.era == era
+            && queued
+                .winners
+                .iter()
+                .all(|w| candidates.iter().any(|(_, c)| c == w))
+        {
+            Some(queued.winners)
+        } else {
+            None
+        }
+    }
+
     /// Selects the new validator set at the end of the era.
     ///
-    /// Order potential validators by their total nominations and
-    /// choose the top-most ValidatorCount::get() of them.
+    /// Use the queued offchain election result if any, otherwise elect the validators
+    /// using `T::ElectionStrategy` on chain.
     ///
     /// This should only be called at the end of an era.
-    fn select_and_update_validators(_current_era: EraIndex) -> Option<Vec<T::AccountId>> {
-        // TODO: might move to offchain worker solution in the future.
-        // Currently there is no performance issue practically.
+    fn select_and_update_validators(current_era: EraIndex) -> Option<Vec<T::AccountId>> {
         let candidates = Self::filter_out_candidates();
         debug!("[select_and_update_validators] candidates:{:?}", candidates);
 
         // Avoid reevaluate validator set if it would leave us with fewer than the minimum
         // needed validators.
         if candidates.len() < Self::reasonable_minimum_validator_count() as usize {
+            QueuedElected::<T>::kill();
             return None;
         }
 
-        let desired_validator_count = ValidatorCount::get() as usize;
-
-        let validators = candidates
-            .into_iter()
-            .take(desired_validator_count)
-            .map(|(_, v)| v)
-            .collect::<Vec<_>>();
+        if let Some(winners) = Self::take_queued_elected(current_era, &candidates) {
+            debug!(
+                "[select_and_update_validators] use the offchain election result:{:?}",
+                winners
+            );
+            return Some(winners);
+        }
 
-        // Always return Some(new_validators).
-        Some(validators)
+        let desired_validator_count = Self::desired_validator_count(candidates.len());
+        T::ElectionStrategy::elect(desired_validator_count, candidates)
     }
 }
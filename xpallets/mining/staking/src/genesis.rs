@@ -36,6 +36,7 @@ impl<T: Trait> Module<T> {
     ) -> DispatchResult {
         if !value.is_zero() {
             Self::bond_reserve(sender, value);
+            Self::index_nomination(sender, target);
             Nominations::<T>::mutate(sender, target, |nominator| {
                 nominator.nomination = value;
             });
@@ -59,6 +60,7 @@ impl<T: Trait> Module<T> {
             Error::<T>::NoMoreUnbondChunks
         );
         Self::unbond_reserve(sender, value)?;
+        Self::index_nomination(sender, target);
         Self::mutate_unbonded_chunks(sender, target, value, locked_until);
         Ok(())
     }
@@ -68,6 +70,7 @@ impl<T: Trait> Module<T> {
         validator: &T::AccountId,
         new_weight: VoteWeight,
     ) {
+        Self::index_nomination(nominator, validator);
         Nominations::<T>::mutate(nominator, validator, |nominator| {
             nominator.last_vote_weight = new_weight;
         });
@@ -155,6 +155,7 @@ impl<T: Trait> Module<T> {
         current_block: T::BlockNumber,
         delta: Delta<BalanceOf<T>>,
     ) {
+        Self::index_nomination(nominator, validator);
         Nominations::<T>::mutate(nominator, validator, |claimer| {
             claimer.nomination = delta.calculate(claimer.nomination);
             claimer.last_vote_weight = new_weight;
@@ -162,6 +163,13 @@ impl<T: Trait> Module<T> {
         });
     }
 
+    /// Adds `nominator` to the nominators of `validator` if it's a new nomination.
+    pub(crate) fn index_nomination(nominator: &T::AccountId, validator: &T::AccountId) {
+        if !Nominations::<T>::contains_key(nominator, validator) {
+            NominatorsOf::<T>::insert(validator, nominator, ());
+        }
+    }
+
     ///
     pub(crate) fn set_validator_vote_weight(
         who: &T::AccountId,
@@ -216,6 +224,10 @@ impl<T: Trait> Claim<T::AccountId> for Module<T> {
         Self::update_claimer_vote_weight_on_claim(claimer, claimee, current_block);
         Self::update_claimee_vote_weight_on_claim(claimee, new_target_weight, current_block);
 
+        if Self::is_restake(claimer) {
+            Self::apply_restake(claimer, claimee, dividend);
+        }
+
         Ok(())
     }
 }
@@ -228,7 +240,8 @@ impl<T: Trait> Module<T> {
 
         // Reset the session offenders.
         if let Some(offenders) = SessionOffenders::<T>::take() {
-            let force_chilled = Self::slash_offenders_in_session(offenders, validator_rewards);
+            let force_chilled =
+                Self::slash_offenders_in_session(offenders, validator_rewards, session_index);
             if !force_chilled.is_empty() {
                 debug!("Force chilled:{:?}", force_chilled);
                 Self::deposit_event(Event::<T>::ForceChilled(session_index, force_chilled));
@@ -268,6 +281,7 @@ impl<T: Trait> Module<T> {
                     // Either `ForceNone`, or `NotForcing && era_length < T::SessionsPerEra::get()`.
                     if era_length + 1 == ideal_era_length {
                         IsCurrentSessionFinal::put(true);
+                        Self::create_election_snapshot();
                     } else if era_length >= ideal_era_length {
                         // Should only happen when we are ready to trigger an era but we have ForceNone,
                         // otherwise previous arm would short circuit.
@@ -328,11 +342,15 @@ impl<T: Trait> Module<T> {
         }
     }
 
-    /// * Increment `active_era.index`,
+    /// * Close the records of the ended era,
+    /// * increment `active_era.index`,
     /// * reset `active_era.start`,
-    /// * update `BondedEras` and apply slashes.
-    fn start_era(_start_session: SessionIndex) {
-        let _active_era = ActiveEra::mutate(|active_era| {
+    /// * apply the due commission changes and deferred slashes.
+    fn start_era(start_session: SessionIndex) {
+        if let Some(ended_era) = Self::active_era() {
+            Self::close_era_records(ended_era.index);
+        }
+        let active_era = ActiveEra::mutate(|active_era| {
             let new_index = active_era.as_ref().map(|info| info.index + 1).unwrap_or(0);
             *active_era = Some(ActiveEraInfo {
                 index: new_index,
@@ -341,6 +359,8 @@ impl<T: Trait> Module<T> {
             });
             new_index
         });
+        Self::apply_pending_commissions(active_era);
+        Self::apply_unapplied_slashes(active_era, start_session);
     }
 
     /// Compute payout for era.
@@ -383,7 +403,8 @@ pub type IdentificationTuple<T> = (
 type Offender<T> = IdentificationTuple<T>;
 
 /// This is intended to be used with `FilterHistoricalOffences` in Substrate/Staking.
-/// In ChainX, we always apply the slash immediately, no deferred slash.
+/// In ChainX, the offenders are slashed at the start of next session, or deferred
+/// for `SlashDeferDuration` eras if it's not zero.
 impl<T: Trait> OnOffenceHandler<Reporter<T>, IdentificationTuple<T>, Weight> for Module<T>
 where
     T: pallet_session::Trait<ValidatorId = <T as frame_system::Trait>::AccountId>,
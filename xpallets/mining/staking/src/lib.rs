@@ -33,25 +33,37 @@ mod tests;
 use sp_std::prelude::*;
 
 use frame_support::{
-    decl_error, decl_event, decl_module, decl_storage, ensure,
+    decl_error, decl_event, decl_module, decl_storage,
+    dispatch::{DispatchError, DispatchResultWithPostInfo},
+    ensure,
     storage::IterableStorageMap,
-    traits::{Currency, ExistenceRequirement, Get, LockableCurrency, WithdrawReasons},
+    traits::{
+        Currency, EnsureOrigin, ExistenceRequirement, Get, LockableCurrency, WithdrawReasons,
+    },
+    weights::Weight,
 };
-use frame_system::{ensure_root, ensure_signed};
+use frame_system::{ensure_none, ensure_root, ensure_signed, offchain::SendTransactionTypes};
 use sp_runtime::{
     traits::{Convert, SaturatedConversion, Saturating, StaticLookup, Zero},
+    transaction_validity::{
+        InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
+        ValidTransaction,
+    },
     DispatchResult, Perbill,
 };
 use sp_std::collections::btree_map::BTreeMap;
 
+use orml_utilities::with_transaction_result;
+
 use chainx_primitives::ReferralId;
-use xp_logging::debug;
+use xp_logging::{debug, error, info, warn};
 pub use xp_mining_common::RewardPotAccountFor;
 use xp_mining_common::{Claim, ComputeMiningWeight, Delta, ZeroMiningWeightError};
 use xp_mining_staking::{AssetMining, SessionIndex, UnbondedIndex};
 use xpallet_support::traits::TreasuryAccount;
 
 use self::constants::*;
+pub use self::election::{ElectionStrategy, ElectionVoter, SequentialPhragmen, TopVotes};
 pub use self::impls::{IdentificationTuple, SimpleValidatorRewardPotAccountDeterminer};
 pub use self::rpc::*;
 pub use self::types::*;
@@ -63,7 +75,7 @@ pub type BalanceOf<T> =
 /// Counter for the number of eras that have passed.
 pub type EraIndex = u32;
 
-pub trait Trait: frame_system::Trait {
+pub trait Trait: frame_system::Trait + SendTransactionTypes<Call<Self>> {
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as frame_system::Trait>::Event>;
 
@@ -96,6 +108,21 @@ pub trait Trait: frame_system::Trait {
     /// there is a chance the authority will produce a block and they won't be necessary.
     type SessionDuration: Get<Self::BlockNumber>;
 
+    /// The origin which can cancel a deferred slash.
+    type SlashCancelOrigin: EnsureOrigin<Self::Origin>;
+
+    /// The strategy for electing the validators on chain.
+    type ElectionStrategy: ElectionStrategy<Self>;
+
+    /// Whether the offchain workers compute and submit the election solution of the next era.
+    type OffchainElection: Get<bool>;
+
+    /// A configuration for base priority of unsigned transactions.
+    ///
+    /// This is exposed so that it can be tuned for particular runtime, when
+    /// multiple pallets send unsigned transactions.
+    type UnsignedPriority: Get<TransactionPriority>;
+
     /// Weight information for extrinsics in this pallet.
     type WeightInfo: WeightInfo;
 }
@@ -157,6 +184,16 @@ decl_storage! {
             double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
             => NominatorLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>;
 
+        /// The map from validator to all the accounts that have ever bonded to it,
+        /// including the validator itself.
+        pub NominatorsOf:
+            double_map hasher(twox_64_concat) T::AccountId, hasher(twox_64_concat) T::AccountId
+            => ();
+
+        /// The nominators that have opted in to bond the claimed dividend back automatically.
+        pub RestakeOf get(fn is_restake):
+            map hasher(twox_64_concat) T::AccountId => bool;
+
         /// The map from nominator to the block number of last `rebond` operation.
         pub LastRebondOf get(fn last_rebond_of):
             map hasher(twox_64_concat) T::AccountId => Option<T::BlockNumber>;
@@ -184,6 +221,15 @@ decl_storage! {
         pub ErasStartSessionIndex get(fn eras_start_session_index):
             map hasher(twox_64_concat) EraIndex => Option<SessionIndex>;
 
+        /// Total staking reward issued to the validators for the last `HISTORY_DEPTH` eras.
+        pub ErasStakingReward get(fn eras_staking_reward):
+            map hasher(twox_64_concat) EraIndex => BalanceOf<T>;
+
+        /// Reward and slash records of each validator for the last `HISTORY_DEPTH` eras.
+        pub ErasValidatorRecords get(fn eras_validator_records):
+            double_map hasher(twox_64_concat) EraIndex, hasher(twox_64_concat) T::AccountId
+            => ValidatorEraRecord<BalanceOf<T>>;
+
         /// True if the current **planned** session is final. Note that this does not take era
         /// forcing into account.
         pub IsCurrentSessionFinal get(fn is_current_session_final): bool = false;
@@ -194,10 +240,40 @@ decl_storage! {
         /// Minimum penalty for each slash.
         pub MinimumPenalty get(fn minimum_penalty) config(): BalanceOf<T>;
 
+        /// Number of eras that slashes are deferred by, after computation.
+        ///
+        /// The slashes are applied immediately if it's zero.
+        pub SlashDeferDuration get(fn slash_defer_duration) config(): EraIndex;
+
+        /// Whether to slash the nominators of the offending validator as well.
+        pub NominatorSlashingEnabled get(fn nominator_slashing_enabled): bool;
+
+        /// Fraction of the balances bonded to the offending validator slashed from each nominator.
+        pub NominatorSlashFraction get(fn nominator_slash_fraction): Perbill;
+
+        /// All the unapplied slashes that are queued for later, indexed by the era to apply.
+        pub UnappliedSlashes get(fn unapplied_slashes):
+            map hasher(twox_64_concat) EraIndex => Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>;
+
         /// Immortal validators will always be elected if any.
         ///
         /// Immortals will be intialized from the genesis validators.
         Immortals get(fn immortals): Option<Vec<T::AccountId>>;
+
+        /// The maximum commission a validator can charge, set by root.
+        pub MaximumValidatorCommission get(fn maximum_validator_commission): Perbill = Perbill::one();
+
+        /// The checked offchain election result to be applied at the start of next era.
+        pub QueuedElected get(fn queued_elected): Option<QueuedElectionResult<T::AccountId>>;
+
+        /// Snapshot of the election candidates taken at the start of the final session of the era.
+        pub SnapshotCandidates get(fn snapshot_candidates): Option<Vec<T::AccountId>>;
+
+        /// Snapshot of the election voters taken at the start of the final session of the era.
+        pub SnapshotVoters get(fn snapshot_voters): Option<Vec<ElectionVoter<T::AccountId>>>;
+
+        /// Storage version of the module.
+        pub StorageVersion get(fn storage_version) build(|_: &GenesisConfig<T>| Releases::V3_0_0): Releases;
     }
 
     add_extra_genesis {
@@ -266,10 +342,28 @@ decl_event!(
         Claimed(AccountId, AccountId, Balance),
         /// The nominator withdrew the locked balance from the unlocking queue. [nominator, amount]
         Withdrawn(AccountId, Balance),
+        /// A nominator cancelled an unbonded chunk and bonded it back. [nominator, validator, amount]
+        UnbondCancelled(AccountId, AccountId, Balance),
         /// Offenders were forcibly to be chilled due to insufficient reward pot balance. [session_index, chilled_validators]
         ForceChilled(SessionIndex, Vec<AccountId>),
         /// Unlock the unbonded withdrawal by force. [account]
         ForceAllWithdrawn(AccountId),
+        /// A validator scheduled a commission change. [validator, commission, effective_era]
+        CommissionChangeScheduled(AccountId, Perbill, EraIndex),
+        /// The scheduled commission of a validator took effect. [validator, commission]
+        CommissionUpdated(AccountId, Perbill),
+        /// A nominator turned on/off the restake mode. [nominator, enabled]
+        RestakeSet(AccountId, bool),
+        /// A nominator failed to claim the dividend from a validator in `claim_all`. [nominator, validator, error]
+        ClaimFailed(AccountId, AccountId, DispatchError),
+        /// A nominator was slashed due to the offence of the validator. [nominator, validator, amount]
+        NominatorSlashed(AccountId, AccountId, Balance),
+        /// The slash of a validator was deferred. [validator, penalty, apply_era]
+        SlashDeferred(AccountId, Balance, EraIndex),
+        /// A deferred slash of a validator was cancelled. [validator, penalty, apply_era]
+        SlashCancelled(AccountId, Balance, EraIndex),
+        /// A better offchain election solution was queued for the era. [era_index]
+        ElectionSolutionQueued(EraIndex),
     }
 );
 
@@ -316,6 +410,26 @@ decl_error! {
         XssCheckFailed,
         /// Failed to allocate the dividend.
         AllocateDividendFailed,
+        /// The commission exceeds `MaximumValidatorCommission`.
+        CommissionTooHigh,
+        /// The slash indices to cancel are empty, unsorted or duplicated.
+        NotSortedAndUnique,
+        /// Can not find the deferred slash given the index.
+        InvalidSlashIndex,
+        /// The offchain election is disabled.
+        OffchainElectionDisabled,
+        /// The election solution is not for the next era or submitted before the final session.
+        OffchainElectionEarlySubmission,
+        /// The election solution has an invalid or unexpected number of winners.
+        OffchainElectionBogusWinner,
+        /// The election solution has an invalid assignment.
+        OffchainElectionBogusAssignment,
+        /// The claimed score does not match with the one computed on chain.
+        OffchainElectionBogusScore,
+        /// The election solution is not better than the queued one.
+        OffchainElectionWeakSubmission,
+        /// The number of nominations exceeds the given witness.
+        InvalidNominationsWitness,
     }
 }
 
@@ -337,6 +451,19 @@ decl_module! {
 
         fn deposit_event() = default;
 
+        fn on_runtime_upgrade() -> Weight {
+            Self::migrate_validator_profile().saturating_add(Self::migrate_nominators_index())
+        }
+
+        fn offchain_worker(_now: T::BlockNumber) {
+            if T::OffchainElection::get()
+                && Self::is_current_session_final()
+                && sp_io::offchain::is_validator()
+            {
+                Self::offchain_election();
+            }
+        }
+
         /// Nominate the `target` with `value` of the origin account's balance locked.
         #[weight = T::WeightInfo::bond()]
         pub fn bond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
@@ -358,7 +485,7 @@ decl_module! {
 
         /// Move the `value` of current nomination from one validator to another.
         #[weight = T::WeightInfo::rebond()]
-        fn rebond(origin, from: <T::Lookup as StaticLookup>::Source, to: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
+        pub fn rebond(origin, from: <T::Lookup as StaticLookup>::Source, to: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
             let sender = ensure_signed(origin)?;
             let from = T::Lookup::lookup(from)?;
             let to = T::Lookup::lookup(to)?;
@@ -384,8 +511,11 @@ decl_module! {
         }
 
         /// Unnominate the `value` of bonded balance for validator `target`.
+        ///
+        /// If an unbonded chunk for `target` falls in the same era bucket, the balance is
+        /// merged into it and the lock of that chunk is extended to the new one's.
         #[weight = T::WeightInfo::unbond()]
-        fn unbond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
+        pub fn unbond(origin, target: <T::Lookup as StaticLookup>::Source, #[compact] value: BalanceOf<T>) {
             let sender = ensure_signed(origin)?;
             let target = T::Lookup::lookup(target)?;
 
@@ -393,6 +523,17 @@ decl_module! {
             Self::apply_unbond(&sender, &target, value)?;
         }
 
+        /// Unnominate all the bonded balance for validator `target`.
+        #[weight = T::WeightInfo::unbond_all()]
+        fn unbond_all(origin, target: <T::Lookup as StaticLookup>::Source) {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            let value = Self::bonded_to(&sender, &target);
+            Self::can_unbond(&sender, &target, value)?;
+            Self::apply_unbond(&sender, &target, value)?;
+        }
+
         /// Unlock the frozen unbonded balances that are due.
         #[weight = T::WeightInfo::unlock_unbonded_withdrawal()]
         fn unlock_unbonded_withdrawal(
@@ -422,6 +563,51 @@ decl_module! {
             Self::deposit_event(Event::<T>::Withdrawn(sender, value));
         }
 
+        /// Cancel the unbonded chunk `unbonded_index` for validator `target`,
+        /// the balance of which is bonded back to `target` immediately.
+        #[weight = T::WeightInfo::cancel_unbond()]
+        fn cancel_unbond(
+            origin,
+            target: <T::Lookup as StaticLookup>::Source,
+            #[compact] unbonded_index: UnbondedIndex
+        ) {
+            let sender = ensure_signed(origin)?;
+            let target = T::Lookup::lookup(target)?;
+
+            ensure!(Self::is_validator(&target), Error::<T>::NotValidator);
+
+            let unbonded_chunks = Self::unbonded_chunks_of(&sender, &target);
+            ensure!(!unbonded_chunks.is_empty(), Error::<T>::EmptyUnbondedChunks);
+            ensure!(unbonded_index < unbonded_chunks.len() as u32, Error::<T>::InvalidUnbondedIndex);
+
+            let value = unbonded_chunks[unbonded_index as usize].value;
+            if !Self::is_validator_bonding_itself(&sender, &target) {
+                Self::check_validator_acceptable_votes_limit(&target, value)?;
+            }
+
+            Self::apply_cancel_unbond(&sender, &target, unbonded_index, value);
+        }
+
+        /// Unlock all the frozen unbonded balances that are due, across all the
+        /// validators the origin account has nominated.
+        ///
+        /// `nominations_witness` is the number of validators the origin account has
+        /// ever nominated, the weight is charged by it upfront and refunded according
+        /// to the actual number of nominations.
+        #[weight = T::WeightInfo::withdraw_unbonded_all(*nominations_witness)]
+        pub fn withdraw_unbonded_all(origin, #[compact] nominations_witness: u32) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let targets = Self::nomination_targets_within(&sender, nominations_witness)?;
+            let count = targets.len() as u32;
+
+            let total_withdrawn = Self::withdraw_unbonded_from(&sender, &targets)?;
+
+            Self::deposit_event(Event::<T>::Withdrawn(sender, total_withdrawn));
+
+            Ok(Some(T::WeightInfo::withdraw_unbonded_all(count)).into())
+        }
+
         /// Claim the staking reward given the `target` validator.
         #[weight = T::WeightInfo::claim()]
         fn claim(origin, target: <T::Lookup as StaticLookup>::Source) {
@@ -433,6 +619,66 @@ decl_module! {
             <Self as Claim<T::AccountId>>::claim(&sender, &target)?;
         }
 
+        /// Claim the staking reward from all the validators the origin account has nominated.
+        ///
+        /// The outcome of each claim is reported via `Claimed` or `ClaimFailed`, a failed
+        /// claim leaves no changes.
+        ///
+        /// `nominations_witness` is the number of validators the origin account has
+        /// ever nominated, the weight is charged by it upfront and refunded according
+        /// to the actual number of nominations.
+        #[weight = T::WeightInfo::claim_all(*nominations_witness)]
+        fn claim_all(origin, #[compact] nominations_witness: u32) -> DispatchResultWithPostInfo {
+            let sender = ensure_signed(origin)?;
+
+            let targets = Self::nomination_targets_within(&sender, nominations_witness)?;
+            let count = targets.len() as u32;
+
+            for target in targets.into_iter().filter(Self::is_validator) {
+                let result = with_transaction_result(|| {
+                    <Self as Claim<T::AccountId>>::claim(&sender, &target).map_err(Into::into)
+                });
+                if let Err(e) = result {
+                    Self::deposit_event(Event::<T>::ClaimFailed(sender.clone(), target, e));
+                }
+            }
+
+            Ok(Some(T::WeightInfo::claim_all(count)).into())
+        }
+
+        /// Claim the staking reward of `nominator` given the `target` validator on behalf of it.
+        ///
+        /// Anyone can trigger the payout, the dividend is bonded back to
+        /// the `target` if `nominator` is in the restake mode.
+        #[weight = T::WeightInfo::payout_for()]
+        fn payout_for(
+            origin,
+            nominator: <T::Lookup as StaticLookup>::Source,
+            target: <T::Lookup as StaticLookup>::Source
+        ) {
+            ensure_signed(origin)?;
+            let nominator = T::Lookup::lookup(nominator)?;
+            let target = T::Lookup::lookup(target)?;
+
+            ensure!(Self::is_validator(&target), Error::<T>::NotValidator);
+
+            <Self as Claim<T::AccountId>>::claim(&nominator, &target)?;
+        }
+
+        /// Turn on/off the restake mode for the origin account.
+        ///
+        /// In the restake mode, the claimed dividend is bonded back to the same validator.
+        #[weight = T::WeightInfo::set_restake()]
+        fn set_restake(origin, enabled: bool) {
+            let sender = ensure_signed(origin)?;
+            if enabled {
+                RestakeOf::<T>::insert(&sender, true);
+            } else {
+                RestakeOf::<T>::remove(&sender);
+            }
+            Self::deposit_event(Event::<T>::RestakeSet(sender, enabled));
+        }
+
         /// Declare the desire to validate for the origin account.
         #[weight = T::WeightInfo::validate()]
         fn validate(origin) {
@@ -483,6 +729,94 @@ decl_module! {
             }
         }
 
+        /// Schedule a change of the commission for the origin validator.
+        ///
+        /// The new commission takes effect at the start of the era after next,
+        /// a pending change scheduled earlier will be replaced.
+        #[weight = T::WeightInfo::set_commission()]
+        fn set_commission(origin, commission: Perbill) {
+            let sender = ensure_signed(origin)?;
+            ensure!(Self::is_validator(&sender), Error::<T>::NotValidator);
+            ensure!(
+                commission <= Self::maximum_validator_commission(),
+                Error::<T>::CommissionTooHigh
+            );
+
+            let active_era = Self::active_era().map(|e| e.index).unwrap_or(0);
+            let effective_era = active_era + 1 + COMMISSION_CHANGE_DELAY;
+            Validators::<T>::mutate(&sender, |validator| {
+                validator.pending_commission = Some(PendingCommission {
+                    commission,
+                    effective_era,
+                });
+            });
+            Self::deposit_event(Event::<T>::CommissionChangeScheduled(sender, commission, effective_era));
+        }
+
+        /// Submit the election solution of the next era computed by the offchain worker.
+        ///
+        /// The solution is queued if it is valid and has a better score than the queued one.
+        #[weight = T::WeightInfo::submit_election_solution(
+            solution.winners.len() as u32,
+            solution.assignments.len() as u32
+        )]
+        fn submit_election_solution(origin, solution: ElectionSolution<T::AccountId>) {
+            ensure_none(origin)?;
+            Self::check_election_solution(&solution)?;
+            let ElectionSolution { era, winners, score, .. } = solution;
+            QueuedElected::<T>::put(QueuedElectionResult { era, winners, score });
+            Self::deposit_event(Event::<T>::ElectionSolutionQueued(era));
+        }
+
+        /// Cancel the deferred slashes of era `era` given the sorted indices in the queue.
+        ///
+        /// The origin must be `T::SlashCancelOrigin`.
+        #[weight = T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32)]
+        fn cancel_deferred_slash(origin, era: EraIndex, slash_indices: Vec<u32>) {
+            T::SlashCancelOrigin::ensure_origin(origin)?;
+
+            ensure!(
+                !slash_indices.is_empty() && slash_indices.windows(2).all(|w| w[0] < w[1]),
+                Error::<T>::NotSortedAndUnique
+            );
+
+            let mut unapplied = UnappliedSlashes::<T>::get(&era);
+            let last_index = slash_indices[slash_indices.len() - 1] as usize;
+            ensure!(last_index < unapplied.len(), Error::<T>::InvalidSlashIndex);
+
+            // Remove from the back so that the indices are still valid.
+            for index in slash_indices.into_iter().rev() {
+                let slash = unapplied.remove(index as usize);
+                Self::deposit_event(Event::<T>::SlashCancelled(slash.validator, slash.penalty, era));
+            }
+
+            if unapplied.is_empty() {
+                UnappliedSlashes::<T>::remove(&era);
+            } else {
+                UnappliedSlashes::<T>::insert(&era, unapplied);
+            }
+        }
+
+        /// Turn on/off the slashing of nominators and set the fraction to slash.
+        #[weight = T::WeightInfo::set_nominator_slashing()]
+        fn set_nominator_slashing(origin, enabled: bool, fraction: Perbill) {
+            ensure_root(origin)?;
+            NominatorSlashingEnabled::put(enabled);
+            NominatorSlashFraction::put(fraction);
+        }
+
+        #[weight = T::WeightInfo::set_slash_defer_duration()]
+        fn set_slash_defer_duration(origin, #[compact] new: EraIndex) {
+            ensure_root(origin)?;
+            SlashDeferDuration::put(new);
+        }
+
+        #[weight = T::WeightInfo::set_maximum_validator_commission()]
+        fn set_maximum_validator_commission(origin, new: Perbill) {
+            ensure_root(origin)?;
+            MaximumValidatorCommission::put(new);
+        }
+
         #[weight = T::WeightInfo::set_validator_count()]
         fn set_validator_count(origin, #[compact] new: u32) {
             ensure_root(origin)?;
@@ -566,6 +900,45 @@ decl_module! {
     }
 }
 
+impl<T: Trait> frame_support::unsigned::ValidateUnsigned for Module<T> {
+    type Call = Call<T>;
+
+    fn validate_unsigned(source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+        if let Call::submit_election_solution(solution) = call {
+            // Only the solutions computed by the local offchain worker or included in a block
+            // are accepted, the ones gossiped from the network are dropped.
+            match source {
+                TransactionSource::Local | TransactionSource::InBlock => {}
+                _ => {
+                    debug!(
+                        "[validate_unsigned] Election solution from {:?} rejected",
+                        source
+                    );
+                    return InvalidTransaction::Call.into();
+                }
+            }
+
+            if let Err(e) = Self::check_election_solution(solution) {
+                debug!("[validate_unsigned] Invalid election solution: {:?}", e);
+                return InvalidTransaction::Custom(e.as_u8()).into();
+            }
+
+            // The solution with a higher minimal support is preferred.
+            let priority = T::UnsignedPriority::get()
+                .saturating_add(solution.score[0].saturated_into::<TransactionPriority>());
+
+            ValidTransaction::with_tag_prefix("XStakingOffchainElection")
+                .priority(priority)
+                .and_provides(solution.era)
+                .longevity(T::SessionDuration::get().saturated_into::<u64>())
+                .propagate(false)
+                .build()
+        } else {
+            InvalidTransaction::Call.into()
+        }
+    }
+}
+
 /// Means for interacting with a specialized version of the `session` trait.
 ///
 /// This is needed because `Staking` sets the `ValidatorIdOf` of the `pallet_session::Trait`
@@ -661,6 +1034,16 @@ impl<T: Trait> Module<T> {
         *Self::locks(who).entry(LockedType::Bonded).or_default()
     }
 
+    /// Returns the commission actually applied to the reward of validator `who`.
+    ///
+    /// The commission is always capped by `MaximumValidatorCommission`, which
+    /// might have been lowered after the validator set its commission.
+    pub fn commission_of(who: &T::AccountId) -> Perbill {
+        Validators::<T>::get(who)
+            .commission
+            .min(Self::maximum_validator_commission())
+    }
+
     /// Returns the associated reward pot account for the given validator.
     #[inline]
     pub fn reward_pot_for(validator: &T::AccountId) -> T::AccountId {
@@ -668,7 +1051,7 @@ impl<T: Trait> Module<T> {
     }
 
     #[inline]
-    fn unbonded_chunks_of(
+    pub fn unbonded_chunks_of(
         nominator: &T::AccountId,
         target: &T::AccountId,
     ) -> Vec<Unbonded<BalanceOf<T>, T::BlockNumber>> {
@@ -688,7 +1071,7 @@ impl<T: Trait> Module<T> {
 
     /// Returns the balance of `nominator` has voted to `nominee`.
     #[inline]
-    fn bonded_to(nominator: &T::AccountId, nominee: &T::AccountId) -> BalanceOf<T> {
+    pub fn bonded_to(nominator: &T::AccountId, nominee: &T::AccountId) -> BalanceOf<T> {
         Nominations::<T>::get(nominator, nominee).nomination
     }
 
@@ -831,7 +1214,7 @@ impl<T: Trait> Module<T> {
         });
     }
 
-    fn can_unbond(
+    fn can_unbond_uncapped(
         sender: &T::AccountId,
         target: &T::AccountId,
         value: BalanceOf<T>,
@@ -842,9 +1225,19 @@ impl<T: Trait> Module<T> {
             value <= Self::bonded_to(sender, target),
             Error::<T>::InvalidUnbondBalance
         );
+        Ok(())
+    }
+
+    fn can_unbond(
+        sender: &T::AccountId,
+        target: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::can_unbond_uncapped(sender, target, value)?;
         ensure!(
             Self::unbonded_chunks_of(sender, target).len()
-                < Self::maximum_unbonded_chunk_size() as usize,
+                < Self::maximum_unbonded_chunk_size() as usize
+                || Self::mergeable_unbonded_chunk(sender, target).is_some(),
             Error::<T>::NoMoreUnbondChunks
         );
         Ok(())
@@ -900,11 +1293,74 @@ impl<T: Trait> Module<T> {
             ValidatorProfile {
                 registered_at: current_block,
                 referral_id,
+                commission: DEFAULT_VALIDATOR_COMMISSION.min(Self::maximum_validator_commission()),
                 ..Default::default()
             },
         );
     }
 
+    /// Records the session reward issued to the validator in the active era.
+    fn record_validator_reward(who: &T::AccountId, reward: BalanceOf<T>) {
+        if let Some(active_era) = Self::active_era() {
+            ErasStakingReward::<T>::mutate(active_era.index, |total| {
+                *total = total.saturating_add(reward)
+            });
+            ErasValidatorRecords::<T>::mutate(active_era.index, who, |record| {
+                record.reward = record.reward.saturating_add(reward)
+            });
+        }
+    }
+
+    /// Records the balance slashed from the validator's reward pot in the active era.
+    fn record_validator_slash(who: &T::AccountId, slash: BalanceOf<T>) {
+        if let Some(active_era) = Self::active_era() {
+            ErasValidatorRecords::<T>::mutate(active_era.index, who, |record| {
+                record.slash = record.slash.saturating_add(slash)
+            });
+        }
+    }
+
+    /// Takes the snapshot of the reward pot balances at the end of era `era_index`
+    /// and prunes the records older than `HISTORY_DEPTH`.
+    ///
+    /// Only the validators having been rewarded or slashed in the era are recorded.
+    fn close_era_records(era_index: EraIndex) {
+        use frame_support::IterableStorageDoubleMap;
+        let validators = ErasValidatorRecords::<T>::iter_prefix(era_index)
+            .map(|(validator, _)| validator)
+            .collect::<Vec<_>>();
+        for validator in validators {
+            let reward_pot_balance = Self::free_balance(&Self::reward_pot_for(&validator));
+            ErasValidatorRecords::<T>::mutate(era_index, &validator, |record| {
+                record.reward_pot_balance = reward_pot_balance
+            });
+        }
+
+        if let Some(stale_era) = era_index.checked_sub(HISTORY_DEPTH) {
+            ErasStakingReward::<T>::remove(stale_era);
+            ErasValidatorRecords::<T>::remove_prefix(stale_era);
+        }
+    }
+
+    /// Apply the scheduled commission changes that are due in era `era_index`.
+    fn apply_pending_commissions(era_index: EraIndex) {
+        let due = Validators::<T>::iter()
+            .filter_map(|(validator, profile)| match profile.pending_commission {
+                Some(pending) if pending.effective_era <= era_index => {
+                    Some((validator, pending.commission))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        for (validator, commission) in due {
+            Validators::<T>::mutate(&validator, |profile| {
+                profile.commission = commission;
+                profile.pending_commission = None;
+            });
+            Self::deposit_event(Event::<T>::CommissionUpdated(validator, commission));
+        }
+    }
+
     fn apply_bond(
         nominator: &T::AccountId,
         nominee: &T::AccountId,
@@ -920,6 +1376,34 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Bond the claimed `dividend` of `nominator` back to `validator`.
+    ///
+    /// The dividend is left in the free balance if the validator can not accept more votes.
+    pub(crate) fn apply_restake(
+        nominator: &T::AccountId,
+        validator: &T::AccountId,
+        dividend: BalanceOf<T>,
+    ) {
+        if dividend.is_zero() {
+            return;
+        }
+        if !Self::is_validator_bonding_itself(nominator, validator)
+            && Self::check_validator_acceptable_votes_limit(validator, dividend).is_err()
+        {
+            debug!(
+                "[apply_restake] {:?} can not accept more votes, dividend {:?} of {:?} is not restaked",
+                validator, dividend, nominator
+            );
+            return;
+        }
+        if let Err(e) = Self::apply_bond(nominator, validator, dividend) {
+            error!(
+                "[apply_restake] Failed to bond the dividend {:?} of {:?} to {:?}: {:?}",
+                dividend, nominator, validator, e
+            );
+        }
+    }
+
     fn apply_rebond(
         who: &T::AccountId,
         from: &T::AccountId,
@@ -940,19 +1424,101 @@ impl<T: Trait> Module<T> {
         ));
     }
 
+    /// Returns the validators `who` has ever nominated, fails if the number of them
+    /// exceeds `witness`.
+    fn nomination_targets_within(
+        who: &T::AccountId,
+        witness: u32,
+    ) -> Result<Vec<T::AccountId>, Error<T>> {
+        let targets = Nominations::<T>::iter_prefix(who)
+            .map(|(target, _)| target)
+            .take(witness.saturating_add(1) as usize)
+            .collect::<Vec<_>>();
+        ensure!(
+            targets.len() <= witness as usize,
+            Error::<T>::InvalidNominationsWitness
+        );
+        Ok(targets)
+    }
+
+    /// Unbonds `value` of `who` from `target` regardless of `MaximumUnbondedChunkSize`.
+    ///
+    /// This is for the accounts unbonding on behalf of many others, e.g., the nomination
+    /// pools, so that one of them can not use up the chunks of all the others. The chunks
+    /// are still merged per era bucket, i.e., bounded by the era buckets of the bonding duration.
+    pub fn unbond_uncapped(
+        who: &T::AccountId,
+        target: &T::AccountId,
+        value: BalanceOf<T>,
+    ) -> DispatchResult {
+        Self::can_unbond_uncapped(who, target, value)?;
+        Self::apply_unbond(who, target, value)?;
+        Ok(())
+    }
+
+    /// Unlocks the unbonded balances of `who` that are due across `targets`,
+    /// returns the total balances withdrawn.
+    pub fn withdraw_unbonded_from(
+        who: &T::AccountId,
+        targets: &[T::AccountId],
+    ) -> Result<BalanceOf<T>, Error<T>> {
+        let current_block = <frame_system::Module<T>>::block_number();
+        let mut has_unbonded = false;
+        let mut total_withdrawn: BalanceOf<T> = Zero::zero();
+        let mut withdrawn_chunks = Vec::new();
+        for target in targets {
+            let unbonded_chunks = Self::unbonded_chunks_of(who, target);
+            has_unbonded |= !unbonded_chunks.is_empty();
+            let (due, pending): (Vec<_>, Vec<_>) = unbonded_chunks
+                .into_iter()
+                .partition(|chunk| current_block > chunk.locked_until);
+            if !due.is_empty() {
+                total_withdrawn = due
+                    .iter()
+                    .fold(total_withdrawn, |acc, chunk| acc + chunk.value);
+                withdrawn_chunks.push((target, pending));
+            }
+        }
+
+        ensure!(has_unbonded, Error::<T>::EmptyUnbondedChunks);
+        ensure!(
+            !withdrawn_chunks.is_empty(),
+            Error::<T>::UnbondedWithdrawalNotYetDue
+        );
+
+        Self::apply_unlock_unbonded_withdrawal(who, total_withdrawn);
+
+        for (target, pending) in withdrawn_chunks {
+            Nominations::<T>::mutate(who, target, |nominator| {
+                nominator.unbonded_chunks = pending;
+            });
+        }
+
+        Ok(total_withdrawn)
+    }
+
+    /// Adds the unbonded `value` to the chunks of `who` for `target`.
+    ///
+    /// The value is merged into the existing chunk falling in the same era bucket as
+    /// `locked_until` to bound the number of chunks, in which case the merged chunk is
+    /// locked until the later one of them, i.e., the lock of the earlier unbonded
+    /// balances is extended by less than an era.
     fn mutate_unbonded_chunks(
         who: &T::AccountId,
         target: &T::AccountId,
         value: BalanceOf<T>,
         locked_until: T::BlockNumber,
     ) {
+        let era_bucket = Self::era_bucket_of(locked_until);
         Nominations::<T>::mutate(who, target, |nominator| {
             if let Some(idx) = nominator
                 .unbonded_chunks
                 .iter()
-                .position(|x| x.locked_until == locked_until)
+                .position(|x| Self::era_bucket_of(x.locked_until) == era_bucket)
             {
-                nominator.unbonded_chunks[idx].value += value;
+                let chunk = &mut nominator.unbonded_chunks[idx];
+                chunk.value += value;
+                chunk.locked_until = chunk.locked_until.max(locked_until);
             } else {
                 nominator.unbonded_chunks.push(Unbonded {
                     value,
@@ -970,6 +1536,37 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    /// Returns the number of blocks of an era, which is used as the bucket size of
+    /// merging the unbonded chunks.
+    fn era_length() -> T::BlockNumber {
+        T::SessionDuration::get()
+            .saturating_mul(Self::sessions_per_era().saturated_into::<T::BlockNumber>())
+    }
+
+    /// Returns the era bucket the block `locked_until` falls in.
+    fn era_bucket_of(locked_until: T::BlockNumber) -> T::BlockNumber {
+        let era_length = Self::era_length();
+        if era_length.is_zero() {
+            locked_until
+        } else {
+            locked_until / era_length
+        }
+    }
+
+    /// Returns the block number until which the balances unbonded right now are locked.
+    fn unbonded_locked_until(who: &T::AccountId, target: &T::AccountId) -> T::BlockNumber {
+        <frame_system::Module<T>>::block_number() + Self::bonding_duration_for(who, target)
+    }
+
+    /// Returns the index of the unbonded chunk the balances unbonded right now
+    /// will be merged into, if any.
+    fn mergeable_unbonded_chunk(who: &T::AccountId, target: &T::AccountId) -> Option<usize> {
+        let era_bucket = Self::era_bucket_of(Self::unbonded_locked_until(who, target));
+        Self::unbonded_chunks_of(who, target)
+            .iter()
+            .position(|x| Self::era_bucket_of(x.locked_until) == era_bucket)
+    }
+
     fn apply_unbond(
         who: &T::AccountId,
         target: &T::AccountId,
@@ -981,8 +1578,7 @@ impl<T: Trait> Module<T> {
         );
         Self::unbond_reserve(who, value)?;
 
-        let locked_until =
-            <frame_system::Module<T>>::block_number() + Self::bonding_duration_for(who, target);
+        let locked_until = Self::unbonded_locked_until(who, target);
         Self::mutate_unbonded_chunks(who, target, value, locked_until);
 
         Self::update_vote_weight(who, target, Delta::Sub(value));
@@ -992,6 +1588,39 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    /// Moves the unbonded chunk back to the active nomination.
+    ///
+    /// The total staking lock is unchanged, only the locked type is switched back.
+    fn apply_cancel_unbond(
+        who: &T::AccountId,
+        target: &T::AccountId,
+        unbonded_index: UnbondedIndex,
+        value: BalanceOf<T>,
+    ) {
+        Nominations::<T>::mutate(who, target, |nominator| {
+            nominator
+                .unbonded_chunks
+                .swap_remove(unbonded_index as usize);
+        });
+
+        Locks::<T>::mutate(who, |locks| {
+            *locks.entry(LockedType::Bonded).or_default() += value;
+            let withdrawal = locks.entry(LockedType::BondedWithdrawal).or_default();
+            *withdrawal = withdrawal.saturating_sub(value);
+            if withdrawal.is_zero() {
+                locks.remove(&LockedType::BondedWithdrawal);
+            }
+        });
+
+        Self::update_vote_weight(who, target, Delta::Add(value));
+
+        Self::deposit_event(Event::<T>::UnbondCancelled(
+            who.clone(),
+            target.clone(),
+            value,
+        ));
+    }
+
     fn apply_unlock_unbonded_withdrawal(who: &T::AccountId, value: BalanceOf<T>) {
         let new_bonded = Self::total_locked_of(who) - value;
         Self::set_lock(who, new_bonded);
@@ -1009,3 +1638,41 @@ impl<T: Trait> Module<T> {
         });
     }
 }
+
+impl<T: Trait> Module<T> {
+    /// Migrate `Validators` to `Releases::V2_0_0`, the existing validators keep
+    /// the commission that was hard-coded before.
+    pub fn migrate_validator_profile() -> Weight {
+        if Self::storage_version() != Releases::V1_0_0 {
+            return 0;
+        }
+        Validators::<T>::translate::<ValidatorProfileV1<T::BlockNumber>, _>(|_, profile| {
+            Some(profile.upgrade())
+        });
+        StorageVersion::put(Releases::V2_0_0);
+
+        let count = Validators::<T>::iter().count() as Weight;
+        info!(
+            "[migrate_validator_profile] {} validator profiles migrated",
+            count
+        );
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+
+    /// Migrate to `Releases::V3_0_0`, index the nominators of each validator.
+    pub fn migrate_nominators_index() -> Weight {
+        if Self::storage_version() != Releases::V2_0_0 {
+            return 0;
+        }
+        use frame_support::IterableStorageDoubleMap;
+        let mut count: Weight = 0;
+        for (nominator, validator, _) in Nominations::<T>::iter() {
+            NominatorsOf::<T>::insert(&validator, &nominator, ());
+            count += 1;
+        }
+        StorageVersion::put(Releases::V3_0_0);
+
+        info!("[migrate_nominators_index] {} nominations indexed", count);
+        T::DbWeight::get().reads_writes(count + 1, count + 1)
+    }
+}
@@ -202,6 +202,13 @@ parameter_types! {
     pub const SessionDuration: BlockNumber = 50;
     pub const MinimumReferralId: u32 = 2;
     pub const MaximumReferralId: u32 = 12;
+    pub const OffchainElection: bool = true;
+    pub const StakingUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
+}
+
+impl frame_system::offchain::SendTransactionTypes<Call<Test>> for Test {
+    type OverarchingCall = Call<Test>;
+    type Extrinsic = sp_runtime::testing::TestXt<Call<Test>, ()>;
 }
 
 impl Trait for Test {
@@ -214,6 +221,10 @@ impl Trait for Test {
     type SessionInterface = Self;
     type TreasuryAccount = DummyTreasuryAccount;
     type DetermineRewardPotAccount = DummyStakingRewardPotAccountDeterminer;
+    type SlashCancelOrigin = frame_system::EnsureRoot<AccountId>;
+    type ElectionStrategy = TopVotes;
+    type OffchainElection = OffchainElection;
+    type UnsignedPriority = StakingUnsignedPriority;
     type WeightInfo = ();
 }
 
@@ -34,12 +34,12 @@ impl<T: Trait> Module<T> {
     ///
     /// Add the reward to their balance, and their reward pot, pro-rata.
     fn apply_reward_validator(who: &T::AccountId, reward: BalanceOf<T>) {
-        // Validator themselves can only directly gain 10%, the rest 90% is for the reward pot.
-        let off_the_table = reward.saturated_into::<BalanceOf<T>>() / 10u32.saturated_into();
+        // Validator themselves can only directly gain the commission, the rest is for the reward pot.
+        let off_the_table = Self::commission_of(who).mul_floor(reward);
         Self::mint(who, off_the_table);
         debug!("💸 Mint validator({:?}):{:?}", who, off_the_table);
 
-        // Issue the rest 90% to validator's reward pot.
+        // Issue the rest to validator's reward pot.
         let to_reward_pot = reward - off_the_table;
         let reward_pot = T::DetermineRewardPotAccount::reward_pot_account_for(who);
         Self::mint(&reward_pot, to_reward_pot);
@@ -53,6 +53,7 @@ impl<T: Trait> Module<T> {
     #[inline]
     fn reward_active_validator(validator: &T::AccountId, reward: BalanceOf<T>) {
         Self::apply_reward_validator(validator, reward);
+        Self::record_validator_reward(validator, reward);
     }
 
     /// Distribute the session reward to all the receivers, returns the total reward for validators.
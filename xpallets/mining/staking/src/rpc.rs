@@ -12,7 +12,8 @@ use sp_runtime::RuntimeDebug;
 use xp_mining_common::RewardPotAccountFor;
 
 use crate::{
-    types::*, BalanceOf, LastRebondOf, Module, Nominations, SessionInterface, Trait,
+    constants::HISTORY_DEPTH, types::*, BalanceOf, EraIndex, ErasStakingReward,
+    ErasValidatorRecords, LastRebondOf, Module, Nominations, RestakeOf, SessionInterface, Trait,
     ValidatorLedgers, Validators,
 };
 
@@ -44,6 +45,19 @@ pub struct ValidatorInfo<AccountId, Balance, VoteWeight, BlockNumber> {
 pub struct NominatorInfo<BlockNumber> {
     /// Block number of last `rebond` operation.
     pub last_rebond: Option<BlockNumber>,
+    /// Whether the claimed dividend is bonded back automatically.
+    pub restake: bool,
+}
+
+/// Rewards and slashes of an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EraRewards<AccountId, Balance> {
+    /// Total staking reward issued to the validators.
+    pub total_reward: Balance,
+    /// Reward and slash records of the validators rewarded or slashed in this era.
+    pub validators: BTreeMap<AccountId, ValidatorEraRecord<Balance>>,
 }
 
 impl<T: Trait> Module<T> {
@@ -93,8 +107,57 @@ impl<T: Trait> Module<T> {
             .collect()
     }
 
+    /// Returns the record of validator `who` in era `era`.
+    ///
+    /// The reward pot balance of the active era is the current one as it is not closed yet.
+    fn era_record_of(
+        era: EraIndex,
+        who: &T::AccountId,
+        mut record: ValidatorEraRecord<BalanceOf<T>>,
+    ) -> ValidatorEraRecord<BalanceOf<T>> {
+        if Self::active_era().map(|e| e.index) == Some(era) {
+            record.reward_pot_balance = Self::free_balance(&Self::reward_pot_for(who));
+        }
+        record
+    }
+
+    pub fn era_rewards(era: EraIndex) -> EraRewards<T::AccountId, BalanceOf<T>> {
+        EraRewards {
+            total_reward: ErasStakingReward::<T>::get(era),
+            validators: ErasValidatorRecords::<T>::iter_prefix(era)
+                .map(|(validator, record)| {
+                    let record = Self::era_record_of(era, &validator, record);
+                    (validator, record)
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the records of validator `who` in the latest `eras` eras, at most `HISTORY_DEPTH`.
+    pub fn validator_history(
+        who: T::AccountId,
+        eras: u32,
+    ) -> BTreeMap<EraIndex, ValidatorEraRecord<BalanceOf<T>>> {
+        let active_era = match Self::active_era() {
+            Some(active_era) if eras > 0 => active_era.index,
+            _ => return Default::default(),
+        };
+        let first_era = active_era.saturating_sub(eras.min(HISTORY_DEPTH) - 1);
+        (first_era..=active_era)
+            .filter(|era| ErasValidatorRecords::<T>::contains_key(era, &who))
+            .map(|era| {
+                let record = ErasValidatorRecords::<T>::get(era, &who);
+                (era, Self::era_record_of(era, &who, record))
+            })
+            .collect()
+    }
+
     pub fn nominator_info_of(who: T::AccountId) -> NominatorInfo<T::BlockNumber> {
         let last_rebond = LastRebondOf::<T>::get(&who);
-        NominatorInfo { last_rebond }
+        let restake = RestakeOf::<T>::get(&who);
+        NominatorInfo {
+            last_rebond,
+            restake,
+        }
     }
 }
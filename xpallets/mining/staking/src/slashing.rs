@@ -2,21 +2,22 @@
 
 use sp_std::ops::Mul;
 
+use frame_support::{weights::DispatchClass, IterableStorageDoubleMap};
+
 use super::*;
 
 impl<T: Trait> Module<T> {
     /// Returns the force chilled offenders if any after applying the slashings.
     ///
-    /// The slashed balances will be moved to the treasury.
+    /// The slashes will be deferred if `SlashDeferDuration` is not zero, in which case
+    /// no offender is force chilled right now.
     pub(crate) fn slash_offenders_in_session(
         offenders: BTreeMap<T::AccountId, Perbill>,
         validator_rewards: Vec<(T::AccountId, BalanceOf<T>)>,
+        slash_session: SessionIndex,
     ) -> Vec<T::AccountId> {
         let validator_rewards = validator_rewards.into_iter().collect::<BTreeMap<_, _>>();
 
-        let treasury_account = T::TreasuryAccount::treasury_account();
-        let slasher = Slasher::<T>::new(treasury_account);
-
         let minimum_penalty = Self::minimum_penalty();
         let calc_base_slash = |offender: &T::AccountId, slash_fraction: Perbill| {
             // https://github.com/paritytech/substrate/blob/c60f00840034017d4b7e6d20bd4fcf9a3f5b529a/frame/im-online/src/lib.rs#L773
@@ -29,9 +30,76 @@ impl<T: Trait> Module<T> {
             }
         };
 
+        let slashes = offenders
+            .into_iter()
+            .map(|(offender, slash_fraction)| {
+                let base_slash = calc_base_slash(&offender, slash_fraction);
+                let penalty = validator_rewards
+                    .get(&offender)
+                    .copied()
+                    .map(|reward| reward + base_slash)
+                    .unwrap_or(base_slash)
+                    .max(minimum_penalty);
+                let others = Self::nominator_slashes_of(&offender);
+                UnappliedSlash {
+                    validator: offender,
+                    penalty,
+                    slash_session,
+                    others,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let slash_defer_duration = Self::slash_defer_duration();
+        if slash_defer_duration.is_zero() {
+            return Self::apply_slashes(slashes);
+        }
+
+        let active_era = Self::active_era().map(|e| e.index).unwrap_or(0);
+        let apply_era = active_era.saturating_add(slash_defer_duration);
+        for slash in slashes.iter() {
+            Self::deposit_event(Event::<T>::SlashDeferred(
+                slash.validator.clone(),
+                slash.penalty,
+                apply_era,
+            ));
+        }
+        UnappliedSlashes::<T>::mutate(apply_era, |unapplied| unapplied.extend(slashes));
+
+        Vec::new()
+    }
+
+    /// Applies the deferred slashes that are due in era `era_index`.
+    pub(crate) fn apply_unapplied_slashes(era_index: EraIndex, start_session: SessionIndex) {
+        let unapplied = UnappliedSlashes::<T>::take(era_index);
+        if unapplied.is_empty() {
+            return;
+        }
+        let force_chilled = Self::apply_slashes(unapplied);
+        if !force_chilled.is_empty() {
+            debug!("Force chilled:{:?}", force_chilled);
+            Self::deposit_event(Event::<T>::ForceChilled(start_session, force_chilled));
+            // Force a new era if some offender's reward pot has been wholly slashed.
+            Self::ensure_new_era();
+        }
+    }
+
+    /// Returns the force chilled offenders if any after applying the slashes.
+    ///
+    /// The slashed balances will be moved to the treasury.
+    fn apply_slashes(
+        slashes: Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+    ) -> Vec<T::AccountId> {
+        let treasury_account = T::TreasuryAccount::treasury_account();
+        let slasher = Slasher::<T>::new(treasury_account);
+
         let minimum_validator_count = Self::reasonable_minimum_validator_count() as usize;
         let mut active_count = Self::active_validator_set().count();
         let mut chill_offender_safe = |offender: T::AccountId| {
+            // The offender of a deferred slash might have been chilled already.
+            if Self::is_chilled(&offender) {
+                return None;
+            }
             // The offender does not have enough balance for the slashing and has to be chilled,
             // but we must avoid the over-slashing, ensure have the minimum active validators.
             if active_count > minimum_validator_count {
@@ -43,42 +111,167 @@ impl<T: Trait> Module<T> {
             }
         };
 
-        offenders
+        slashes
             .into_iter()
-            .flat_map(|(offender, slash_fraction)| {
-                let base_slash = calc_base_slash(&offender, slash_fraction);
-                let penalty = validator_rewards
-                    .get(&offender)
-                    .copied()
-                    .map(|reward| reward + base_slash)
-                    .unwrap_or(base_slash)
-                    .max(minimum_penalty);
-                match slasher.try_slash(&offender, penalty) {
-                    SlashOutcome::Slashed(_) => {
-                        debug!(
-                            "Slash the offender:{:?} for penalty {:?} by the given slash_fraction:{:?} successfully",
-                            offender, penalty, slash_fraction
-                        );
-                        None
-                    }
-                    SlashOutcome::InsufficientSlash(actual_slashed) => {
-                        debug!(
-                            "Insufficient reward pot balance of {:?}, actual slashed:{:?}",
-                            offender, actual_slashed
-                        );
-                        chill_offender_safe(offender)
-                    }
-                    SlashOutcome::SlashFailed(e) => {
-                        debug!("Slash the offender {:?} for {:?} somehow failed: {:?}", offender, penalty, e);
-                        // we still chill the offender even the slashing failed as currently
-                        // the offender is only the authorties without running a node.
-                        //
-                        // TODO: Reconsider this once https://github.com/paritytech/substrate/pull/7127
-                        // is merged.
-                        chill_offender_safe(offender)
+            .flat_map(
+                |UnappliedSlash {
+                     validator: offender,
+                     penalty,
+                     others,
+                     ..
+                 }| {
+                    Self::slash_nominators(&offender, others, slasher.treasury_account());
+                    match slasher.try_slash(&offender, penalty) {
+                        SlashOutcome::Slashed(actual_slashed) => {
+                            Self::record_validator_slash(&offender, actual_slashed);
+                            debug!(
+                                "Slash the offender:{:?} for penalty {:?} successfully",
+                                offender, penalty
+                            );
+                            None
+                        }
+                        SlashOutcome::InsufficientSlash(actual_slashed) => {
+                            Self::record_validator_slash(&offender, actual_slashed);
+                            debug!(
+                                "Insufficient reward pot balance of {:?}, actual slashed:{:?}",
+                                offender, actual_slashed
+                            );
+                            chill_offender_safe(offender)
+                        }
+                        SlashOutcome::SlashFailed(e) => {
+                            debug!(
+                                "Slash the offender {:?} for {:?} somehow failed: {:?}",
+                                offender, penalty, e
+                            );
+                            // we still chill the offender even the slashing failed as currently
+                            // the offender is only the authorties without running a node.
+                            //
+                            // TODO: Reconsider this once https://github.com/paritytech/substrate/pull/7127
+                            // is merged.
+                            chill_offender_safe(offender)
+                        }
                     }
+                },
+            )
+            .collect()
+    }
+
+    /// Returns the balances to be slashed from each nominator of the offender,
+    /// `NominatorSlashFraction` of what the nominator has bonded to the offender,
+    /// including the unbonded ones which are still locked.
+    ///
+    /// The self-bonded balances of the offender are slashed as well.
+    ///
+    /// The weight of reading the nominations is registered to the current block.
+    fn nominator_slashes_of(offender: &T::AccountId) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        let fraction = Self::nominator_slash_fraction();
+        if !Self::nominator_slashing_enabled() || fraction.is_zero() {
+            return Vec::new();
+        }
+        let mut reads: Weight = 0;
+        let slashes = NominatorsOf::<T>::iter_prefix(offender)
+            .filter_map(|(nominator, _)| {
+                reads += 2;
+                let exposure =
+                    Self::nomination_exposure(&Nominations::<T>::get(&nominator, offender));
+                let slash = fraction.mul(exposure);
+                if slash.is_zero() {
+                    None
+                } else {
+                    Some((nominator, slash))
                 }
             })
-            .collect()
+            .collect();
+        <frame_system::Module<T>>::register_extra_weight_unchecked(
+            T::DbWeight::get().reads(reads),
+            DispatchClass::Mandatory,
+        );
+        slashes
+    }
+
+    /// Returns the bonded balances of the nomination, including the unbonded ones.
+    fn nomination_exposure(
+        ledger: &NominatorLedger<BalanceOf<T>, VoteWeight, T::BlockNumber>,
+    ) -> BalanceOf<T> {
+        ledger
+            .unbonded_chunks
+            .iter()
+            .fold(ledger.nomination, |acc, chunk| acc + chunk.value)
+    }
+
+    /// Slashes the nominators of the offender recorded at the time of the offence.
+    ///
+    /// The slash is taken from the current nomination and the unbonded chunks pro rata,
+    /// capped by what the nominator still has bonded to the offender.
+    fn slash_nominators(
+        offender: &T::AccountId,
+        nominator_slashes: Vec<(T::AccountId, BalanceOf<T>)>,
+        treasury_account: &T::AccountId,
+    ) {
+        for (nominator, slash) in nominator_slashes {
+            let ledger = Nominations::<T>::get(&nominator, offender);
+            let exposure = Self::nomination_exposure(&ledger);
+            if exposure.is_zero() {
+                continue;
+            }
+            let fraction = Perbill::from_rational_approximation(slash.min(exposure), exposure);
+            let bonded_slash = fraction.mul(ledger.nomination);
+            let unbonded_slash = ledger
+                .unbonded_chunks
+                .iter()
+                .fold(Zero::zero(), |acc: BalanceOf<T>, chunk| {
+                    acc + fraction.mul(chunk.value)
+                });
+            let total_slash = bonded_slash + unbonded_slash;
+            if total_slash.is_zero() {
+                continue;
+            }
+
+            if !bonded_slash.is_zero() {
+                Self::update_vote_weight(&nominator, offender, Delta::Sub(bonded_slash));
+            }
+            Nominations::<T>::mutate(&nominator, offender, |ledger| {
+                for chunk in ledger.unbonded_chunks.iter_mut() {
+                    chunk.value -= fraction.mul(chunk.value);
+                }
+                ledger
+                    .unbonded_chunks
+                    .retain(|chunk| !chunk.value.is_zero());
+            });
+
+            // Release the slashed balances from the locks before taking them away.
+            Locks::<T>::mutate(&nominator, |locks| {
+                for (locked_type, slash) in [
+                    (LockedType::Bonded, bonded_slash),
+                    (LockedType::BondedWithdrawal, unbonded_slash),
+                ]
+                .iter()
+                {
+                    if let Some(locked) = locks.get_mut(locked_type) {
+                        *locked = locked.saturating_sub(*slash);
+                        if locked.is_zero() {
+                            locks.remove(locked_type);
+                        }
+                    }
+                }
+                let staking_locked = locks
+                    .values()
+                    .fold(Zero::zero(), |acc: BalanceOf<T>, x| acc + *x);
+                Self::set_lock(&nominator, staking_locked);
+            });
+
+            let (imbalance, _) = T::Currency::slash(&nominator, total_slash);
+            T::Currency::resolve_creating(treasury_account, imbalance);
+
+            debug!(
+                "Slash the nominator:{:?} of offender:{:?} for {:?}",
+                nominator, offender, total_slash
+            );
+            Self::deposit_event(Event::<T>::NominatorSlashed(
+                nominator,
+                offender.clone(),
+                total_slash,
+            ));
+        }
     }
 }
@@ -281,6 +281,53 @@ fn unbond_should_work() {
     });
 }
 
+#[test]
+fn cancel_unbond_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        assert_err!(
+            XStaking::cancel_unbond(Origin::signed(1), 2, 0),
+            Error::<Test>::EmptyUnbondedChunks
+        );
+
+        t_system_block_number_inc(1);
+        assert_ok!(t_bond(1, 2, 10));
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(1, 2, 5));
+
+        assert_err!(
+            XStaking::cancel_unbond(Origin::signed(1), 2, 1),
+            Error::<Test>::InvalidUnbondedIndex
+        );
+
+        // Block 4
+        t_system_block_number_inc(1);
+        assert_ok!(XStaking::cancel_unbond(Origin::signed(1), 2, 0));
+        assert_bonded_locks(1, 10 + 10);
+        assert!(!Locks::<Test>::get(&1).contains_key(&LockedType::BondedWithdrawal));
+
+        assert_eq!(
+            <ValidatorLedgers<Test>>::get(2),
+            ValidatorLedger {
+                total_nomination: 30,
+                last_total_vote_weight: 30 + 20 * 2 + 25,
+                last_total_vote_weight_update: 4,
+            }
+        );
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2),
+            NominatorLedger {
+                nomination: 10,
+                last_vote_weight: 10 + 5,
+                last_vote_weight_update: 4,
+                unbonded_chunks: vec![],
+            }
+        );
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::staking(RawEvent::UnbondCancelled(1, 2, 5))));
+    });
+}
+
 #[test]
 fn rebond_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -410,6 +457,161 @@ fn withdraw_unbond_should_work() {
     });
 }
 
+#[test]
+fn unbond_all_and_merge_chunks_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_system_block_number_inc(1);
+        t_issue_pcx(1, 100);
+        assert_ok!(t_bond(1, 2, 30));
+        MaximumUnbondedChunkSize::put(1);
+
+        // Block 3, the era length of mock is 50 * 3 = 150 blocks.
+        assert_ok!(t_unbond(1, 2, 5));
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2).unbonded_chunks,
+            vec![Unbonded {
+                value: 5,
+                locked_until: DEFAULT_BONDING_DURATION + 3
+            }]
+        );
+
+        // The chunks falling in the same era bucket are merged even if the
+        // maximum chunk size has been reached.
+        t_system_block_number_inc(97);
+        assert_ok!(t_unbond(1, 2, 5));
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2).unbonded_chunks,
+            vec![Unbonded {
+                value: 10,
+                locked_until: DEFAULT_BONDING_DURATION + 100
+            }]
+        );
+
+        t_system_block_number_inc(100);
+        assert_err!(t_unbond(1, 2, 5), Error::<Test>::NoMoreUnbondChunks);
+        assert_err!(
+            XStaking::unbond_all(Origin::signed(1), 2),
+            Error::<Test>::NoMoreUnbondChunks
+        );
+
+        MaximumUnbondedChunkSize::put(10);
+        assert_ok!(XStaking::unbond_all(Origin::signed(1), 2));
+        assert_eq!(XStaking::bonded_to(&1, &2), 0);
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2).unbonded_chunks,
+            vec![
+                Unbonded {
+                    value: 10,
+                    locked_until: DEFAULT_BONDING_DURATION + 100
+                },
+                Unbonded {
+                    value: 20,
+                    locked_until: DEFAULT_BONDING_DURATION + 200
+                }
+            ]
+        );
+        assert_bonded_withdrawal_locks(1, 30);
+        assert_eq!(<ValidatorLedgers<Test>>::get(2).total_nomination, 20);
+
+        assert_err!(
+            XStaking::unbond_all(Origin::signed(1), 2),
+            Error::<Test>::ZeroBalance
+        );
+    });
+}
+
+#[test]
+fn merged_unbonded_chunk_should_extend_the_lock() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_system_block_number_inc(1);
+        t_issue_pcx(1, 100);
+        assert_ok!(t_bond(1, 2, 30));
+
+        assert_ok!(t_unbond(1, 2, 5));
+        let locked_until = <Nominations<Test>>::get(1, 2).unbonded_chunks[0].locked_until;
+        t_system_block_number_inc(10);
+        assert_ok!(t_unbond(1, 2, 5));
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2).unbonded_chunks,
+            vec![Unbonded {
+                value: 10,
+                locked_until: locked_until + 10
+            }]
+        );
+
+        // The balances unbonded first are locked until the merged chunk is due.
+        System::set_block_number(locked_until + 1);
+        assert_err!(
+            t_withdraw_unbonded(1, 2, 0),
+            Error::<Test>::UnbondedWithdrawalNotYetDue
+        );
+        System::set_block_number(locked_until + 11);
+        assert_ok!(t_withdraw_unbonded(1, 2, 0));
+        assert_bonded_withdrawal_locks(1, 0);
+    });
+}
+
+#[test]
+fn withdraw_unbonded_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_system_block_number_inc(1);
+        t_issue_pcx(1, 100);
+        assert_err!(
+            XStaking::withdraw_unbonded_all(Origin::signed(1), 2),
+            Error::<Test>::EmptyUnbondedChunks
+        );
+
+        assert_ok!(t_bond(1, 2, 20));
+        assert_ok!(t_bond(1, 3, 30));
+        assert_ok!(t_unbond(1, 2, 10));
+        assert_ok!(t_unbond(1, 3, 30));
+        t_system_block_number_inc(200);
+        assert_ok!(t_unbond(1, 2, 10));
+        assert_bonded_withdrawal_locks(1, 50);
+
+        assert_err!(
+            XStaking::withdraw_unbonded_all(Origin::signed(1), 2),
+            Error::<Test>::UnbondedWithdrawalNotYetDue
+        );
+
+        assert_err!(
+            XStaking::withdraw_unbonded_all(Origin::signed(1), 1),
+            Error::<Test>::InvalidNominationsWitness
+        );
+
+        // Only the chunks unbonded at block 3 are due.
+        t_system_block_number_inc(DEFAULT_BONDING_DURATION - 200 + 1);
+        let usable_balance = Balances::usable_balance(&1);
+        assert_ok!(XStaking::withdraw_unbonded_all(Origin::signed(1), 2));
+        assert_eq!(Balances::usable_balance(&1), usable_balance + 40);
+        assert_bonded_withdrawal_locks(1, 10);
+        assert!(<Nominations<Test>>::get(1, 3).unbonded_chunks.is_empty());
+        assert_eq!(
+            <Nominations<Test>>::get(1, 2).unbonded_chunks,
+            vec![Unbonded {
+                value: 10,
+                locked_until: DEFAULT_BONDING_DURATION + 203
+            }]
+        );
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::staking(RawEvent::Withdrawn(1, 40))));
+
+        t_system_block_number_inc(200);
+        assert_ok!(XStaking::withdraw_unbonded_all(Origin::signed(1), 2));
+        assert_bonded_withdrawal_locks(1, 0);
+        assert_eq!(
+            frame_system::Account::<Test>::get(&1).data,
+            pallet_balances::AccountData {
+                free: 200,
+                reserved: 0,
+                misc_frozen: 10,
+                fee_frozen: 10,
+            }
+        );
+    });
+}
+
 #[test]
 fn regular_staking_should_work() {
     ExtBuilder::default().build_and_execute(|| {
@@ -896,3 +1098,549 @@ fn migration_session_offset_should_work() {
         );
     });
 }
+
+#[test]
+fn commission_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_make_a_validator_candidate(123, 100);
+        assert_eq!(XStaking::commission_of(&123), Perbill::from_percent(10));
+
+        t_start_session(1);
+        assert_err!(
+            XStaking::set_commission(Origin::signed(124), Perbill::from_percent(20)),
+            Error::<Test>::NotValidator
+        );
+        assert_ok!(XStaking::set_commission(
+            Origin::signed(123),
+            Perbill::from_percent(20)
+        ));
+        assert_eq!(
+            XStaking::validators(123).pending_commission,
+            Some(PendingCommission {
+                commission: Perbill::from_percent(20),
+                effective_era: 2,
+            })
+        );
+
+        // The commission does not change in the next era.
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(1));
+        assert_eq!(XStaking::commission_of(&123), Perbill::from_percent(10));
+
+        t_start_session(6);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(2));
+        assert_eq!(XStaking::commission_of(&123), Perbill::from_percent(20));
+        assert_eq!(XStaking::validators(123).pending_commission, None);
+
+        let free_before = Balances::free_balance(123);
+        let pot_before = t_reward_pot_balance(123);
+        XStaking::reward_active_validator(&123, 1000);
+        assert_eq!(Balances::free_balance(123), free_before + 200);
+        assert_eq!(t_reward_pot_balance(123), pot_before + 800);
+
+        // Lowering the maximum caps the commission immediately.
+        assert_ok!(XStaking::set_maximum_validator_commission(
+            Origin::root(),
+            Perbill::from_percent(5)
+        ));
+        assert_eq!(XStaking::commission_of(&123), Perbill::from_percent(5));
+        assert_err!(
+            XStaking::set_commission(Origin::signed(123), Perbill::from_percent(6)),
+            Error::<Test>::CommissionTooHigh
+        );
+    });
+}
+
+#[test]
+fn migrate_validator_profile_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        // the same layout as `ValidatorProfileV1`
+        let old_profile = (5u64, true, Some(3u64), b"old".to_vec());
+        frame_support::storage::unhashed::put(
+            &Validators::<Test>::hashed_key_for(999),
+            &old_profile,
+        );
+        StorageVersion::put(Releases::V1_0_0);
+
+        XStaking::migrate_validator_profile();
+        assert_eq!(XStaking::storage_version(), Releases::V2_0_0);
+        let profile = XStaking::validators(999);
+        assert_eq!(profile.registered_at, 5);
+        assert!(profile.is_chilled);
+        assert_eq!(profile.last_chilled, Some(3));
+        assert_eq!(profile.referral_id, b"old".to_vec());
+        assert_eq!(profile.commission, DEFAULT_VALIDATOR_COMMISSION);
+        assert_eq!(profile.pending_commission, None);
+    });
+}
+
+#[test]
+fn restake_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 100);
+        t_issue_pcx(t_2, 100);
+        t_make_a_validator_candidate(123, 1_000_000_000_000);
+
+        assert_ok!(t_bond(t_1, 123, 100));
+        assert_ok!(XStaking::set_restake(Origin::signed(t_1), true));
+        assert!(XStaking::is_restake(&t_1));
+        assert!(XStaking::nominator_info_of(t_1).restake);
+
+        t_start_session(1);
+        t_system_block_number_inc(1);
+
+        let dividend = XStaking::compute_dividend_at(&t_1, &123, System::block_number()).unwrap();
+        assert!(dividend > 0);
+        assert_ok!(XStaking::claim(Origin::signed(t_1), 123));
+        assert_eq!(XStaking::bonded_to(&t_1, &123), 100 + dividend);
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend);
+        assert_bonded_locks(t_1, 100 + dividend);
+        assert_eq!(
+            <Nominations<Test>>::get(t_1, 123).last_vote_weight_update,
+            System::block_number()
+        );
+
+        // Anyone can trigger the payout on behalf of the nominator.
+        t_system_block_number_inc(1);
+        let dividend2 = XStaking::compute_dividend_at(&t_1, &123, System::block_number()).unwrap();
+        assert_ok!(XStaking::payout_for(Origin::signed(t_2), t_1, 123));
+        assert_eq!(XStaking::bonded_to(&t_1, &123), 100 + dividend + dividend2);
+        assert_eq!(XStaking::free_balance(&t_2), 100);
+
+        // The dividend stays free once the restake mode is turned off.
+        assert_ok!(XStaking::set_restake(Origin::signed(t_1), false));
+        assert!(!XStaking::is_restake(&t_1));
+        t_system_block_number_inc(1);
+        let bonded = XStaking::bonded_to(&t_1, &123);
+        let dividend3 = XStaking::compute_dividend_at(&t_1, &123, System::block_number()).unwrap();
+        assert_ok!(XStaking::payout_for(Origin::signed(t_2), t_1, 123));
+        assert_eq!(XStaking::bonded_to(&t_1, &123), bonded);
+        assert_eq!(
+            XStaking::free_balance(&t_1),
+            100 + dividend + dividend2 + dividend3
+        );
+    });
+}
+
+#[test]
+fn restake_over_acceptable_votes_should_keep_dividend_free() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(XStaking::set_restake(Origin::signed(t_1), true));
+
+        t_start_session(1);
+        t_system_block_number_inc(1);
+
+        // Validator 1 can accept at most 10 * 10 votes.
+        let dividend = XStaking::compute_dividend_at(&t_1, &1, System::block_number()).unwrap();
+        assert!(dividend > 100);
+        assert_ok!(XStaking::claim(Origin::signed(t_1), 1));
+        assert_eq!(XStaking::bonded_to(&t_1, &1), 10);
+        assert_eq!(XStaking::free_balance(&t_1), 100 + dividend);
+    });
+}
+
+#[test]
+fn claim_all_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        t_issue_pcx(t_1, 100);
+        XStaking::mint(&888, (FIXED_TOTAL / 2) as u128);
+
+        assert_ok!(t_bond(t_1, 1, 10));
+        assert_ok!(t_bond(t_1, 2, 10));
+        assert_ok!(t_bond(t_1, 3, 10));
+
+        t_start_session(1);
+        t_system_block_number_inc(1);
+
+        let current_block = System::block_number();
+        let dividends = [1, 2, 3]
+            .iter()
+            .map(|v| XStaking::compute_dividend_at(&t_1, v, current_block).unwrap())
+            .collect::<Vec<_>>();
+
+        assert_err!(
+            XStaking::claim_all(Origin::signed(t_1), 2),
+            Error::<Test>::InvalidNominationsWitness
+        );
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 3));
+        assert_eq!(
+            XStaking::free_balance(&t_1),
+            100 + dividends.iter().sum::<Balance>()
+        );
+        for v in [1, 2, 3].iter() {
+            assert_eq!(
+                <Nominations<Test>>::get(t_1, v).last_vote_weight_update,
+                current_block
+            );
+        }
+
+        // Claim again in the same block, all the claims fail due to the zero vote weight.
+        assert_ok!(XStaking::claim_all(Origin::signed(t_1), 3));
+        let failed = System::events()
+            .into_iter()
+            .filter(|e| match &e.event {
+                MetaEvent::staking(RawEvent::ClaimFailed(who, _, _)) => *who == t_1,
+                _ => false,
+            })
+            .count();
+        assert_eq!(failed, 3);
+    });
+}
+
+fn t_election_setup() -> (AccountId, AccountId, AccountId) {
+    let (a, b, c) = (11, 12, 13);
+    t_make_a_validator_candidate(a, 10);
+    t_make_a_validator_candidate(b, 10);
+    t_make_a_validator_candidate(c, 10);
+
+    let (x, y) = (1111, 2222);
+    t_issue_pcx(x, 1000);
+    t_issue_pcx(y, 1000);
+    assert_ok!(t_bond(x, a, 100));
+    assert_ok!(t_bond(x, b, 10));
+    assert_ok!(t_bond(y, c, 45));
+
+    (a, b, c)
+}
+
+#[test]
+fn election_strategy_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let (a, b, c) = t_election_setup();
+
+        let candidates = vec![(110, a), (55, c), (20, b)];
+        assert_eq!(
+            <TopVotes as ElectionStrategy<Test>>::elect(2, candidates.clone()),
+            Some(vec![a, c])
+        );
+
+        // The stake of x approves both a and b, which makes b more supported than c.
+        let mut winners =
+            <SequentialPhragmen as ElectionStrategy<Test>>::elect(2, candidates).unwrap();
+        winners.sort();
+        assert_eq!(winners, vec![a, b]);
+    });
+}
+
+#[test]
+fn offchain_election_should_work() {
+    use frame_support::unsigned::ValidateUnsigned;
+
+    ExtBuilder::default().build_and_execute(|| {
+        let (a, b, c) = t_election_setup();
+        ValidatorCount::put(2);
+        assert!(XStaking::compute_election_solution().is_none());
+
+        // The snapshot is taken at the start of the final session.
+        t_start_session(1);
+        assert_eq!(XStaking::current_era(), Some(0));
+        assert!(XStaking::is_current_session_final());
+        assert!(XStaking::snapshot_voters().is_some());
+
+        // The nominations after the snapshot are not taken into account.
+        let z = 3333;
+        t_issue_pcx(z, 1000);
+        assert_ok!(t_bond(z, c, 500));
+
+        let solution = XStaking::compute_election_solution().unwrap();
+        assert_eq!(solution.era, 1);
+        let mut winners = solution.winners.clone();
+        winners.sort();
+        assert_eq!(winners, vec![a, b]);
+
+        let mut bogus_score = solution.clone();
+        bogus_score.score[0] += 1;
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), bogus_score),
+            Error::<Test>::OffchainElectionBogusScore
+        );
+
+        let mut bogus_winner = solution.clone();
+        bogus_winner.winners[0] = 9999;
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), bogus_winner),
+            Error::<Test>::OffchainElectionBogusWinner
+        );
+
+        let mut bogus_assignment = solution.clone();
+        bogus_assignment.winners = vec![a, c];
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), bogus_assignment),
+            Error::<Test>::OffchainElectionBogusAssignment
+        );
+
+        let call = Call::submit_election_solution(solution.clone());
+        assert!(XStaking::validate_unsigned(TransactionSource::Local, &call).is_ok());
+        assert!(XStaking::validate_unsigned(TransactionSource::InBlock, &call).is_ok());
+        assert!(XStaking::validate_unsigned(TransactionSource::External, &call).is_err());
+
+        assert_ok!(XStaking::submit_election_solution(
+            Origin::none(),
+            solution.clone()
+        ));
+        assert_eq!(XStaking::queued_elected().map(|q| q.era), Some(1));
+        assert_err!(
+            XStaking::submit_election_solution(Origin::none(), solution),
+            Error::<Test>::OffchainElectionWeakSubmission
+        );
+
+        // The queued winners are used in the new era instead of the top voted a and c.
+        t_start_session(2);
+        assert_eq!(XStaking::current_era(), Some(1));
+        assert!(XStaking::queued_elected().is_none());
+        assert!(XStaking::snapshot_voters().is_none());
+        t_start_session(3);
+        let mut validators = Session::validators();
+        validators.sort();
+        assert_eq!(validators, vec![a, b]);
+    });
+}
+
+#[test]
+fn era_history_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        t_start_session(1);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(0));
+
+        let era_0 = XStaking::era_rewards(0);
+        assert!(era_0.total_reward > 0);
+        assert_eq!(
+            era_0.validators.values().map(|r| r.reward).sum::<Balance>(),
+            era_0.total_reward
+        );
+        // The reward pot balance of the active era is the current one.
+        assert_eq!(
+            era_0.validators[&1].reward_pot_balance,
+            t_reward_pot_balance(1)
+        );
+
+        // Validator 2 is slashed in session 2.
+        let mut offenders = BTreeMap::new();
+        offenders.insert(2, Perbill::zero());
+        SessionOffenders::<Test>::put(offenders);
+        t_start_session(2);
+        let slashed = System::events()
+            .into_iter()
+            .filter_map(|e| match e.event {
+                MetaEvent::staking(RawEvent::Slashed(2, value)) => Some(value),
+                _ => None,
+            })
+            .sum::<Balance>();
+        assert!(slashed > 0);
+        assert_eq!(XStaking::eras_validator_records(0, 2).slash, slashed);
+
+        // Era 0 is closed at the start of era 1.
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(1));
+        let pot_balance = t_reward_pot_balance(1);
+        assert_eq!(
+            XStaking::eras_validator_records(0, 1).reward_pot_balance,
+            pot_balance
+        );
+
+        t_start_session(4);
+        assert!(XStaking::eras_validator_records(1, 1).reward > 0);
+        let history = XStaking::validator_history(1, 2);
+        assert_eq!(history.keys().copied().collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(history[&0].reward_pot_balance, pot_balance);
+        assert_eq!(history[&1].reward_pot_balance, t_reward_pot_balance(1));
+        assert_eq!(
+            XStaking::validator_history(1, 1)
+                .keys()
+                .copied()
+                .collect::<Vec<_>>(),
+            vec![1]
+        );
+        assert!(XStaking::validator_history(1, 0).is_empty());
+
+        // The records older than `HISTORY_DEPTH` are pruned.
+        XStaking::close_era_records(HISTORY_DEPTH);
+        assert_eq!(XStaking::eras_staking_reward(0), 0);
+        assert!(!ErasValidatorRecords::<Test>::contains_key(0, 1));
+        assert!(ErasValidatorRecords::<Test>::contains_key(1, 1));
+    });
+}
+
+#[test]
+fn deferred_slash_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        SlashDeferDuration::put(1);
+        t_start_session(1);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(0));
+
+        let mut offenders = BTreeMap::new();
+        offenders.insert(2, Perbill::zero());
+        offenders.insert(3, Perbill::zero());
+        SessionOffenders::<Test>::put(offenders);
+
+        let slashed_events = || {
+            System::events()
+                .into_iter()
+                .filter_map(|e| match e.event {
+                    MetaEvent::staking(RawEvent::Slashed(who, value)) => Some((who, value)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The slashes are queued to era 1 instead of being applied right now.
+        t_start_session(2);
+        assert!(slashed_events().is_empty());
+        let unapplied = XStaking::unapplied_slashes(1);
+        assert_eq!(
+            unapplied.iter().map(|s| s.validator).collect::<Vec<_>>(),
+            vec![2, 3]
+        );
+        let penalty_of_2 = unapplied[0].penalty;
+        assert!(penalty_of_2 > 0);
+
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::signed(1), 1, vec![1]),
+            DispatchError::BadOrigin
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 1, vec![]),
+            Error::<Test>::NotSortedAndUnique
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 1, vec![1, 1]),
+            Error::<Test>::NotSortedAndUnique
+        );
+        assert_err!(
+            XStaking::cancel_deferred_slash(Origin::root(), 1, vec![2]),
+            Error::<Test>::InvalidSlashIndex
+        );
+        assert_ok!(XStaking::cancel_deferred_slash(Origin::root(), 1, vec![1]));
+        assert_eq!(XStaking::unapplied_slashes(1).len(), 1);
+
+        // The slash of 2 is applied at the start of era 1, 3 is spared.
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(1));
+        assert_eq!(slashed_events(), vec![(2, penalty_of_2)]);
+        assert!(XStaking::unapplied_slashes(1).is_empty());
+    });
+}
+
+#[test]
+fn nominator_slashing_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 1000);
+        t_issue_pcx(t_2, 1000);
+
+        assert_ok!(t_bond(t_1, 2, 100));
+        assert_ok!(t_bond(t_2, 3, 50));
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(t_1, 2, 20));
+        let locked_until = <Nominations<Test>>::get(t_1, 2).unbonded_chunks[0].locked_until;
+
+        let slash_validator_2 = |session_index| {
+            let mut offenders = BTreeMap::new();
+            offenders.insert(2, Perbill::zero());
+            SessionOffenders::<Test>::put(offenders);
+            t_start_session(session_index);
+        };
+
+        // The nominators are not slashed by default.
+        slash_validator_2(1);
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 80);
+        assert_eq!(Balances::free_balance(&t_1), 1000);
+
+        assert_err!(
+            XStaking::set_nominator_slashing(Origin::signed(1), true, Perbill::from_percent(10)),
+            DispatchError::BadOrigin
+        );
+        assert_ok!(XStaking::set_nominator_slashing(
+            Origin::root(),
+            true,
+            Perbill::from_percent(10)
+        ));
+
+        slash_validator_2(2);
+
+        // 10% of both the bonded and the unbonded are slashed.
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 72);
+        assert_eq!(
+            <Nominations<Test>>::get(t_1, 2).unbonded_chunks,
+            vec![Unbonded {
+                value: 18,
+                locked_until
+            }]
+        );
+        assert_bonded_locks(t_1, 72);
+        assert_bonded_withdrawal_locks(t_1, 18);
+        assert_eq!(Balances::free_balance(&t_1), 990);
+        assert_eq!(Balances::usable_balance(&t_1), 900);
+        assert_eq!(<ValidatorLedgers<Test>>::get(2).total_nomination, 18 + 72);
+        assert!(System::events()
+            .iter()
+            .any(|e| e.event == MetaEvent::staking(RawEvent::NominatorSlashed(t_1, 2, 10))));
+
+        // The self-bonded balances are slashed as well.
+        assert_eq!(XStaking::bonded_to(&2, &2), 18);
+        // The nominators of other validators are not affected.
+        assert_eq!(XStaking::bonded_to(&t_2, &3), 50);
+        assert_eq!(Balances::free_balance(&t_2), 1000);
+
+        // The slashed unbonded chunk can still be withdrawn.
+        t_system_block_number_inc(locked_until);
+        let usable_balance = Balances::usable_balance(&t_1);
+        assert_ok!(t_withdraw_unbonded(t_1, 2, 0));
+        assert_eq!(Balances::usable_balance(&t_1), usable_balance + 18);
+        assert_bonded_withdrawal_locks(t_1, 0);
+    });
+}
+
+#[test]
+fn deferred_nominator_slashing_should_work() {
+    ExtBuilder::default().build_and_execute(|| {
+        let t_1 = 1111;
+        let t_2 = 2222;
+        t_issue_pcx(t_1, 1000);
+        t_issue_pcx(t_2, 1000);
+        assert_ok!(t_bond(t_1, 2, 100));
+        assert_ok!(XStaking::set_nominator_slashing(
+            Origin::root(),
+            true,
+            Perbill::from_percent(10)
+        ));
+        SlashDeferDuration::put(1);
+        t_start_session(1);
+
+        let mut offenders = BTreeMap::new();
+        offenders.insert(2, Perbill::zero());
+        SessionOffenders::<Test>::put(offenders);
+        t_start_session(2);
+
+        // The nominators are recorded at the time of the offence.
+        let others = XStaking::unapplied_slashes(1)[0].others.clone();
+        assert!(others.contains(&(t_1, 10)));
+        assert!(others.iter().all(|(nominator, _)| *nominator != t_2));
+
+        // Unbonding after the offence does not escape the slash,
+        // bonding after the offence is not slashed.
+        t_system_block_number_inc(1);
+        assert_ok!(t_unbond(t_1, 2, 50));
+        assert_ok!(t_bond(t_2, 2, 100));
+
+        t_start_session(3);
+        assert_eq!(XStaking::active_era().map(|e| e.index), Some(1));
+        assert_eq!(XStaking::bonded_to(&t_1, &2), 45);
+        assert_eq!(
+            <Nominations<Test>>::get(t_1, 2).unbonded_chunks[0].value,
+            45
+        );
+        assert_eq!(Balances::free_balance(&t_1), 990);
+        assert_eq!(XStaking::bonded_to(&t_2, &2), 100);
+        assert_eq!(Balances::free_balance(&t_2), 1000);
+    });
+}
@@ -6,17 +6,20 @@ use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 
+use sp_npos_elections::ElectionScore;
 use sp_runtime::{
     traits::{SaturatedConversion, Saturating},
-    DispatchError, DispatchResult, RuntimeDebug,
+    DispatchError, DispatchResult, Perbill, RuntimeDebug,
 };
 
 use chainx_primitives::{AssetId, ReferralId};
 use xp_logging::debug;
 use xp_mining_common::{RewardPotAccountFor, WeightType};
-use xp_mining_staking::MiningPower;
+use xp_mining_staking::{MiningPower, SessionIndex};
 
-use crate::{AssetMining, BalanceOf, EraIndex, Event, Module, Trait};
+use crate::{
+    constants::DEFAULT_VALIDATOR_COMMISSION, AssetMining, BalanceOf, EraIndex, Event, Module, Trait,
+};
 
 pub type VoteWeight = WeightType;
 
@@ -108,6 +111,90 @@ pub struct ValidatorProfile<BlockNumber> {
     /// Referral identity that belongs to the validator.
     #[cfg_attr(feature = "std", serde(with = "xp_rpc::serde_text"))]
     pub referral_id: ReferralId,
+    /// Proportion of the session reward minted directly to the validator,
+    /// the rest goes to its reward pot.
+    pub commission: Perbill,
+    /// The commission change that has been scheduled but not yet taken effect.
+    pub pending_commission: Option<PendingCommission>,
+}
+
+/// A commission change of validator, which takes effect at the start of `effective_era`.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct PendingCommission {
+    /// The new commission.
+    pub commission: Perbill,
+    /// Index of the era from which the new commission is applied.
+    pub effective_era: EraIndex,
+}
+
+/// The layout of `ValidatorProfile` before the commission was introduced.
+#[derive(Encode, Decode)]
+pub(crate) struct ValidatorProfileV1<BlockNumber> {
+    registered_at: BlockNumber,
+    is_chilled: bool,
+    last_chilled: Option<BlockNumber>,
+    referral_id: ReferralId,
+}
+
+impl<BlockNumber> ValidatorProfileV1<BlockNumber> {
+    /// Upgrade to the current layout, using the previously hard-coded commission.
+    pub(crate) fn upgrade(self) -> ValidatorProfile<BlockNumber> {
+        ValidatorProfile {
+            registered_at: self.registered_at,
+            is_chilled: self.is_chilled,
+            last_chilled: self.last_chilled,
+            referral_id: self.referral_id,
+            commission: DEFAULT_VALIDATOR_COMMISSION,
+            pending_commission: None,
+        }
+    }
+}
+
+/// The storage version of the XStaking module.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug)]
+pub enum Releases {
+    V1_0_0,
+    /// `ValidatorProfile` has the `commission` and `pending_commission` fields.
+    V2_0_0,
+    /// The nominators of each validator are indexed in `NominatorsOf`.
+    V3_0_0,
+}
+
+impl Default for Releases {
+    fn default() -> Self {
+        Releases::V1_0_0
+    }
+}
+
+/// A pending slash of the validator, applied at the start of some era later.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct UnappliedSlash<AccountId, Balance> {
+    /// The offending validator.
+    pub validator: AccountId,
+    /// The penalty to be slashed from the reward pot of the validator.
+    pub penalty: Balance,
+    /// Index of the session in which the offence was reported.
+    pub slash_session: SessionIndex,
+    /// The nominators of the validator at the time of the offence and the balances
+    /// to be slashed from each of them.
+    pub others: Vec<(AccountId, Balance)>,
+}
+
+/// Reward and slash records of a validator in an era.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ValidatorEraRecord<Balance> {
+    /// Total session rewards issued to the validator, including the commission.
+    pub reward: Balance,
+    /// Total balance slashed from the reward pot.
+    pub slash: Balance,
+    /// Balance of the reward pot at the end of the era.
+    pub reward_pot_balance: Balance,
 }
 
 /// Information regarding the active era (era in used in session).
@@ -142,6 +229,33 @@ impl Default for Forcing {
     }
 }
 
+/// The distribution of a voter's stake, (voter, [(validator, ratio)]).
+pub type ElectionAssignment<AccountId> = (AccountId, Vec<(AccountId, Perbill)>);
+
+/// Election solution of the next era computed offchain.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct ElectionSolution<AccountId> {
+    /// Index of the era this solution is for.
+    pub era: EraIndex,
+    /// Elected validators.
+    pub winners: Vec<AccountId>,
+    /// Distribution of each nominator's stake to the winners.
+    pub assignments: Vec<ElectionAssignment<AccountId>>,
+    /// Claimed score of the solution.
+    pub score: ElectionScore,
+}
+
+/// A checked election solution waiting to be applied at the start of era `era`.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode, RuntimeDebug)]
+pub struct QueuedElectionResult<AccountId> {
+    /// Index of the era this result is for.
+    pub era: EraIndex,
+    /// Elected validators.
+    pub winners: Vec<AccountId>,
+    /// Score of the solution.
+    pub score: ElectionScore,
+}
+
 /// Top level shares of various reward destinations.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Encode, Decode, RuntimeDebug)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
@@ -246,6 +360,11 @@ impl<T: Trait> Slasher<T> {
         Self(treasury_account)
     }
 
+    /// Returns the account receiving the slashed balances.
+    pub fn treasury_account(&self) -> &T::AccountId {
+        &self.0
+    }
+
     /// Try to slash the reward pot of the offender.
     ///
     /// If the reward pot of offender has enough balance to cover the slashing,
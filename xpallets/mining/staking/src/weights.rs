@@ -44,6 +44,18 @@ pub trait WeightInfo {
     fn set_validator_bonding_duration() -> Weight;
     fn set_minimum_penalty() -> Weight;
     fn set_sessions_per_era() -> Weight;
+    fn set_commission() -> Weight;
+    fn set_maximum_validator_commission() -> Weight;
+    fn set_restake() -> Weight;
+    fn payout_for() -> Weight;
+    fn claim_all(n: u32) -> Weight;
+    fn submit_election_solution(w: u32, a: u32) -> Weight;
+    fn cancel_deferred_slash(s: u32) -> Weight;
+    fn set_slash_defer_duration() -> Weight;
+    fn set_nominator_slashing() -> Weight;
+    fn unbond_all() -> Weight;
+    fn withdraw_unbonded_all(n: u32) -> Weight;
+    fn cancel_unbond() -> Weight;
 }
 
 /// Weights for xpallet_mining_staking using the Substrate node and recommended hardware.
@@ -107,6 +119,68 @@ impl<T: frame_system::Trait> WeightInfo for SubstrateWeight<T> {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(3 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_validator_commission() -> Weight {
+        (4_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_restake() -> Weight {
+        (12_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn payout_for() -> Weight {
+        (386_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(15 as Weight))
+            .saturating_add(T::DbWeight::get().writes(9 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add((388_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().reads((15 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    fn submit_election_solution(w: u32, a: u32) -> Weight {
+        (95_000_000 as Weight)
+            .saturating_add((46_000_000 as Weight).saturating_mul(w as Weight))
+            .saturating_add((31_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(T::DbWeight::get().reads(8 as Weight))
+            .saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(w as Weight)))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (41_000_000 as Weight)
+            .saturating_add((5_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(T::DbWeight::get().reads(1 as Weight))
+            .saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (2_000_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+    }
+    fn set_nominator_slashing() -> Weight {
+        (3_000_000 as Weight).saturating_add(T::DbWeight::get().writes(2 as Weight))
+    }
+    fn unbond_all() -> Weight {
+        (158_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(7 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw_unbonded_all(n: u32) -> Weight {
+        (96_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(T::DbWeight::get().reads(2 as Weight))
+            .saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(T::DbWeight::get().writes(2 as Weight))
+            .saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn cancel_unbond() -> Weight {
+        (148_000_000 as Weight)
+            .saturating_add(T::DbWeight::get().reads(6 as Weight))
+            .saturating_add(T::DbWeight::get().writes(3 as Weight))
+    }
 }
 
 // For backwards compatibility and tests
@@ -169,4 +243,66 @@ impl WeightInfo for () {
     fn set_sessions_per_era() -> Weight {
         (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
     }
+    fn set_commission() -> Weight {
+        (31_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(3 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_maximum_validator_commission() -> Weight {
+        (4_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_restake() -> Weight {
+        (12_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn payout_for() -> Weight {
+        (386_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(15 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(9 as Weight))
+    }
+    fn claim_all(n: u32) -> Weight {
+        (28_000_000 as Weight)
+            .saturating_add((388_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((15 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes((9 as Weight).saturating_mul(n as Weight)))
+    }
+    fn submit_election_solution(w: u32, a: u32) -> Weight {
+        (95_000_000 as Weight)
+            .saturating_add((46_000_000 as Weight).saturating_mul(w as Weight))
+            .saturating_add((31_000_000 as Weight).saturating_mul(a as Weight))
+            .saturating_add(RocksDbWeight::get().reads(8 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(w as Weight)))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(a as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn cancel_deferred_slash(s: u32) -> Weight {
+        (41_000_000 as Weight)
+            .saturating_add((5_200_000 as Weight).saturating_mul(s as Weight))
+            .saturating_add(RocksDbWeight::get().reads(1 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_slash_defer_duration() -> Weight {
+        (2_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(1 as Weight))
+    }
+    fn set_nominator_slashing() -> Weight {
+        (3_000_000 as Weight).saturating_add(RocksDbWeight::get().writes(2 as Weight))
+    }
+    fn unbond_all() -> Weight {
+        (158_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(7 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
+    fn withdraw_unbonded_all(n: u32) -> Weight {
+        (96_000_000 as Weight)
+            .saturating_add((24_000_000 as Weight).saturating_mul(n as Weight))
+            .saturating_add(RocksDbWeight::get().reads(2 as Weight))
+            .saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+            .saturating_add(RocksDbWeight::get().writes(2 as Weight))
+            .saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+    }
+    fn cancel_unbond() -> Weight {
+        (148_000_000 as Weight)
+            .saturating_add(RocksDbWeight::get().reads(6 as Weight))
+            .saturating_add(RocksDbWeight::get().writes(3 as Weight))
+    }
 }